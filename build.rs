@@ -1,7 +1,7 @@
 use chrono::Utc;
 
 fn main() {
-    let utc_date = Utc::today();
+    let utc_date = Utc::now();
 
     println!(
         "cargo:rustc-env=RELEASE_DATE={}",
@@ -0,0 +1,96 @@
+//! Derive macros for the `IntoObject` and `FromObject` traits of tr-lang
+//!
+//! Both are derived for structs with named fields, which are turned into and made from a `Harita`
+//! with a key for every field. Use them through `tr_lang::mem`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Error, Fields, FieldsNamed, Generics};
+
+/// The named fields of the struct `input`, or an error pointing at what isn't one
+fn named_fields(input: &DeriveInput) -> Result<&FieldsNamed, Error> {
+    match &input.data {
+        Data::Struct(s) => match &s.fields {
+            Fields::Named(fields) => Ok(fields),
+            _ => Err(Error::new_spanned(&s.fields, "only structs with named fields can be converted")),
+        },
+        _ => Err(Error::new_spanned(&input.ident, "only structs with named fields can be converted")),
+    }
+}
+
+/// `generics` with every field type bound by `bound`
+fn bound_fields(generics: &Generics, fields: &FieldsNamed, bound: TokenStream2) -> Generics {
+    let mut generics = generics.clone();
+    let where_clause = generics.make_where_clause();
+    for field in fields.named.iter() {
+        let ty = &field.ty;
+        where_clause.predicates.push(parse_quote!(#ty: #bound));
+    }
+    generics
+}
+
+#[proc_macro_derive(IntoObject)]
+pub fn derive_into_object(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let fields = match named_fields(&input) {
+        Ok(fields) => fields,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let generics = bound_fields(&input.generics, fields, quote!(::tr_lang::mem::IntoObject));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let name = &input.ident;
+    let inserts = fields.named.iter().map(|field| {
+        let ident = field.ident.as_ref().unwrap();
+        let key = ident.to_string();
+        quote! {
+            map.insert(#key.to_string(), ::tr_lang::mem::IntoObject::into_object(self.#ident));
+        }
+    });
+    quote! {
+        impl #impl_generics ::tr_lang::mem::IntoObject for #name #ty_generics #where_clause {
+            fn into_object(self) -> ::tr_lang::mem::Object {
+                let mut map = ::std::collections::HashMap::new();
+                #(#inserts)*
+                ::tr_lang::mem::Object::Harita(::tr_lang::mem::Map::from(map))
+            }
+        }
+    }
+    .into()
+}
+
+#[proc_macro_derive(FromObject)]
+pub fn derive_from_object(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let fields = match named_fields(&input) {
+        Ok(fields) => fields,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let generics = bound_fields(&input.generics, fields, quote!(::tr_lang::mem::FromObject));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let name = &input.ident;
+    let inits = fields.named.iter().map(|field| {
+        let ident = field.ident.as_ref().unwrap();
+        let key = ident.to_string();
+        quote! {
+            #ident: ::tr_lang::mem::FromObject::from_object(
+                map.remove(#key).unwrap_or(::tr_lang::mem::Object::Hiç),
+            )
+            .map_err(|e| e.at(#key))?,
+        }
+    });
+    quote! {
+        impl #impl_generics ::tr_lang::mem::FromObject for #name #ty_generics #where_clause {
+            fn from_object(o: ::tr_lang::mem::Object) -> ::std::result::Result<Self, ::tr_lang::mem::ConversionError> {
+                let mut map = match o {
+                    ::tr_lang::mem::Object::Harita(map) => map.into_inner(),
+                    o => return Err(::tr_lang::mem::ConversionError::new("harita", &o)),
+                };
+                Ok(Self {
+                    #(#inits)*
+                })
+            }
+        }
+    }
+    .into()
+}
@@ -2,6 +2,7 @@
 use crate::interactive::QuietLevel;
 #[cfg(feature = "fmt")]
 use crate::fmt::{LineEnding, IndentOptions};
+use crate::runtime::{Permission, Permissions};
 use crate::util;
 use crate::utilbin;
 use std::env;
@@ -12,6 +13,7 @@ pub enum Subcommands {
     Byt,
     RunBytes,
     Command,
+    Disassemble,
     #[cfg(feature = "interactive")]
     Interact,
     #[cfg(feature = "fmt")]
@@ -23,6 +25,7 @@ pub struct Options {
     pub name: String,
     pub file: String,
     pub outfile: Option<String>,
+    #[allow(dead_code)]
     pub prd_out: bool,
     pub sub_cmd: Subcommands,
     pub help: bool,
@@ -30,9 +33,13 @@ pub struct Options {
     pub version: bool,
     pub lex_out: bool,
     pub prs_out: bool,
+    #[allow(dead_code)]
     pub argv: Vec<String>,
     pub license: bool,
     pub supress_warnings: bool,
+    pub json: bool,
+    pub optimize: bool,
+    pub permissions: Permissions,
     #[cfg(feature = "interactive")]
     pub quiet: QuietLevel,
     #[cfg(feature = "fmt")]
@@ -44,11 +51,15 @@ pub struct Options {
 pub fn parse_args() -> Options {
     let mut args: Vec<String> = env::args().collect();
 
-    let name = args.get(0).unwrap().to_string();
+    let name = args.first().unwrap().to_string();
     args.remove(0);
 
     let mut argv_m = false;
     let mut supress_warnings = false;
+    let mut json = false;
+    let mut optimize = false;
+    // everything is allowed unless a permission is given, then only what is given is
+    let mut permissions: Option<Permissions> = None;
     let mut argv: Vec<String> = vec![];
 
     #[cfg(feature = "interactive")]
@@ -80,12 +91,13 @@ pub fn parse_args() -> Options {
         }
     }
 
-    let sub_cmd = if args.len() >= 1 {
-        let s = match args.get(0).unwrap().as_str() {
+    let sub_cmd = if !args.is_empty() {
+        let s = match args.first().unwrap().as_str() {
             "y" | "yürüt" => Subcommands::Run,
             "b" | "byt" => Subcommands::Byt,
             "yb" | "yürbyt" => Subcommands::RunBytes,
             "k" | "komut" => Subcommands::Command,
+            "ayrıştır" | "dis" => Subcommands::Disassemble,
             #[cfg(feature = "interactive")]
             "i" | "inter" => Subcommands::Interact,
             #[cfg(feature = "fmt")]
@@ -99,7 +111,7 @@ pub fn parse_args() -> Options {
             "-L" | "--license" | "--lisans" => {
                 utilbin::print_license();
             }
-            a => util::error_print("unknown subcommand", format!("{}", a)),
+            a => util::error_print("unknown subcommand", a.to_string()),
         };
         args.remove(0);
         s
@@ -117,13 +129,13 @@ pub fn parse_args() -> Options {
     let file = if let Subcommands::Interact = sub_cmd {
         "".to_string()
     } else {
-        let file = args.get(0).expect("couldn't get <FILE>").to_string();
+        let file = args.first().expect("couldn't get <FILE>").to_string();
         args.remove(0);
         file
     };
     #[cfg(not(feature = "interactive"))]
     let file = {
-        let file = args.get(0).expect("couldn't get <FILE>").to_string();
+        let file = args.first().expect("couldn't get <FILE>").to_string();
         args.remove(0);
         file
     };
@@ -164,6 +176,22 @@ pub fn parse_args() -> Options {
             "-l" | "--lexer-çıktısı" => lex_out = true,
             "-u" | "--uyarıları-engelle" => supress_warnings = true,
             "-p" | "--parser-çıktısı" => prs_out = true,
+            "-j" | "--json" => json = true,
+            "-O" | "--optimize" | "--iyileştir" => optimize = true,
+            "--izinsiz" => {
+                permissions.get_or_insert_with(Permissions::none);
+            }
+            a if a.starts_with("--izin-") => {
+                let permission = match &a["--izin-".len()..] {
+                    "dosya" => Permission::File,
+                    "süreç" => Permission::Process,
+                    "ağ" => Permission::Network,
+                    "ffi" => Permission::Ffi,
+                    "girdi" => Permission::Input,
+                    _ => util::error_print("unknown argument", a.to_string()),
+                };
+                permissions = Some(permissions.unwrap_or_else(Permissions::none).allow(permission));
+            }
             "-o" | "-ç" | "--çıkış" => {
                 outs = true;
                 prd_out = true;
@@ -184,7 +212,7 @@ pub fn parse_args() -> Options {
             "-i" | "--indent" | "--girinti" => {
                 change_indent = true;
             }
-            a => util::error_print("unknown argument", format!("{}", a)),
+            a => util::error_print("unknown argument", a.to_string()),
         }
     }
 
@@ -201,6 +229,9 @@ pub fn parse_args() -> Options {
         sub_cmd,
         license,
         supress_warnings,
+        json,
+        optimize,
+        permissions: permissions.unwrap_or_default(),
         help_exitc: 0,
         #[cfg(feature = "interactive")]
         quiet,
@@ -1,10 +1,119 @@
-use crate::token::ParserToken;
+use super::{verify, Program};
+use crate::error::Error;
+use crate::store::VERSION;
+use crate::util::{get_lang, SupportedLanguage};
 use bincode;
+use std::convert::TryInto;
 
-pub fn to_bytecode(parsed: Vec<ParserToken>) -> Vec<u8> {
-    bincode::serialize(&parsed).unwrap()
+/// The first bytes of every .trbyt file
+pub const MAGIC: &[u8; 4] = b"TRBY";
+/// Version of the .trbyt layout, bumped whenever [`Program`] changes shape
+pub const FORMAT_VERSION: u16 = 2;
+
+/// Serializes `program` into a .trbyt file
+///
+/// The file is laid out as:
+/// - [`MAGIC`]
+/// - [`FORMAT_VERSION`] as a little endian `u16`
+/// - the length of the compiler version as a `u8`, followed by the version itself
+/// - the CRC-32 checksum of the rest of the file as a little endian `u32`
+/// - `program`, serialized with bincode
+pub fn to_bytecode(program: &Program) -> Vec<u8> {
+    let payload = bincode::serialize(program).unwrap();
+    let mut buf = Vec::with_capacity(payload.len() + 11 + VERSION.len());
+    buf.extend_from_slice(MAGIC);
+    buf.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+    buf.push(VERSION.len() as u8);
+    buf.extend_from_slice(VERSION.as_bytes());
+    buf.extend_from_slice(&crc32fast::hash(&payload).to_le_bytes());
+    buf.extend_from_slice(&payload);
+    buf
+}
+
+pub(super) fn invalid(file: &str, tr: String, en: String) -> Error {
+    let trace = vec![(0, 0, file.to_string(), None)];
+    match get_lang() {
+        SupportedLanguage::Turkish => Error::new("BytecodeHatası", &tr, trace, None),
+        SupportedLanguage::English => Error::new("BytecodeError", &en, trace, None),
+    }
+}
+
+/// Takes `n` bytes off the front of `buf`
+fn take<'a>(buf: &mut &'a [u8], n: usize, file: &str) -> Result<&'a [u8], Error> {
+    if buf.len() < n {
+        return Err(invalid(
+            file,
+            "dosya bytecode başlığı bitmeden sona eriyor".to_string(),
+            "file ends before the end of the bytecode header".to_string(),
+        ));
+    }
+    let (head, rest) = buf.split_at(n);
+    *buf = rest;
+    Ok(head)
+}
+
+/// Reads a .trbyt file written by [`to_bytecode`], `file` is where it was read from
+///
+/// Files with the wrong magic number, written by another format or compiler version, or whose
+/// checksum does not match are rejected, as are programs that fail to [`verify`].
+pub fn from_bytecode(bytecode: &[u8], file: &str) -> Result<Program, Error> {
+    let program = read_bytecode(bytecode, file)?;
+    verify(&program, file)?;
+    Ok(program)
 }
 
-pub fn from_bytecode(bytecode: &[u8]) -> Vec<ParserToken> {
-    bincode::deserialize(bytecode).unwrap()
+/// Same as [`from_bytecode`] without verifying the program, which is not safe to run
+pub fn read_bytecode(bytecode: &[u8], file: &str) -> Result<Program, Error> {
+    let mut buf = bytecode;
+    if take(&mut buf, MAGIC.len(), file)? != MAGIC {
+        return Err(invalid(
+            file,
+            "dosya bir tr-lang bytecode dosyası değil".to_string(),
+            "file is not a tr-lang bytecode file".to_string(),
+        ));
+    }
+    let format = u16::from_le_bytes(take(&mut buf, 2, file)?.try_into().unwrap());
+    if format != FORMAT_VERSION {
+        return Err(invalid(
+            file,
+            format!(
+                "bytecode biçimi sürümü {} desteklenmiyor, bu tr-lang sürüm {} biçimini okuyabilir",
+                format, FORMAT_VERSION,
+            ),
+            format!(
+                "bytecode format version {} is not supported, this tr-lang reads version {}",
+                format, FORMAT_VERSION,
+            ),
+        ));
+    }
+    let len = take(&mut buf, 1, file)?[0] as usize;
+    let version = String::from_utf8_lossy(take(&mut buf, len, file)?);
+    if version != VERSION {
+        return Err(invalid(
+            file,
+            format!(
+                "bytecode tr-lang sürüm {} ile derlenmiş ancak bu tr-lang sürüm {}, dosyayı yeniden derleyin",
+                version, VERSION,
+            ),
+            format!(
+                "bytecode was compiled by tr-lang version {} but this is tr-lang version {}, recompile the file",
+                version, VERSION,
+            ),
+        ));
+    }
+    let checksum = u32::from_le_bytes(take(&mut buf, 4, file)?.try_into().unwrap());
+    if crc32fast::hash(buf) != checksum {
+        return Err(invalid(
+            file,
+            "bytecode sağlama toplamı eşleşmiyor, dosya bozulmuş".to_string(),
+            "bytecode checksum does not match, the file is corrupted".to_string(),
+        ));
+    }
+    bincode::deserialize(buf).map_err(|e| {
+        invalid(
+            file,
+            format!("bytecode okunamadı: {}", e),
+            format!("couldn't read bytecode: {}", e),
+        )
+    })
 }
@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+
+use super::instruction::{Constant, Import, Instruction, Program, Span};
+use super::resolve::resolve;
+use crate::error::Error;
+use crate::token::{tokentypes::ParserTokenType as TokenType, ParserToken as Token};
+use crate::util::{get_lang, SupportedLanguage};
+
+/// Key of a constant in the pool, numbers are compared by their bits
+#[derive(PartialEq, Eq, Hash)]
+enum ConstKey {
+    Sayı(u64),
+    Yazı(String),
+    Baytlar(Vec<u8>),
+}
+
+struct Compiler {
+    program: Program,
+    consts: HashMap<ConstKey, u32>,
+    names: HashMap<String, u32>,
+    files: HashMap<String, u32>,
+}
+
+/// Compiles the output of the parser into a [`Program`]
+pub fn compile(tokens: Vec<Token>) -> Result<Program, Error> {
+    Compiler {
+        program: Program::default(),
+        consts: HashMap::new(),
+        names: HashMap::new(),
+        files: HashMap::new(),
+    }
+    .compile(tokens)
+}
+
+fn unexpected(expected: (&str, &str), found: Option<&Token>, at: &Token) -> Error {
+    let found = found.map(|t| t.repr()).unwrap_or_else(|| "EOF".to_string());
+    let msg = match get_lang() {
+        SupportedLanguage::Turkish => format!("{} beklenmişti ancak `{}` bulundu", expected.0, found),
+        SupportedLanguage::English => format!("expected {}, but found `{}`", expected.1, found),
+    };
+    Error::new("BeklenmedikSimge", &msg, vec![(at.line, at.col, at.file.clone(), None)], None)
+}
+
+const IDENTIFIER: (&str, &str) = ("tanımlayıcı", "identifier");
+const PAREN_R: (&str, &str) = ("`)`", "`)`");
+
+impl Compiler {
+    fn compile(mut self, tokens: Vec<Token>) -> Result<Program, Error> {
+        // where every token ended up in `code`, so that jumps can be pointed at instructions
+        let mut locs = vec![u32::MAX; tokens.len() + 1];
+        let mut i = 0;
+        while i < tokens.len() {
+            locs[i] = self.program.code.len() as u32;
+            let token = &tokens[i];
+            let ins = match token.typ {
+                TokenType::Sayı { val } => Instruction::Sabit(self.constant(Constant::Sayı(val))),
+                TokenType::Yazı { ref val } => Instruction::Sabit(self.constant(Constant::Yazı(val.clone()))),
+                TokenType::Baytlar { ref val } => Instruction::Sabit(self.constant(Constant::Baytlar(val.clone()))),
+                TokenType::Bool { val } => Instruction::Bool(val),
+                TokenType::Hiç => Instruction::Hiç,
+                TokenType::Identifier { ref id } => Instruction::Identifier(self.name(id)),
+                TokenType::Koy => {
+                    i += 1;
+                    Instruction::Koy(self.identifier(&tokens, i, token)?)
+                }
+                TokenType::İkiNokta => {
+                    i += 1;
+                    Instruction::İkiNokta(self.identifier(&tokens, i, token)?)
+                }
+                TokenType::Blok => {
+                    i += 1;
+                    Instruction::Blok(self.identifier(&tokens, i, token)?)
+                }
+                TokenType::İşlev { sonloc } => {
+                    i += 1;
+                    Instruction::İşlev {
+                        ad: self.identifier(&tokens, i, token)?,
+                        son: sonloc.unwrap() as u32,
+                    }
+                }
+                TokenType::Tipinde => {
+                    // conversion errors point at the type rather than `@`
+                    i += 1;
+                    let ad = self.identifier(&tokens, i, token)?;
+                    self.emit(Instruction::Tipinde(ad), &tokens[i]);
+                    i += 1;
+                    continue;
+                }
+                TokenType::InScopeParentL => {
+                    let (import, next) = self.import(&tokens, i)?;
+                    self.program.imports.push(import);
+                    self.emit(Instruction::İçeAktar(self.program.imports.len() as u32 - 1), token);
+                    i = next;
+                    continue;
+                }
+                TokenType::LibSymbol(ref s) => Instruction::LibSymbol(self.name(s)),
+                TokenType::İşlevSonlandır { .. } => Instruction::İşlevSonlandır,
+                TokenType::Ver { .. } => Instruction::Ver,
+                TokenType::Son { tp } => Instruction::Atla(tp as u32),
+                TokenType::Yoksa(tp) => Instruction::Atla(tp.unwrap() as u32),
+                TokenType::İse(tp) => Instruction::İse(tp.unwrap() as u32),
+                TokenType::İken(tp) => Instruction::İken(tp.unwrap() as u32),
+                TokenType::BlokSonlandır => Instruction::BlokSonlandır,
+                TokenType::Artı => Instruction::Artı,
+                TokenType::ArtıArtı => Instruction::ArtıArtı,
+                TokenType::Eksi => Instruction::Eksi,
+                TokenType::EksiEksi => Instruction::EksiEksi,
+                TokenType::Çarpı => Instruction::Çarpı,
+                TokenType::Bölü => Instruction::Bölü,
+                TokenType::Modulo => Instruction::Modulo,
+                TokenType::De => Instruction::De,
+                TokenType::Büyüktür => Instruction::Büyüktür,
+                TokenType::BüyükEşittir => Instruction::BüyükEşittir,
+                TokenType::Küçüktür => Instruction::Küçüktür,
+                TokenType::KüçükEşittir => Instruction::KüçükEşittir,
+                TokenType::Eşittir => Instruction::Eşittir,
+                TokenType::EşitDeğildir => Instruction::EşitDeğildir,
+                TokenType::Değildir => Instruction::Değildir,
+                TokenType::Ve => Instruction::Ve,
+                TokenType::Veya => Instruction::Veya,
+                TokenType::Sına => Instruction::Sına,
+                TokenType::Kopya => Instruction::Kopya,
+                TokenType::Takas => Instruction::Takas,
+                TokenType::Döndür => Instruction::Döndür,
+                TokenType::Üst => Instruction::Üst,
+                TokenType::At => Instruction::At,
+                TokenType::Girdi => Instruction::Girdi,
+                // only mark places to jump to
+                TokenType::İkiNoktaNokta | TokenType::EOF | TokenType::InScopeParentR => {
+                    i += 1;
+                    continue;
+                }
+                TokenType::ParenL => unreachable!(),
+            };
+            self.emit(ins, token);
+            i += 1;
+        }
+
+        // tokens that were folded into another instruction jump to the one after them
+        let mut next = self.program.code.len() as u32;
+        for loc in locs.iter_mut().rev() {
+            if *loc == u32::MAX {
+                *loc = next;
+            } else {
+                next = *loc;
+            }
+        }
+        for ins in self.program.code.iter_mut() {
+            match ins {
+                Instruction::Atla(tp) | Instruction::İse(tp) | Instruction::İken(tp) => {
+                    *tp = locs[*tp as usize]
+                }
+                Instruction::İşlev { son, .. } => *son = locs[*son as usize],
+                _ => (),
+            }
+        }
+        resolve(&mut self.program);
+        Ok(self.program)
+    }
+
+    fn emit(&mut self, ins: Instruction, token: &Token) {
+        let span = self.span(token);
+        self.program.code.push(ins);
+        self.program.spans.push(span);
+    }
+
+    fn span(&mut self, token: &Token) -> Span {
+        let file = match self.files.get(&token.file) {
+            Some(f) => *f,
+            None => {
+                let f = self.program.files.len() as u32;
+                self.program.files.push(token.file.clone());
+                self.files.insert(token.file.clone(), f);
+                f
+            }
+        };
+        Span { line: token.line, col: token.col, file }
+    }
+
+    fn constant(&mut self, c: Constant) -> u32 {
+        let key = match c {
+            Constant::Sayı(n) => ConstKey::Sayı(n.to_bits()),
+            Constant::Yazı(ref s) => ConstKey::Yazı(s.clone()),
+            Constant::Baytlar(ref b) => ConstKey::Baytlar(b.clone()),
+        };
+        match self.consts.get(&key) {
+            Some(i) => *i,
+            None => {
+                let i = self.program.consts.len() as u32;
+                self.program.consts.push(c);
+                self.consts.insert(key, i);
+                i
+            }
+        }
+    }
+
+    fn name(&mut self, name: &str) -> u32 {
+        match self.names.get(name) {
+            Some(i) => *i,
+            None => {
+                let i = self.program.names.len() as u32;
+                self.program.names.push(name.to_string());
+                self.names.insert(name.to_string(), i);
+                i
+            }
+        }
+    }
+
+    /// The name of the identifier at `tokens[i]`, which has to follow `at`
+    fn identifier(&mut self, tokens: &[Token], i: usize, at: &Token) -> Result<u32, Error> {
+        match tokens.get(i) {
+            Some(Token { typ: TokenType::Identifier { id }, .. }) => Ok(self.name(id)),
+            t => Err(unexpected(IDENTIFIER, t, at)),
+        }
+    }
+
+    /// Compiles the import starting with the `(` at `tokens[start]`, returns it along with where
+    /// the tokens after it start
+    fn import(&mut self, tokens: &[Token], start: usize) -> Result<(Import, usize), Error> {
+        let paren = &tokens[start];
+        let root = match tokens.get(start + 1) {
+            Some(t @ Token { typ: TokenType::Identifier { id }, .. }) => (self.name(id), self.span(t)),
+            t => return Err(unexpected(IDENTIFIER, t, paren)),
+        };
+        let mut import = Import { root, members: vec![], all: false };
+        let mut i = start + 2;
+        loop {
+            match tokens.get(i).map(|t| &t.typ) {
+                Some(TokenType::İkiNokta) => (),
+                Some(TokenType::InScopeParentR) if !import.members.is_empty() => return Ok((import, i + 1)),
+                _ => return Err(unexpected(("`:`", "`:`"), tokens.get(i), paren)),
+            }
+            match tokens.get(i + 1) {
+                Some(t @ Token { typ: TokenType::Identifier { id }, .. }) => {
+                    let member = (self.name(id), self.span(t));
+                    import.members.push(member);
+                    i += 2;
+                }
+                Some(Token { typ: TokenType::Çarpı, .. }) => {
+                    import.all = true;
+                    return match tokens.get(i + 2) {
+                        Some(Token { typ: TokenType::InScopeParentR, .. }) => Ok((import, i + 3)),
+                        t => Err(unexpected(PAREN_R, t, paren)),
+                    };
+                }
+                t => return Err(unexpected(PAREN_R, t, paren)),
+            }
+        }
+    }
+}
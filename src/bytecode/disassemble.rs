@@ -0,0 +1,150 @@
+use std::fmt::Write;
+
+use super::instruction::{Constant, Instruction, Program};
+use crate::util::bytes_repr;
+
+/// One instruction of a program, as shown by the disassembler
+struct Entry {
+    kind: String,
+    operands: Vec<String>,
+    target: Option<usize>,
+    /// `file:line:col` of the instruction
+    position: String,
+}
+
+/// `names[i]`, the program may not have been verified so anything out of range is shown as `?`
+fn name(program: &Program, i: u32) -> String {
+    program.names.get(i as usize).cloned().unwrap_or_else(|| "?".to_string())
+}
+
+fn entry(program: &Program, pc: usize) -> Entry {
+    use Instruction as I;
+    let ins = program.code[pc];
+    // the variant name, without its operands
+    let kind = format!("{:?}", ins)
+        .split(['(', ' '])
+        .next()
+        .unwrap()
+        .to_string();
+    let (operands, target) = match ins {
+        I::Sabit(i) => (
+            vec![match program.consts.get(i as usize) {
+                Some(Constant::Sayı(n)) if n.fract() == 0.0 => format!("{:.0?}", n),
+                Some(Constant::Sayı(n)) => format!("{:?}", n),
+                Some(Constant::Yazı(s)) => format!("{:?}", s),
+                Some(Constant::Baytlar(b)) => bytes_repr(b),
+                None => "?".to_string(),
+            }],
+            None,
+        ),
+        I::Bool(b) => (vec![if b { "doğru" } else { "yanlış" }.to_string()], None),
+        I::Identifier(i)
+        | I::Yerel(i)
+        | I::Global(i)
+        | I::Koy(i)
+        | I::İkiNokta(i)
+        | I::Tipinde(i)
+        | I::Blok(i)
+        | I::LibSymbol(i) => (vec![name(program, i)], None),
+        I::İşlev { ad, son } => (vec![name(program, ad)], Some(son as usize)),
+        I::Atla(tp) | I::İse(tp) | I::İken(tp) => (vec![], Some(tp as usize)),
+        I::İçeAktar(i) => match program.imports.get(i as usize) {
+            Some(import) => {
+                let mut path = name(program, import.root.0);
+                for (member, _) in import.members.iter() {
+                    path.push(':');
+                    path.push_str(&name(program, *member));
+                }
+                if import.all {
+                    path.push_str(":*");
+                }
+                (vec![format!("({})", path)], None)
+            }
+            None => (vec!["?".to_string()], None),
+        },
+        _ => (vec![], None),
+    };
+    let span = program.spans.get(pc);
+    let position = match span.and_then(|s| program.files.get(s.file as usize).map(|f| (s, f))) {
+        Some((s, f)) => format!("{}:{}:{}", f, s.line, s.col),
+        None => "?".to_string(),
+    };
+    Entry { kind, operands, target, position }
+}
+
+/// What is at `tp`, for showing where a jump goes
+fn target_kind(program: &Program, tp: usize) -> String {
+    match program.code.get(tp) {
+        Some(_) => entry(program, tp).kind,
+        None if tp == program.code.len() => "EOF".to_string(),
+        None => "?".to_string(),
+    }
+}
+
+/// A listing of `program`, one instruction per line with its index, kind, operands, jump target
+/// and where it came from in the source
+pub fn disassemble(program: &Program) -> String {
+    let width = program.code.len().to_string().len();
+    let mut buf = String::new();
+    for pc in 0..program.code.len() {
+        let entry = entry(program, pc);
+        let mut operands = entry.operands.join(" ");
+        if let Some(tp) = entry.target {
+            if !operands.is_empty() {
+                operands.push(' ');
+            }
+            write!(operands, "-> {} ({})", tp, target_kind(program, tp)).unwrap();
+        }
+        writeln!(
+            buf,
+            "{:>width$}  {:<16}{:<32}{}",
+            pc, entry.kind, operands, entry.position,
+            width = width,
+        )
+        .unwrap();
+    }
+    buf
+}
+
+fn json_string(s: &str) -> String {
+    let mut buf = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => buf.push_str("\\\""),
+            '\\' => buf.push_str("\\\\"),
+            '\n' => buf.push_str("\\n"),
+            '\r' => buf.push_str("\\r"),
+            '\t' => buf.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(buf, "\\u{:04x}", c as u32).unwrap(),
+            c => buf.push(c),
+        }
+    }
+    buf.push('"');
+    buf
+}
+
+/// The same listing as [`disassemble`] as a JSON array, for tools
+///
+/// Every instruction is an object with the keys `index`, `kind`, `operands`, `target` and
+/// `position`; `target` is `null` for instructions that do not jump.
+pub fn disassemble_json(program: &Program) -> String {
+    let mut buf = String::from("[");
+    for pc in 0..program.code.len() {
+        let entry = entry(program, pc);
+        if pc > 0 {
+            buf.push(',');
+        }
+        write!(
+            buf,
+            "\n  {{\"index\": {}, \"kind\": {}, \"operands\": [{}], \"target\": {}, \"position\": {}}}",
+            pc,
+            json_string(&entry.kind),
+            entry.operands.iter().map(|o| json_string(o)).collect::<Vec<_>>().join(", "),
+            entry.target.map(|t| t.to_string()).unwrap_or_else(|| "null".to_string()),
+            json_string(&entry.position),
+        )
+        .unwrap();
+    }
+    buf.push_str("\n]\n");
+    buf
+}
@@ -0,0 +1,187 @@
+use serde::{Deserialize, Serialize};
+
+use crate::mem::Object;
+use crate::util::bytes_repr;
+
+/// A single instruction of the virtual machine
+///
+/// Operands are indexes into the pools of the [`Program`] the instruction belongs to, or into
+/// its `code` for jumps.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Instruction {
+    /// Pushes `consts[i]`
+    Sabit(u32),
+    Bool(bool),
+    Hiç,
+    /// Pushes the value named `names[i]`, calls it instead if it is a function
+    Identifier(u32),
+    /// Same as [`Instruction::Identifier`], for names that are known to be set in the innermost
+    /// scope
+    Yerel(u32),
+    /// Same as [`Instruction::Identifier`], for names that can only be set in the global scope
+    Global(u32),
+    /// Pops a value and names it `names[i]`
+    Koy(u32),
+    /// Pops a map and pushes its member `names[i]`, calls it instead if it is a function
+    İkiNokta(u32),
+    /// Pops a value and converts it to the type `names[i]`
+    Tipinde(u32),
+    /// Defines the function `names[ad]` whose body starts at the next instruction and ends with
+    /// the [`Instruction::İşlevSonlandır`] at `son`, then jumps over the body
+    İşlev { ad: u32, son: u32 },
+    /// Returns from the current function
+    İşlevSonlandır,
+    /// Pops a value, returns it from the current function
+    Ver,
+    /// Jumps to `code[i]`
+    Atla(u32),
+    /// Pops a condition, jumps to `code[i]` if it does not hold
+    İse(u32),
+    /// Same as [`Instruction::İse`], at the head of a loop
+    İken(u32),
+    /// Opens the block `names[i]`
+    Blok(u32),
+    /// Closes the current block, naming a map of its variables after it
+    BlokSonlandır,
+    /// Runs the import `imports[i]`, like `(a:b)` or `(a:*)`
+    İçeAktar(u32),
+    /// Loads the standard library module or dynamic library `names[i]`
+    LibSymbol(u32),
+    Artı,
+    ArtıArtı,
+    Eksi,
+    EksiEksi,
+    Çarpı,
+    Bölü,
+    Modulo,
+    De,
+    Büyüktür,
+    BüyükEşittir,
+    Küçüktür,
+    KüçükEşittir,
+    Eşittir,
+    EşitDeğildir,
+    Değildir,
+    Ve,
+    Veya,
+    Sına,
+    Kopya,
+    Takas,
+    Döndür,
+    Üst,
+    At,
+    Girdi,
+}
+
+/// A value of the constant pool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Constant {
+    Sayı(f64),
+    Yazı(String),
+    Baytlar(Vec<u8>),
+}
+
+impl Constant {
+    pub fn to_object(&self) -> Object {
+        match self {
+            Self::Sayı(n) => Object::Sayı(*n),
+            Self::Yazı(s) => Object::Yazı(s.clone()),
+            Self::Baytlar(b) => Object::Baytlar(b.clone()),
+        }
+    }
+}
+
+/// Where an instruction came from in the source, `file` is an index into `files`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Span {
+    pub line: usize,
+    pub col: usize,
+    pub file: u32,
+}
+
+/// An import like `(a:b:c)`, which names `c` the member `c` of the member `b` of `a`, or
+/// `(a:b:*)` which brings every member of `a:b` into scope
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Import {
+    pub root: (u32, Span),
+    pub members: Vec<(u32, Span)>,
+    pub all: bool,
+}
+
+/// A compiled tr-lang program
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Program {
+    pub code: Vec<Instruction>,
+    /// The span of every instruction in `code`
+    pub spans: Vec<Span>,
+    pub consts: Vec<Constant>,
+    pub names: Vec<String>,
+    pub files: Vec<String>,
+    pub imports: Vec<Import>,
+}
+
+impl Program {
+    /// The line, column and file of `span`
+    pub fn position(&self, span: Span) -> (usize, usize, String) {
+        (span.line, span.col, self.files[span.file as usize].clone())
+    }
+
+    /// The first instruction on `line` of `file`, where a breakpoint for the line goes
+    pub fn at_line(&self, file: &str, line: usize) -> Option<usize> {
+        self.spans
+            .iter()
+            .position(|span| span.line == line && self.files[span.file as usize] == file)
+    }
+
+    /// How the instruction at `pc` was written, used in error messages
+    pub fn repr(&self, pc: usize) -> String {
+        use Instruction as I;
+        match self.code[pc] {
+            I::Sabit(i) => match &self.consts[i as usize] {
+                Constant::Sayı(n) if n.fract() == 0.0 => format!("{:.0?}", n),
+                Constant::Sayı(n) => format!("{:?}", n),
+                Constant::Yazı(s) => format!("{:?}", s),
+                Constant::Baytlar(b) => bytes_repr(b),
+            },
+            I::Bool(true) => "doğru".to_string(),
+            I::Bool(false) => "yanlış".to_string(),
+            I::Hiç => "hiç".to_string(),
+            I::Identifier(i) | I::Yerel(i) | I::Global(i) => self.names[i as usize].clone(),
+            I::Koy(_) => "->".to_string(),
+            I::İkiNokta(_) => ":".to_string(),
+            I::Tipinde(_) => "@".to_string(),
+            I::İşlev { .. } => "işlev".to_string(),
+            I::İşlevSonlandır | I::BlokSonlandır | I::Atla(_) => "son".to_string(),
+            I::Ver => "ver".to_string(),
+            I::İse(_) => "ise".to_string(),
+            I::İken(_) => "iken".to_string(),
+            I::Blok(_) => "blok".to_string(),
+            I::İçeAktar(_) => "(".to_string(),
+            I::LibSymbol(_) => "yükle".to_string(),
+            I::Artı => "+".to_string(),
+            I::ArtıArtı => "++".to_string(),
+            I::Eksi => "-".to_string(),
+            I::EksiEksi => "--".to_string(),
+            I::Çarpı => "*".to_string(),
+            I::Bölü => "/".to_string(),
+            I::Modulo => "%".to_string(),
+            I::De => "de".to_string(),
+            I::Büyüktür => ">".to_string(),
+            I::BüyükEşittir => ">=".to_string(),
+            I::Küçüktür => "<".to_string(),
+            I::KüçükEşittir => "<=".to_string(),
+            I::Eşittir => "=".to_string(),
+            I::EşitDeğildir => "!=".to_string(),
+            I::Değildir => "!".to_string(),
+            I::Ve => "ve".to_string(),
+            I::Veya => "veya".to_string(),
+            I::Sına => "sına".to_string(),
+            I::Kopya => "kpy".to_string(),
+            I::Takas => "tks".to_string(),
+            I::Döndür => "dön".to_string(),
+            I::Üst => "üst".to_string(),
+            I::At => "at".to_string(),
+            I::Girdi => "girdi".to_string(),
+        }
+    }
+}
@@ -1,4 +1,20 @@
+#[allow(clippy::module_inception)]
 mod bytecode;
+mod compiler;
+mod disassemble;
+mod instruction;
+mod optimize;
+mod resolve;
+mod verify;
 
 pub use bytecode::from_bytecode;
+pub use bytecode::read_bytecode;
 pub use bytecode::to_bytecode;
+pub use bytecode::{FORMAT_VERSION, MAGIC};
+pub use compiler::compile;
+pub use disassemble::{disassemble, disassemble_json};
+pub use instruction::{Constant, Import, Instruction, Program, Span};
+pub use optimize::{optimize, optimize_program};
+pub use resolve::resolve;
+pub use verify::verify;
+pub(crate) use verify::nesting;
@@ -0,0 +1,383 @@
+use std::collections::HashSet;
+
+use super::instruction::{Constant, Instruction, Program};
+use super::resolve::resolve;
+use crate::mem::Object;
+use crate::stdlib;
+
+/// Optimizes `program` without changing what it does
+///
+/// - operations on literals are computed at compile time, as long as they can not fail
+/// - `ise` and `iken` with a literal condition are replaced with a jump or removed
+/// - instructions that can never be reached, and jumps to the next instruction, are removed
+/// - functions defined inside other functions that are never used are removed
+///
+/// Functions defined at the top level of the program are always kept, even if the program never
+/// uses them, since a host may still call them with [`Run::call`](crate::runtime::Run::call). Use
+/// [`optimize_program`] to remove those too when the program is only ever run on its own.
+///
+/// Every instruction that is left keeps its source position.
+pub fn optimize(program: Program) -> Program {
+    Optimizer { program, keep_top_level: true }.optimize()
+}
+
+/// Same as [`optimize`], but also removes top-level functions that the program never uses
+///
+/// Only for programs that are run on their own, functions removed here can no longer be called
+/// with [`Run::call`](crate::runtime::Run::call).
+pub fn optimize_program(program: Program) -> Program {
+    Optimizer { program, keep_top_level: false }.optimize()
+}
+
+struct Optimizer {
+    program: Program,
+    /// whether functions outside of all other functions are kept even if they are not used
+    keep_top_level: bool,
+}
+
+impl Optimizer {
+    fn optimize(mut self) -> Program {
+        self.fold_constants();
+        self.fold_branches();
+        while self.remove_unused_functions() {}
+        self.remove_unreachable();
+        self.remove_redundant_jumps();
+        self.remove_unused_constants();
+        resolve(&mut self.program);
+        self.program
+    }
+}
+
+/// What `ins` results in when run on `args`, if it is an operation that is safe to compute at
+/// compile time with these operands
+///
+/// Only combinations that always succeed are computed, the rest are left to be reported, or to
+/// panic, when the program is run.
+fn fold(ins: Instruction, args: &[&Object]) -> Option<Object> {
+    use Instruction as I;
+    use Object as O;
+    let res = match (ins, args) {
+        (I::ArtıArtı, [a @ O::Sayı(_)]) => a.ekle(O::Sayı(1.0)),
+        (I::EksiEksi, [a @ O::Sayı(_)]) => a.çıkar(O::Sayı(1.0)),
+        (I::Değildir, [a @ O::Bool(_)]) => a.değildir(),
+        (I::Artı, [a @ O::Yazı(_), b @ O::Yazı(_)])
+        | (I::Artı, [a @ O::Baytlar(_), b @ O::Baytlar(_)]) => a.ekle((*b).clone()),
+        (I::Eşittir, [a, b]) | (I::EşitDeğildir, [a, b])
+            if std::mem::discriminant(*a) == std::mem::discriminant(*b)
+                && matches!(a, O::Sayı(_) | O::Bool(_) | O::Yazı(_) | O::Baytlar(_)) =>
+        {
+            let equal = match a.eşittir((*b).clone()) {
+                Ok(O::Bool(e)) => e,
+                _ => return None,
+            };
+            Ok(O::Bool(equal == matches!(ins, I::Eşittir)))
+        }
+        (I::Ve, [O::Bool(a), O::Bool(b)]) => Ok(O::Bool(*a && *b)),
+        (I::Veya, [O::Bool(a), O::Bool(b)]) => Ok(O::Bool(*a || *b)),
+        (_, [a @ O::Sayı(_), b @ O::Sayı(_)]) => {
+            let b = (*b).clone();
+            match ins {
+                I::Artı => a.ekle(b),
+                I::Eksi => a.çıkar(b),
+                I::Çarpı => a.çarp(b),
+                I::Bölü => a.böl(b),
+                I::Modulo => a.modulo(b),
+                I::Büyüktür => a.büyüktür(b),
+                I::BüyükEşittir => a.büyük_eşittir(b),
+                I::Küçüktür => a.küçüktür(b),
+                I::KüçükEşittir => a.küçük_eşittir(b),
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+    res.ok()
+}
+
+impl Optimizer {
+    /// Every instruction that something can jump to, or that a function is called at
+    fn targets(&self) -> Vec<bool> {
+        let len = self.program.code.len();
+        let mut targets = vec![false; len + 1];
+        for (pc, ins) in self.program.code.iter().enumerate() {
+            match *ins {
+                Instruction::Atla(tp) | Instruction::İse(tp) | Instruction::İken(tp) => {
+                    targets[tp as usize] = true
+                }
+                Instruction::İşlev { son, .. } => {
+                    targets[pc + 1] = true;
+                    targets[son as usize + 1] = true;
+                }
+                _ => (),
+            }
+        }
+        targets
+    }
+
+    /// Removes the instructions not in `keep`, pointing jumps to what was after them
+    fn retain(&mut self, keep: &[bool]) {
+        let program = &mut self.program;
+        // where every instruction ends up, and where jumps past the last one go
+        let mut locs = Vec::with_capacity(keep.len() + 1);
+        let mut next = 0;
+        for k in keep.iter() {
+            locs.push(next);
+            if *k {
+                next += 1;
+            }
+        }
+        locs.push(next);
+
+        let code = std::mem::take(&mut program.code);
+        let spans = std::mem::take(&mut program.spans);
+        for ((mut ins, span), k) in code.into_iter().zip(spans).zip(keep) {
+            if !k {
+                continue;
+            }
+            match ins {
+                Instruction::Atla(ref mut tp) | Instruction::İse(ref mut tp) | Instruction::İken(ref mut tp) => {
+                    *tp = locs[*tp as usize]
+                }
+                Instruction::İşlev { ref mut son, .. } => *son = locs[*son as usize],
+                _ => (),
+            }
+            program.code.push(ins);
+            program.spans.push(span);
+        }
+    }
+
+    /// The instruction that pushes `o`, if `o` can be pushed by a single instruction
+    fn literal(&mut self, o: Object) -> Option<Instruction> {
+        let c = match o {
+            Object::Bool(b) => return Some(Instruction::Bool(b)),
+            Object::Hiç => return Some(Instruction::Hiç),
+            Object::Sayı(n) => Constant::Sayı(n),
+            Object::Yazı(s) => Constant::Yazı(s),
+            Object::Baytlar(b) => Constant::Baytlar(b),
+            _ => return None,
+        };
+        self.program.consts.push(c);
+        Some(Instruction::Sabit(self.program.consts.len() as u32 - 1))
+    }
+
+    /// Computes operations whose operands are all literals
+    fn fold_constants(&mut self) {
+        let targets = self.targets();
+        let mut keep = vec![true; self.program.code.len()];
+        // the literals on top of the stack, with where they were pushed
+        let mut known: Vec<(usize, Object)> = vec![];
+        for (pc, target) in targets.iter().enumerate().take(self.program.code.len()) {
+            if *target {
+                known.clear();
+            }
+            let ins = self.program.code[pc];
+            let arity = match ins {
+                Instruction::Sabit(i) => {
+                    known.push((pc, self.program.consts[i as usize].to_object()));
+                    continue;
+                }
+                Instruction::Bool(b) => {
+                    known.push((pc, Object::Bool(b)));
+                    continue;
+                }
+                Instruction::Hiç => {
+                    known.push((pc, Object::Hiç));
+                    continue;
+                }
+                Instruction::ArtıArtı | Instruction::EksiEksi | Instruction::Değildir => 1,
+                _ => 2,
+            };
+            let args: Vec<&Object> = known.iter().rev().take(arity).rev().map(|(_, o)| o).collect();
+            let o = match fold(ins, &args) {
+                Some(o) => o,
+                None => {
+                    known.clear();
+                    continue;
+                }
+            };
+            match self.literal(o.clone()) {
+                Some(lit) => {
+                    for (at, _) in known.drain(known.len() - arity..) {
+                        keep[at] = false;
+                    }
+                    self.program.code[pc] = lit;
+                    known.push((pc, o));
+                }
+                None => known.clear(),
+            }
+        }
+        self.retain(&keep);
+    }
+
+    /// Replaces `ise` and `iken` right after a literal boolean with a jump, or removes them
+    fn fold_branches(&mut self) {
+        let targets = self.targets();
+        let mut keep = vec![true; self.program.code.len()];
+        for pc in 1..self.program.code.len() {
+            let b = match self.program.code[pc - 1] {
+                Instruction::Bool(b) if keep[pc - 1] && !targets[pc] => b,
+                _ => continue,
+            };
+            match self.program.code[pc] {
+                Instruction::İse(_) | Instruction::İken(_) if b => {
+                    keep[pc - 1] = false;
+                    keep[pc] = false;
+                }
+                Instruction::İse(tp) | Instruction::İken(tp) => {
+                    keep[pc - 1] = false;
+                    self.program.code[pc] = Instruction::Atla(tp);
+                }
+                _ => (),
+            }
+        }
+        self.retain(&keep);
+    }
+
+    /// Removes instructions that can never be run
+    ///
+    /// Functions, blocks and their ends are always kept so that they stay properly nested.
+    fn remove_unreachable(&mut self) {
+        let len = self.program.code.len();
+        let mut reached = vec![false; len + 1];
+        let mut todo = vec![0];
+        while let Some(pc) = todo.pop() {
+            if reached[pc] {
+                continue;
+            }
+            reached[pc] = true;
+            if pc == len {
+                continue;
+            }
+            match self.program.code[pc] {
+                Instruction::Atla(tp) => todo.push(tp as usize),
+                Instruction::İse(tp) | Instruction::İken(tp) => {
+                    todo.push(pc + 1);
+                    todo.push(tp as usize);
+                }
+                Instruction::İşlev { son, .. } => {
+                    todo.push(pc + 1);
+                    todo.push(son as usize + 1);
+                }
+                Instruction::İşlevSonlandır | Instruction::Ver => (),
+                _ => todo.push(pc + 1),
+            }
+        }
+        let keep: Vec<bool> = self
+            .program
+            .code
+            .iter()
+            .zip(reached.iter())
+            .map(|(ins, r)| {
+                *r || matches!(
+                    ins,
+                    Instruction::İşlev { .. }
+                        | Instruction::İşlevSonlandır
+                        | Instruction::Blok(_)
+                        | Instruction::BlokSonlandır
+                )
+            })
+            .collect();
+        self.retain(&keep);
+    }
+
+    /// Removes functions that are never used, returns whether any were removed
+    ///
+    /// A function is used if its name is ever looked up, imported or written as a string, since
+    /// modules like `http` take functions by name. Functions in blocks can be reached through the
+    /// block and are always kept, as are all functions if a dynamic library is loaded or
+    /// everything in a block is imported. Top-level functions are kept if `keep_top_level` is set.
+    fn remove_unused_functions(&mut self) -> bool {
+        let program = &self.program;
+        let mut used: HashSet<&str> = HashSet::new();
+        for ins in program.code.iter() {
+            match *ins {
+                Instruction::Identifier(i)
+                | Instruction::Yerel(i)
+                | Instruction::Global(i)
+                | Instruction::İkiNokta(i) => {
+                    used.insert(&program.names[i as usize]);
+                }
+                Instruction::İçeAktar(i) => {
+                    let import = &program.imports[i as usize];
+                    if import.all {
+                        return false;
+                    }
+                    used.insert(&program.names[import.root.0 as usize]);
+                    for (member, _) in import.members.iter() {
+                        used.insert(&program.names[*member as usize]);
+                    }
+                }
+                Instruction::LibSymbol(i) if stdlib::module(&program.names[i as usize]).is_none() => {
+                    return false
+                }
+                _ => (),
+            }
+        }
+        for c in program.consts.iter() {
+            if let Constant::Yazı(s) = c {
+                used.insert(s);
+            }
+        }
+
+        let mut keep = vec![true; program.code.len()];
+        let mut removed = false;
+        let mut blocks = 0;
+        // how many of the functions that are kept the instruction is in
+        let mut functions = 0;
+        let mut pc = 0;
+        while pc < program.code.len() {
+            match program.code[pc] {
+                Instruction::Blok(_) => blocks += 1,
+                Instruction::BlokSonlandır => blocks -= 1,
+                Instruction::İşlevSonlandır => functions -= 1,
+                Instruction::İşlev { ad, son }
+                    if blocks == 0
+                        && (functions > 0 || !self.keep_top_level)
+                        && !used.contains(program.names[ad as usize].as_str()) =>
+                {
+                    for k in keep[pc..=son as usize].iter_mut() {
+                        *k = false;
+                    }
+                    removed = true;
+                    pc = son as usize;
+                }
+                Instruction::İşlev { .. } => functions += 1,
+                _ => (),
+            }
+            pc += 1;
+        }
+        if removed {
+            self.retain(&keep);
+        }
+        removed
+    }
+
+    /// Removes jumps to the instruction right after them
+    fn remove_redundant_jumps(&mut self) {
+        let keep: Vec<bool> = self
+            .program
+            .code
+            .iter()
+            .enumerate()
+            .map(|(pc, ins)| !matches!(ins, Instruction::Atla(tp) if *tp as usize == pc + 1))
+            .collect();
+        self.retain(&keep);
+    }
+
+    /// Drops constants that are no longer used after folding
+    fn remove_unused_constants(&mut self) {
+        let program = &mut self.program;
+        let mut locs = vec![u32::MAX; program.consts.len()];
+        let mut consts = vec![];
+        for ins in program.code.iter_mut() {
+            if let Instruction::Sabit(ref mut i) = ins {
+                if locs[*i as usize] == u32::MAX {
+                    locs[*i as usize] = consts.len() as u32;
+                    consts.push(program.consts[*i as usize].clone());
+                }
+                *i = locs[*i as usize];
+            }
+        }
+        program.consts = consts;
+    }
+}
@@ -0,0 +1,213 @@
+use std::collections::HashSet;
+
+use super::instruction::{Instruction, Program};
+use crate::stdlib;
+
+/// Where the variables of a region of code are kept
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    /// Code outside any function or block, which runs in the global scope
+    Top,
+    /// The body of a function, which can see the scopes of whatever called it
+    İşlev,
+    Blok,
+}
+
+struct Region {
+    kind: Kind,
+    parent: usize,
+    entry: usize,
+    /// Names that may be set in the scope of the region
+    bound: HashSet<u32>,
+    /// Whether names that can't be known at compile time may be set in the scope of the region
+    any: bool,
+}
+
+/// A set of names, one bit for each
+#[derive(Clone, PartialEq)]
+struct Names(Vec<u64>);
+
+impl Names {
+    fn new(len: usize) -> Self {
+        Self(vec![0; len.div_ceil(64)])
+    }
+    fn insert(&mut self, i: u32) {
+        self.0[i as usize / 64] |= 1 << (i % 64);
+    }
+    fn contains(&self, i: u32) -> bool {
+        self.0[i as usize / 64] & (1 << (i % 64)) != 0
+    }
+    fn intersect(&mut self, other: &Self) {
+        for (a, b) in self.0.iter_mut().zip(other.0.iter()) {
+            *a &= b;
+        }
+    }
+}
+
+/// Resolves names that are looked up to the scope they are in, where that can be known at
+/// compile time
+///
+/// Scoping is dynamic, a function can see the variables of whatever called it, so only two kinds
+/// of lookups are resolved:
+/// - names that are set on every path to the lookup in the same scope become
+///   [`Instruction::Yerel`]
+/// - names outside functions that no enclosing block can set become [`Instruction::Global`]
+///
+/// Everything else stays an [`Instruction::Identifier`] and is looked up through every scope.
+/// Nothing is resolved when a dynamic library is loaded, since it can set any name anywhere.
+/// `program` has to be verified.
+pub fn resolve(program: &mut Program) {
+    for ins in program.code.iter_mut() {
+        if let Instruction::Yerel(i) | Instruction::Global(i) = *ins {
+            *ins = Instruction::Identifier(i);
+        }
+    }
+    let dynamic = program.code.iter().any(|ins| match ins {
+        Instruction::LibSymbol(i) => stdlib::module(&program.names[*i as usize]).is_none(),
+        _ => false,
+    });
+    if dynamic {
+        return;
+    }
+
+    let len = program.code.len();
+    // the innermost region every instruction is in, closing instructions are inside what they
+    // close while opening ones are outside
+    let mut region_of = vec![0; len];
+    // where the function or block opened at each instruction ends
+    let mut ends = vec![0; len];
+    let mut regions = vec![Region { kind: Kind::Top, parent: 0, entry: 0, bound: HashSet::new(), any: false }];
+    let mut open = vec![(0, 0)];
+    for (pc, ins) in program.code.iter().enumerate() {
+        let (r, opened_at) = *open.last().unwrap();
+        region_of[pc] = r;
+        let region = &mut regions[r];
+        match *ins {
+            Instruction::Koy(i) => {
+                region.bound.insert(i);
+            }
+            Instruction::İçeAktar(i) => {
+                let import = &program.imports[i as usize];
+                match import.members.last() {
+                    Some((last, _)) if !import.all => {
+                        region.bound.insert(*last);
+                    }
+                    _ => region.any = true,
+                }
+            }
+            Instruction::LibSymbol(_) => region.any = true,
+            Instruction::İşlev { ad, .. } | Instruction::Blok(ad) => {
+                region.bound.insert(ad);
+                let kind = match ins {
+                    Instruction::Blok(_) => Kind::Blok,
+                    _ => Kind::İşlev,
+                };
+                regions.push(Region { kind, parent: r, entry: pc + 1, bound: HashSet::new(), any: false });
+                open.push((regions.len() - 1, pc));
+            }
+            Instruction::İşlevSonlandır | Instruction::BlokSonlandır => {
+                ends[opened_at] = pc;
+                open.pop();
+            }
+            _ => (),
+        }
+    }
+
+    // whether lookups in each region can only find names in its scope, the scopes of the blocks
+    // around it and the global scope
+    let mut fixed = vec![true; regions.len()];
+    for r in 1..regions.len() {
+        fixed[r] = regions[r].kind == Kind::Blok && fixed[regions[r].parent];
+    }
+
+    for r in 0..regions.len() {
+        let assigned = definitely_assigned(program, &regions[r], r, &region_of, &ends);
+        for pc in 0..len {
+            let i = match program.code[pc] {
+                Instruction::Identifier(i) if region_of[pc] == r => i,
+                _ => continue,
+            };
+            match &assigned[pc] {
+                Some(names) if names.contains(i) => program.code[pc] = Instruction::Yerel(i),
+                Some(_) if fixed[r] => {
+                    let mut s = r;
+                    let mut global = true;
+                    while s != 0 {
+                        if regions[s].any || regions[s].bound.contains(&i) {
+                            global = false;
+                            break;
+                        }
+                        s = regions[s].parent;
+                    }
+                    if global {
+                        program.code[pc] = Instruction::Global(i);
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+/// The names that are set in the scope of `region` on every path to each of its instructions,
+/// `None` for instructions that can't be reached or are not in `region`
+fn definitely_assigned(
+    program: &Program,
+    region: &Region,
+    r: usize,
+    region_of: &[usize],
+    ends: &[usize],
+) -> Vec<Option<Names>> {
+    let len = program.code.len();
+    let mut sets: Vec<Option<Names>> = vec![None; len];
+    if region.entry >= len || region_of[region.entry] != r {
+        return sets;
+    }
+    sets[region.entry] = Some(Names::new(program.names.len()));
+    let mut todo = vec![region.entry];
+    while let Some(pc) = todo.pop() {
+        let mut names = sets[pc].clone().unwrap();
+        let next: &[usize] = match program.code[pc] {
+            Instruction::Atla(tp) => &[tp as usize],
+            Instruction::İse(tp) | Instruction::İken(tp) => &[pc + 1, tp as usize],
+            Instruction::İşlevSonlandır | Instruction::BlokSonlandır | Instruction::Ver => &[],
+            // functions and blocks inside the region are skipped over, only naming something
+            Instruction::İşlev { ad: i, .. } | Instruction::Blok(i) => {
+                names.insert(i);
+                &[ends[pc] + 1]
+            }
+            Instruction::Koy(i) => {
+                names.insert(i);
+                &[pc + 1]
+            }
+            Instruction::İçeAktar(i) => {
+                let import = &program.imports[i as usize];
+                if let Some((last, _)) = import.members.last() {
+                    if !import.all {
+                        names.insert(*last);
+                    }
+                }
+                &[pc + 1]
+            }
+            _ => &[pc + 1],
+        };
+        let next: Vec<usize> = next.iter().copied().filter(|n| *n < len).collect();
+        for n in next {
+            let changed = match &mut sets[n] {
+                Some(set) => {
+                    let before = set.clone();
+                    set.intersect(&names);
+                    *set != before
+                }
+                s => {
+                    *s = Some(names.clone());
+                    true
+                }
+            };
+            if changed {
+                todo.push(n);
+            }
+        }
+    }
+    sets
+}
@@ -0,0 +1,205 @@
+use super::bytecode::invalid;
+use super::instruction::{Instruction, Program};
+use crate::error::Error;
+
+/// Checks that `program` is safe to run, `file` is where it was read from
+///
+/// Every pool index has to exist, functions and blocks have to be properly nested, functions
+/// have to end at their [`Instruction::İşlevSonlandır`], `ver` can only be used directly inside a
+/// function and jumps can not leave or enter a function or block.
+pub fn verify(program: &Program, file: &str) -> Result<(), Error> {
+    Verifier { program, file }.verify()
+}
+
+/// How many blocks every instruction of the verified `program` is in, counting only the ones
+/// inside the function it is in, along with where that function starts
+///
+/// Opening instructions are outside what they open and closing ones inside what they close. The
+/// end of the program, one past its last instruction, is included.
+pub(crate) fn nesting(program: &Program) -> Vec<(usize, Option<usize>)> {
+    let mut nesting = Vec::with_capacity(program.code.len() + 1);
+    // the blocks and the function every instruction is in, saved when a function starts
+    let (mut blocks, mut function): (usize, Option<usize>) = (0, None);
+    let mut outer = vec![];
+    for (pc, ins) in program.code.iter().enumerate() {
+        nesting.push((blocks, function));
+        match ins {
+            Instruction::İşlev { .. } => {
+                outer.push((blocks, function));
+                (blocks, function) = (0, Some(pc));
+            }
+            Instruction::İşlevSonlandır => (blocks, function) = outer.pop().unwrap_or_default(),
+            Instruction::Blok(_) => blocks += 1,
+            Instruction::BlokSonlandır => blocks = blocks.saturating_sub(1),
+            _ => (),
+        }
+    }
+    nesting.push((blocks, function));
+    nesting
+}
+
+struct Verifier<'a> {
+    program: &'a Program,
+    file: &'a str,
+}
+
+impl Verifier<'_> {
+    /// An error for the instruction at `pc`, spans have to be checked before using it
+    fn error(&self, pc: usize, tr: String, en: String) -> Error {
+        let (line, col, file) = self.program.position(self.program.spans[pc]);
+        invalid(
+            self.file,
+            format!("{} numaralı komut ({}:{}:{}) geçersiz: {}", pc, file, line, col, tr),
+            format!("instruction {} ({}:{}:{}) is invalid: {}", pc, file, line, col, en),
+        )
+    }
+
+    fn check(&self, pc: usize, i: u32, len: usize, pool: (&str, &str)) -> Result<(), Error> {
+        if (i as usize) < len {
+            return Ok(());
+        }
+        Err(self.error(
+            pc,
+            format!("{} havuzunda {} numaralı eleman yok", pool.0, i),
+            format!("there is no entry {} in the {} pool", i, pool.1),
+        ))
+    }
+
+    fn name(&self, pc: usize, i: u32) -> Result<(), Error> {
+        self.check(pc, i, self.program.names.len(), ("isim", "name"))
+    }
+
+    fn verify(&self) -> Result<(), Error> {
+        let program = self.program;
+        let len = program.code.len();
+        if program.spans.len() != len {
+            return Err(invalid(
+                self.file,
+                "komut ve konum sayıları eşleşmiyor".to_string(),
+                "the number of instructions and positions do not match".to_string(),
+            ));
+        }
+        for (pc, span) in program.spans.iter().enumerate() {
+            if span.file as usize >= program.files.len() {
+                return Err(invalid(
+                    self.file,
+                    format!("{} numaralı komutun konumu olmayan bir dosyada", pc),
+                    format!("the position of instruction {} is in a file that doesn't exist", pc),
+                ));
+            }
+        }
+
+        // the innermost function or block every instruction is in, by where it starts; closing
+        // instructions are inside what they close
+        let mut scopes: Vec<Option<usize>> = Vec::with_capacity(len + 1);
+        let mut open: Vec<usize> = vec![];
+        for (pc, ins) in program.code.iter().enumerate() {
+            match *ins {
+                Instruction::Sabit(i) => {
+                    self.check(pc, i, program.consts.len(), ("sabit", "constant"))?
+                }
+                Instruction::Identifier(i)
+                | Instruction::Yerel(i)
+                | Instruction::Global(i)
+                | Instruction::Koy(i)
+                | Instruction::İkiNokta(i)
+                | Instruction::Tipinde(i)
+                | Instruction::LibSymbol(i) => self.name(pc, i)?,
+                Instruction::İçeAktar(i) => {
+                    self.check(pc, i, program.imports.len(), ("içe aktarma", "import"))?;
+                    let import = &program.imports[i as usize];
+                    self.name(pc, import.root.0)?;
+                    for (member, _) in import.members.iter() {
+                        self.name(pc, *member)?;
+                    }
+                    if !import.all && import.members.is_empty() {
+                        return Err(self.error(
+                            pc,
+                            "içe aktarma hiçbir şey içe aktarmıyor".to_string(),
+                            "import does not import anything".to_string(),
+                        ));
+                    }
+                }
+                Instruction::İşlev { ad, son } => {
+                    self.name(pc, ad)?;
+                    let son = son as usize;
+                    if son <= pc || !matches!(program.code.get(son), Some(Instruction::İşlevSonlandır)) {
+                        return Err(self.error(
+                            pc,
+                            format!("işlev {} numaralı komutta sonlanmıyor", son),
+                            format!("function does not end at instruction {}", son),
+                        ));
+                    }
+                }
+                Instruction::Blok(i) => self.name(pc, i)?,
+                Instruction::İşlevSonlandır => match open.last().map(|o| (*o, &program.code[*o])) {
+                    Some((o, Instruction::İşlev { son, .. })) if *son as usize == pc => {
+                        scopes.push(Some(o));
+                        open.pop();
+                        continue;
+                    }
+                    _ => return Err(self.error(
+                        pc,
+                        "işlevin sonu bir işlevi kapatmıyor".to_string(),
+                        "end of function does not close a function".to_string(),
+                    )),
+                },
+                Instruction::BlokSonlandır => match open.last().map(|o| (*o, &program.code[*o])) {
+                    Some((o, Instruction::Blok(_))) => {
+                        scopes.push(Some(o));
+                        open.pop();
+                        continue;
+                    }
+                    _ => return Err(self.error(
+                        pc,
+                        "bloğun sonu bir bloğu kapatmıyor".to_string(),
+                        "end of block does not close a block".to_string(),
+                    )),
+                },
+                Instruction::Ver => match open.last().map(|o| &program.code[*o]) {
+                    Some(Instruction::İşlev { .. }) => (),
+                    _ => return Err(self.error(
+                        pc,
+                        "`ver` bir işlevin içinde değil".to_string(),
+                        "`ver` is not inside a function".to_string(),
+                    )),
+                },
+                _ => (),
+            }
+            scopes.push(open.last().copied());
+            if let Instruction::İşlev { .. } | Instruction::Blok(_) = ins {
+                open.push(pc);
+            }
+        }
+        if let Some(o) = open.last() {
+            return Err(self.error(
+                *o,
+                "sonlandırılmamış".to_string(),
+                "it is never closed".to_string(),
+            ));
+        }
+        // jumping past the last instruction ends the program
+        scopes.push(None);
+
+        for (pc, ins) in program.code.iter().enumerate() {
+            if let Instruction::Atla(tp) | Instruction::İse(tp) | Instruction::İken(tp) = *ins {
+                let tp = tp as usize;
+                if tp > len {
+                    return Err(self.error(
+                        pc,
+                        format!("{} numaralı komuta atlıyor ancak bu komut yok", tp),
+                        format!("jumps to instruction {} which doesn't exist", tp),
+                    ));
+                }
+                if scopes[tp] != scopes[pc] {
+                    return Err(self.error(
+                        pc,
+                        format!("{} numaralı komuta atlayarak bir işlev ya da bloğa giriyor veya çıkıyor", tp),
+                        format!("jumping to instruction {} enters or leaves a function or block", tp),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 use crate::util::{get_lang, SupportedLanguage};
 use std::error;
 use std::fmt;
+use std::io::{self, Write};
 use std::process::exit;
 use serde::{Serialize, Deserialize};
 
@@ -49,16 +50,24 @@ impl Error {
         exit(1);
     }
     pub fn warn(&self) {
-        self.print(match get_lang() {
+        self.warn_to(&mut io::stderr());
+    }
+    /// Writes the warning to `out` instead of the standard error
+    pub fn warn_to(&self, out: &mut dyn Write) {
+        let _ = self.write(out, match get_lang() {
             SupportedLanguage::Turkish => ("UYARI", "Satır", "Sütun"),
             SupportedLanguage::English => ("WARNING", "Line", "Column"),
-        })
+        });
     }
     pub fn error_print(&self) {
-        self.print(match get_lang() {
+        self.error_to(&mut io::stderr());
+    }
+    /// Writes the error to `out` instead of the standard error
+    pub fn error_to(&self, out: &mut dyn Write) {
+        let _ = self.write(out, match get_lang() {
             SupportedLanguage::Turkish => ("HATA", "Satır", "Sütun"),
             SupportedLanguage::English => ("ERROR", "Line", "Column"),
-        })
+        });
     }
     pub fn auto_print(&self) {
         if self.is_warning {
@@ -74,23 +83,25 @@ impl Error {
             self.error();
         }
     }
-    fn print(&self, (title, line, col): (&str, &str, &str)) {
-        eprintln!();
+    fn write(&self, out: &mut dyn Write, (title, line, col): (&str, &str, &str)) -> io::Result<()> {
+        writeln!(out)?;
         for position in &self.traceback {
-            eprintln!(
+            writeln!(
+                out,
                 "[{}] {}, {} {:?}, {} {:?}{}",
                 title, position.file, line, position.line, col, position.col,
                 if let Some(a) = &position.function_name {
                     format!(": {}", a)
                 } else { "".to_string() }
-            );
+            )?;
         }
-        eprintln!("    {}: {}", self.name, self.explanation);
+        writeln!(out, "    {}: {}", self.name, self.explanation)?;
         if let Some(note) = self.after_note.clone() {
             for line in note.lines() {
-                eprintln!("    {line}");
+                writeln!(out, "    {line}")?;
             }
         }
+        Ok(())
     }
     pub fn new(
         name: &str,
@@ -1,4 +1,4 @@
-use std::{error, any::Any};
+use std::{error, any::Any, rc::Rc};
 
 use dyn_clonable::*;
 
@@ -30,19 +30,22 @@ pub fn terminate_library(name: &str, lib: Library, stack: &mut StackMemory, hash
     Ok(())
 }
 
-#[clonable]
-pub trait FfiObject: Clone + 'static {
-    fn repr(&self) -> String;
-    fn as_any(&'static self) -> &'static dyn Any where Self: Sized {
+/// Gives access to the concrete type behind a `dyn FfiObject`, implemented for every type
+pub trait AsAny {
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: Any> AsAny for T {
+    fn as_any(&self) -> &dyn Any {
         self
     }
-    fn extract<T>(&'static self) -> Option<&T>
-    where
-        T: FfiObject + 'static,
-        Self: Sized
-    {
-        self.as_any().downcast_ref::<T>()
-    }
+}
+
+// the traces are taken as `&Vec`s, which implementations outside of tr-lang rely on
+#[allow(clippy::ptr_arg)]
+#[clonable]
+pub trait FfiObject: AsAny + Clone + 'static {
+    fn repr(&self) -> String;
     fn destroy(&mut self);
 
     fn add(&self, _o: Object, trace: &Vec<Trace>) -> Result<Object, Error> {
@@ -113,22 +116,54 @@ pub trait FfiObject: Clone + 'static {
     }
 }
 
+impl dyn FfiObject {
+    /// Returns the object as `T` if it is one
+    pub fn downcast_ref<T: FfiObject>(&self) -> Option<&T> {
+        self.as_any().downcast_ref::<T>()
+    }
+}
+
+pub type FfiFn = fn(&mut StackMemory, &mut HashMemory) -> Result<Option<Object>, Error>;
+
+/// What an [`FfiFunction`] runs, a function or a closure that can keep state of its own
+pub type FfiClosure = dyn Fn(&mut StackMemory, &mut HashMemory) -> Result<Option<Object>, Error>;
+
+/// A function implemented in Rust, copies of it share the same closure
+///
+/// This used to hold a `Box<FfiFn>`, so functions that were made with
+/// `FfiFunction(Box::new(f))` are now made with `FfiFunction::new(f)`, `FfiFunction::from(f)` or
+/// the [`make_function!`](crate::make_function) macro. A `Box<FfiFn>` can still be turned into one with `From`.
 #[derive(Clone)]
-pub struct FfiFunction(pub Box<fn(&mut StackMemory, &mut HashMemory) -> Result<Option<Object>, Error>>);
+pub struct FfiFunction(pub Rc<FfiClosure>);
+
+impl From<FfiFn> for FfiFunction {
+    fn from(f: FfiFn) -> Self {
+        Self::new(f)
+    }
+}
+
+impl From<Box<FfiFn>> for FfiFunction {
+    fn from(f: Box<FfiFn>) -> Self {
+        Self::new(*f)
+    }
+}
 
 impl FfiFunction {
+    pub fn new(f: impl Fn(&mut StackMemory, &mut HashMemory) -> Result<Option<Object>, Error> + 'static) -> Self {
+        Self(Rc::new(f))
+    }
     pub fn call(&self, s: &mut StackMemory, h: &mut HashMemory) -> Result<Option<Object>, Error> {
         self.fun()(s, h)
     }
-    pub fn fun(&self) -> &Box<fn(&mut StackMemory, &mut HashMemory) -> Result<Option<Object>, Error>> {
-        &self.0
+    pub fn fun(&self) -> &FfiClosure {
+        &*self.0
     }
 }
 
 #[macro_export]
 macro_rules! make_function {
     ($f:expr) => {
-        $crate::mem::Object::FfiFunction($crate::ffi::FfiFunction(Box::new($f)))
+        $crate::mem::Object::FfiFunction($crate::ffi::FfiFunction::new($f))
     };
 }
 
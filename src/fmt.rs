@@ -6,9 +6,9 @@ pub enum IndentOptions {
     Spaces(u8),
     Tabs,
 }
-impl Into<String> for IndentOptions {
-    fn into(self) -> String {
-        match self {
+impl From<IndentOptions> for String {
+    fn from(indent: IndentOptions) -> String {
+        match indent {
             IndentOptions::Tabs => "\t".to_string(),
             IndentOptions::Spaces(spaces) => {
                 let mut s = String::new();
@@ -78,7 +78,7 @@ impl Fmt {
                 },
                 LexerTokenType::Yoksa => {
                     let repr = token.repr();
-                    if !result.lines().last().unwrap().trim().is_empty() {
+                    if !result.lines().next_back().unwrap().trim().is_empty() {
                         result += self._line_ending.as_ref();
                         for _ in 1..indent_level {
                             result += &space;
@@ -101,19 +101,19 @@ impl Fmt {
                     at_line_start = true;
                     indent_level -= 1;
                     for _ in 0..Into::<String>::into(self._indent.clone()).len() {
-                        if result.chars().filter(|c| c != &'\x08').last().unwrap().is_whitespace() {
+                        if result.chars().rfind(|c| c != &'\x08').unwrap().is_whitespace() {
                             result += "\x08";
                         } else {
                             result += " ";
                         }
                     }
                     result += &repr;
-                    if indent_level <= 0 {
+                    if indent_level == 0 {
                         result += self._line_ending.as_ref();
                     }
                 }
                 LexerTokenType::İşlev | LexerTokenType::Blok => {
-                    if !result.lines().last().unwrap().chars().all(|c| c.is_whitespace()) {
+                    if !result.lines().next_back().unwrap().chars().all(|c| c.is_whitespace()) {
                         result += self._line_ending.as_ref();
                     }
                     let repr = token.repr();
@@ -150,13 +150,13 @@ impl Fmt {
                 }
                 LexerTokenType::EOF => (),
                 LexerTokenType::İkiNokta => {
-                    if result.chars().filter(|c| c != &'\x08').last().unwrap().is_whitespace() {
+                    if result.chars().rfind(|c| c != &'\x08').unwrap().is_whitespace() {
                         result += "\x08";
                     }
                     result += &token.repr()
                 }
                 LexerTokenType::ParenR | LexerTokenType::InScopeParentR => {
-                    if result.chars().filter(|c| c != &'\x08').last().unwrap().is_whitespace() {
+                    if result.chars().rfind(|c| c != &'\x08').unwrap().is_whitespace() {
                         result += "\x08";
                     }
                     result += &token.repr();
@@ -167,14 +167,14 @@ impl Fmt {
                 }
                 LexerTokenType::ParenL | LexerTokenType::InScopeParentL | LexerTokenType::Değildir => result += &token.repr(),
                 LexerTokenType::Comma => {
-                    if result.chars().filter(|c| c != &'\x08').last().unwrap().is_whitespace() {
+                    if result.chars().rfind(|c| c != &'\x08').unwrap().is_whitespace() {
                         result += "\x08";
                     }
                     result += &token.repr();
                     result += " ";
                 }
                 LexerTokenType::Tipinde => {
-                    if result.chars().filter(|c| c != &'\x08').last().unwrap().is_whitespace() {
+                    if result.chars().rfind(|c| c != &'\x08').unwrap().is_whitespace() {
                         result += "\x08";
                     }
                     result += &token.repr();
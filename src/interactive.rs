@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use crate::bytecode::compile;
 use crate::error::Error;
 use crate::lexer::Lexer;
 use crate::parser::Parser;
@@ -14,6 +15,7 @@ use rustyline::Editor;
 
 // TODO: Finish completer
 /// Completer in progress
+#[allow(dead_code)]
 struct InteractiveCompleter;
 impl Completer for InteractiveCompleter {
     type Candidate = Pair;
@@ -159,9 +161,9 @@ impl Interactive {
             let rl = editor.readline(&pr);
             match rl {
                 Ok(buf) => {
-                    if buf.starts_with('#') {
+                    if let Some(stripped) = buf.strip_prefix('#') {
                         let (cmd, args): (String, Vec<String>) = {
-                            let mut t = buf[1..].split_whitespace().map(String::from);
+                            let mut t = stripped.split_whitespace().map(String::from);
                             let cmd = t.next().unwrap();
                             (cmd, t.collect())
                         };
@@ -176,8 +178,8 @@ impl Interactive {
                                                 e.error_print();
                                                 continue;
                                             }
-                                        }.parse() {
-                                            Ok(ptk) => ptk,
+                                        }.parse().and_then(compile) {
+                                            Ok(program) => program,
                                             Err(e) => { e.error_print(); continue; }
                                         },
                                     )
@@ -191,7 +193,7 @@ impl Interactive {
                                         (s, h)
                                     });
                                     println!();
-                                    if memcs.len() > 0 {
+                                    if !memcs.is_empty() {
                                         println!("=> {:?}", memcs.iter_vec());
                                     }
                                     if self.opt.reset_buf_on_run {
@@ -5,6 +5,7 @@ use crate::util::SupportedLanguage;
 use crate::util::get_lang;
 use crate::util::{char_in_str, in_vec, read_file, FSErr};
 use crate::error::Error;
+use crate::stdlib;
 
 use std::fs::canonicalize;
 use std::path::PathBuf;
@@ -66,13 +67,24 @@ impl Lexer {
                         })
                     };
                     match next_token.typ {
+                        TokenType::Yazı if stdlib::module(&next_token.lexeme).is_some() => {
+                            let res = Ok(vec![Token::new(
+                                TokenType::LibSymbol(next_token.lexeme),
+                                "".to_string(),
+                                next_token.line,
+                                next_token.col,
+                                file.clone(),
+                                Precedence::Reserved,
+                            )]);
+                            current = self.include(&prog, current, res, &mut tokens, file.clone())?;
+                        }
                         TokenType::Yazı => {
                             let mut tmp_visited = visited.clone();
                             let mut path = canonicalize(PathBuf::from(file.clone()))
-                                .expect(match get_lang() {
+                                .unwrap_or_else(|_| panic!("{}", match get_lang() {
                                     SupportedLanguage::Turkish => "yol normalleştirilemedi",
                                     SupportedLanguage::English => "couldn't canonicalize path",
-                                });
+                                }));
                             if path.is_dir() {
                                 path.push(next_token.lexeme);
                             } else {
@@ -141,97 +153,7 @@ impl Lexer {
                                     )])
                                 };
 
-                                let next_token = match prog.get(current + 2) {
-                                    Some(e) => e.clone(),
-                                    None => return Err(match get_lang() {
-                                        SupportedLanguage::Turkish => Error::new(
-                                            "BeklenmedikSimge",
-                                            "yükle <yazı> dan sonra `*` veya `->` bekleniyordu ancak bulunamadı",
-                                            vec![(0, 0, file, None)],
-                                            None,
-                                        ),
-                                        SupportedLanguage::English => Error::new(
-                                            "BeklenmedikSimge",
-                                            "expected `*` or `->` after `yükle <string>` but couldn't find any",
-                                            vec![(0, 0, file, None)],
-                                            None,
-                                        ),
-                                    }),
-                                };
-                                match next_token.typ {
-                                    TokenType::Çarpı => {
-                                        tokens.append(&mut res?);
-                                        current += 3;
-                                    }
-                                    TokenType::Koy => {
-                                        tokens.push(Token::new(
-                                            TokenType::Blok,
-                                            "blok".to_string(),
-                                            next_token.line,
-                                            next_token.col,
-                                            next_token.file.clone(),
-                                            Precedence::Reserved,
-                                        ));
-                                        let next_token = match prog.get(current + 3) {
-                                            Some(e) => e.clone(),
-                                            None => return Err(match get_lang() {
-                                                SupportedLanguage::Turkish => Error::new(
-                                                    "BeklenmedikSimge",
-                                                    "`yükle <yazı> ->` dan sonra tanımlayıcı bekleniyordu ancak bulunamadı",
-                                                    vec![(0, 0, file, None)],
-                                                    None,
-                                                ),
-                                                SupportedLanguage::English => Error::new(
-                                                    "BeklenmedikSimge",
-                                                    "expected identifier after `yükle <string> ->` but couldn't find it",
-                                                    vec![(0, 0, file, None)],
-                                                    None,
-                                                ),
-                                            }),
-                                        };
-                                        match next_token.typ {
-                                            TokenType::Identifier => tokens.push(next_token.clone()),
-                                            _ => return Err(match get_lang() {
-                                                SupportedLanguage::Turkish => Error::new(
-                                                    "BeklenmedikSimge",
-                                                    "`yükle <yazı> ->` dan sonra tanımlayıcı bekleniyordu ancak bulunamadı",
-                                                    vec![(0, 0, file, None)],
-                                                    None,
-                                                ),
-                                                SupportedLanguage::English => Error::new(
-                                                    "BeklenmedikSimge",
-                                                    "expected identifier after `yükle <string> ->` but couldn't find it",
-                                                    vec![(0, 0, file, None)],
-                                                    None,
-                                                ),
-                                            }),
-                                        }
-                                        tokens.append(&mut res?);
-                                        tokens.push(Token::new(
-                                            TokenType::Son,
-                                            "son".to_string(),
-                                            next_token.line,
-                                            next_token.col,
-                                            next_token.file,
-                                            Precedence::Reserved,
-                                        ));
-                                        current += 4;
-                                    }
-                                    _ => return Err(match get_lang() {
-                                        SupportedLanguage::Turkish => Error::new(
-                                            "BeklenmedikSimge",
-                                            "`yükle <yazı>` dan sonra `*` veya `->` bekleniyordu ancak bulunamadı",
-                                            vec![(next_token.line, next_token.col, next_token.file, None)],
-                                            None,
-                                        ),
-                                        SupportedLanguage::English => Error::new(
-                                            "BeklenmedikSimge",
-                                            "expected `*` or `->` after `yükle <string>` but couldn't find any",
-                                            vec![(next_token.line, next_token.col, next_token.file, None)],
-                                            None,
-                                        ),
-                                    }),
-                                }
+                                current = self.include(&prog, current, res, &mut tokens, file.clone())?;
                             } else {
                                 *visited = tmp_visited;
                             }
@@ -307,6 +229,101 @@ impl Lexer {
         }
         Ok(tokens)
     }
+    /// Expands `yükle <yazı>` into the tokens of the loaded module, either in place (`*`) or in a
+    /// block (`-> ad`), returning the position after the import statement
+    fn include(&self, prog: &[Token], current: usize, res: Result<Vec<Token>, Error>, tokens: &mut Vec<Token>, file: String) -> Result<usize, Error> {
+        let next_token = match prog.get(current + 2) {
+            Some(e) => e.clone(),
+            None => return Err(match get_lang() {
+                SupportedLanguage::Turkish => Error::new(
+                    "BeklenmedikSimge",
+                    "yükle <yazı> dan sonra `*` veya `->` bekleniyordu ancak bulunamadı",
+                    vec![(0, 0, file, None)],
+                    None,
+                ),
+                SupportedLanguage::English => Error::new(
+                    "BeklenmedikSimge",
+                    "expected `*` or `->` after `yükle <string>` but couldn't find any",
+                    vec![(0, 0, file, None)],
+                    None,
+                ),
+            }),
+        };
+        match next_token.typ {
+            TokenType::Çarpı => {
+                tokens.append(&mut res?);
+                Ok(current + 3)
+            }
+            TokenType::Koy => {
+                tokens.push(Token::new(
+                    TokenType::Blok,
+                    "blok".to_string(),
+                    next_token.line,
+                    next_token.col,
+                    next_token.file.clone(),
+                    Precedence::Reserved,
+                ));
+                let next_token = match prog.get(current + 3) {
+                    Some(e) => e.clone(),
+                    None => return Err(match get_lang() {
+                        SupportedLanguage::Turkish => Error::new(
+                            "BeklenmedikSimge",
+                            "`yükle <yazı> ->` dan sonra tanımlayıcı bekleniyordu ancak bulunamadı",
+                            vec![(0, 0, file, None)],
+                            None,
+                        ),
+                        SupportedLanguage::English => Error::new(
+                            "BeklenmedikSimge",
+                            "expected identifier after `yükle <string> ->` but couldn't find it",
+                            vec![(0, 0, file, None)],
+                            None,
+                        ),
+                    }),
+                };
+                match next_token.typ {
+                    TokenType::Identifier => tokens.push(next_token.clone()),
+                    _ => return Err(match get_lang() {
+                        SupportedLanguage::Turkish => Error::new(
+                            "BeklenmedikSimge",
+                            "`yükle <yazı> ->` dan sonra tanımlayıcı bekleniyordu ancak bulunamadı",
+                            vec![(0, 0, file, None)],
+                            None,
+                        ),
+                        SupportedLanguage::English => Error::new(
+                            "BeklenmedikSimge",
+                            "expected identifier after `yükle <string> ->` but couldn't find it",
+                            vec![(0, 0, file, None)],
+                            None,
+                        ),
+                    }),
+                }
+                tokens.append(&mut res?);
+                tokens.push(Token::new(
+                    TokenType::Son,
+                    "son".to_string(),
+                    next_token.line,
+                    next_token.col,
+                    next_token.file,
+                    Precedence::Reserved,
+                ));
+                Ok(current + 4)
+            }
+            _ => Err(match get_lang() {
+                SupportedLanguage::Turkish => Error::new(
+                    "BeklenmedikSimge",
+                    "`yükle <yazı>` dan sonra `*` veya `->` bekleniyordu ancak bulunamadı",
+                    vec![(next_token.line, next_token.col, next_token.file, None)],
+                    None,
+                ),
+                SupportedLanguage::English => Error::new(
+                    "BeklenmedikSimge",
+                    "expected `*` or `->` after `yükle <string>` but couldn't find any",
+                    vec![(next_token.line, next_token.col, next_token.file, None)],
+                    None,
+                ),
+            }),
+        }
+    }
     /// The tokenizer; it will try to tokenize the source code,
     ///
     /// If it encounters errors it will send `Err(tr_lang::error::Error)` back
@@ -764,6 +781,12 @@ impl Lexer {
                         self.col += 1;
                     }
 
+                    if buf == "b" && self.source.len() > self.current && matches!(self.currentc(), '\'' | '"') {
+                        let token = self.bytes(file.clone())?;
+                        tokens.push(token);
+                        continue;
+                    }
+
                     match buf.as_str() {
                         "at" => tokens.push(Token::new(
                             TokenType::At,
@@ -913,6 +936,96 @@ impl Lexer {
             Ok(tokens)
         }
     }
+    /// Lexes a `Baytlar` literal, `b` has already been consumed and the current character is the
+    /// opening quote
+    fn bytes(&mut self, file: String) -> Result<Token, Error> {
+        let quote = self.currentc();
+        let start = self.current - 1;
+        let (start_line, start_col) = (self.line, self.col - 1);
+        let unterminated = || match get_lang() {
+            SupportedLanguage::English => Error::new(
+                "SözdizimHatası",
+                "unterminated byte string",
+                vec![(start_line, start_col, file.clone(), None)], None,
+            ),
+            SupportedLanguage::Turkish => Error::new(
+                "SözdizimHatası",
+                "bitirilmemiş bayt dizisi",
+                vec![(start_line, start_col, file.clone(), None)], None,
+            ),
+        };
+        let mut buf = vec![];
+
+        self.current += 1;
+        self.col += 1;
+        loop {
+            let c = match self.source.get(self.current) {
+                Some(c) if *c == quote => break,
+                Some(c) => *c,
+                None => return Err(unterminated()),
+            };
+            if c == '\n' {
+                self.line += 1;
+                self.col = 1;
+            } else {
+                self.col += 1;
+            }
+            self.current += 1;
+            if c != '\\' {
+                let mut enc = [0; 4];
+                buf.extend_from_slice(c.encode_utf8(&mut enc).as_bytes());
+                continue;
+            }
+            let e = match self.source.get(self.current) {
+                Some(e) => *e,
+                None => return Err(unterminated()),
+            };
+            self.current += 1;
+            self.col += 1;
+            match e {
+                't' => buf.push(b'\t'),
+                'n' => buf.push(b'\n'),
+                'r' => buf.push(b'\r'),
+                '"' => buf.push(b'"'),
+                '\'' => buf.push(b'\''),
+                '\\' => buf.push(b'\\'),
+                '\n' | '\t' => (),
+                'x' => {
+                    let hex: String = self.source.iter().skip(self.current).take(2).collect();
+                    match u8::from_str_radix(&hex, 16) {
+                        Ok(b) if hex.len() == 2 => buf.push(b),
+                        _ => return Err(match get_lang() {
+                            SupportedLanguage::Turkish => Error::new(
+                                "SözdizimHatası",
+                                &format!("geçersiz bayt kaçışı: `\\x{}`", hex),
+                                vec![(self.line, self.col, file.clone(), None)], None,
+                            ),
+                            SupportedLanguage::English => Error::new(
+                                "SözdizimHatası",
+                                &format!("invalid byte escape: `\\x{}`", hex),
+                                vec![(self.line, self.col, file.clone(), None)], None,
+                            ),
+                        }),
+                    }
+                    self.current += 2;
+                    self.col += 2;
+                }
+                e => {
+                    buf.push(b'\\');
+                    let mut enc = [0; 4];
+                    buf.extend_from_slice(e.encode_utf8(&mut enc).as_bytes());
+                }
+            }
+        }
+        self.current += 1;
+        self.col += 1;
+        Ok(Token::new(
+            TokenType::Baytlar(buf),
+            self.source[start..self.current].iter().collect(),
+            self.line, self.col, file,
+            Precedence::None,
+        ))
+    }
     fn currentc(&self) -> char {
         *self.source.get(self.current).unwrap()
     }
@@ -1,5 +1,4 @@
 #![feature(io_error_more)]
-#![feature(associated_type_defaults)]
 
 //! tr-lang programming language is a language that aims to make programming simpler for
 //! turkish speaking groups
@@ -18,6 +17,7 @@ pub mod runtime;
 #[cfg(feature = "interactive")]
 pub mod interactive;
 pub mod ffi;
+pub mod stdlib;
 
 pub mod prelude {
     use crate::*;
@@ -37,7 +37,7 @@ macro_rules! run {
             buf.push(' ');
         )*
         let p = $crate::parser::Parser::from_lexer(&mut $crate::lexer::Lexer::new(buf), $fl)
-            .map(|mut a| a.parse().map(|b| $crate::runtime::Run::new(b).run($crate::runtime::RunConfig::default())));
+            .map(|mut a| a.parse().and_then($crate::bytecode::compile).map(|b| $crate::runtime::Run::new(b).run($crate::runtime::RunConfig::default())));
     };
     ($fl:ident; $($x:tt)*) => {
         let mut buf = String::new();
@@ -46,7 +46,7 @@ macro_rules! run {
             buf.push(' ');
         )*
         let p = $crate::parser::Parser::from_lexer(&mut $crate::lexer::Lexer::new(buf), stringify!($fl).to_string())
-            .map(|mut a| a.parse().map(|b| $crate::runtime::Run::new(b).run($crate::runtime::RunConfig::default())));
+            .map(|mut a| a.parse().and_then($crate::bytecode::compile).map(|b| $crate::runtime::Run::new(b).run($crate::runtime::RunConfig::default())));
     };
     ($($x:tt)*) => {
         let mut buf = String::new();
@@ -55,7 +55,7 @@ macro_rules! run {
             buf.push(' ');
         )*
         let p = $crate::parser::Parser::from_lexer(&mut $crate::lexer::Lexer::new(buf), "rust".to_string())
-            .map(|mut a| a.parse().map(|b| $crate::runtime::Run::new(b).run($crate::runtime::RunConfig::default())));
+            .map(|mut a| a.parse().and_then($crate::bytecode::compile).map(|b| $crate::runtime::Run::new(b).run($crate::runtime::RunConfig::default())));
     };
 }
 
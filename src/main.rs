@@ -28,6 +28,7 @@ use error::Error;
 pub mod runtime;
 
 pub mod ffi;
+pub mod stdlib;
 
 mod argsparser;
 #[cfg(feature = "interactive")]
@@ -35,7 +36,7 @@ mod interactive;
 
 fn main() {
     let args = argsparser::parse_args();
-    if args.help == true {
+    if args.help {
         utilbin::print_help(args.help_exitc, args.name);
     }
     if args.version {
@@ -94,7 +95,7 @@ fn main() {
                     .clone()
                     .tokenize(&mut vec![canon_path.clone()], canon_path)
                     .unwrap_or_else(|e| e.error());
-                println!("{:#?}", &lexed);
+                println!("{:#?}", lexed);
             }
 
             let mut parser = match Parser::from_lexer(&mut lexer, args.file.clone()) {
@@ -106,7 +107,11 @@ fn main() {
                 println!("{:#?}", parsed.clone());
             }
 
-            let encoded = bytecode::to_bytecode(parsed.clone());
+            let mut program = bytecode::compile(parsed).unwrap_or_else(|e| e.error());
+            if args.optimize {
+                program = bytecode::optimize(program);
+            }
+            let encoded = bytecode::to_bytecode(&program);
             {
                 let mut bytecode_src = fs::File::create(&match &args.outfile {
                     Some(f) => f.clone(),
@@ -179,13 +184,18 @@ fn main() {
             };
             let parsed = parser.clone().parse().unwrap_or_else(|e| e.error());
             if args.prs_out {
-                println!("{:#?}", &parsed);
+                println!("{:#?}", parsed);
             }
 
-            let mut run = runtime::Run::new(parsed);
+            let mut program = bytecode::compile(parsed).unwrap_or_else(|e| e.error());
+            if args.optimize {
+                program = bytecode::optimize_program(program);
+            }
+            let mut run = runtime::Run::new(program);
             run.run(runtime::RunConfig {
                 file: args.file,
                 supress_warnings: args.supress_warnings,
+                permissions: args.permissions,
                 ..Default::default()
             })
                 .unwrap_or_else(|(s, h, a)| { a.auto(); (s, h) });
@@ -193,25 +203,43 @@ fn main() {
         argsparser::Subcommands::RunBytes => {
             let path = PathBuf::from(args.file.clone());
             let con = utilbin::read_file_to_vec_u8(&path);
-            let parsed = bytecode::from_bytecode(&con[..]);
+            let program = bytecode::from_bytecode(&con[..], &args.file).unwrap_or_else(|e| e.error());
 
-            let mut run = runtime::Run::new(parsed);
+            let mut run = runtime::Run::new(program);
             run.run(runtime::RunConfig {
                 file: args.file,
                 supress_warnings: args.supress_warnings,
+                permissions: args.permissions,
                 ..Default::default()
             })
                 .unwrap_or_else(|(s, h, a)| { a.auto(); (s, h) });
         }
+        argsparser::Subcommands::Disassemble => {
+            let path = PathBuf::from(args.file.clone());
+            let con = utilbin::read_file_to_vec_u8(&path);
+            let program = bytecode::read_bytecode(&con[..], &args.file).unwrap_or_else(|e| e.error());
+            if args.json {
+                print!("{}", bytecode::disassemble_json(&program));
+            } else {
+                print!("{}", bytecode::disassemble(&program));
+            }
+            // the listing is still shown for programs that would be rejected, to help find out why
+            if let Err(e) = bytecode::verify(&program, &args.file) {
+                e.error();
+            }
+        }
         argsparser::Subcommands::Command => {
-            runtime::Run::new(
-                match Parser::from_lexer(&mut Lexer::new(args.file), ".".to_string()) {
-                    Ok(p) => p,
-                    Err(e) => e.error(),
-                }.parse().unwrap_or_else(|e| e.error()),
-            )
+            let mut program = match Parser::from_lexer(&mut Lexer::new(args.file), ".".to_string()) {
+                Ok(p) => p,
+                Err(e) => e.error(),
+            }.parse().and_then(bytecode::compile).unwrap_or_else(|e| e.error());
+            if args.optimize {
+                program = bytecode::optimize_program(program);
+            }
+            runtime::Run::new(program)
             .run(runtime::RunConfig {
                 supress_warnings: args.supress_warnings,
+                permissions: args.permissions,
                 ..Default::default()
             })
             .unwrap_or_else(|(s, h, a)| { a.auto(); (s, h) });
@@ -268,7 +296,7 @@ fn main() {
                 .tokenize(&mut vec![canon_path.clone()], canon_path)
                 .unwrap_or_else(|e| e.error());
             if args.lex_out {
-                println!("{:#?}", &lexed);
+                println!("{:#?}", lexed);
             }
 
             let fmtr = fmt::Fmt::new(lexed)
@@ -1,68 +1,135 @@
-use crate::hashmap;
 use crate::mem::Object;
 use std::collections::HashMap;
 
 pub type Stack = Vec<Object>;
 type GStack = Vec<Stack>;
-type GHash = Vec<HashMap<String, Object>>;
 
 #[derive(Clone, Debug)]
 pub struct StackMemory {
     gstack: GStack,
+    /// How many values are on the stack
+    len: usize,
+    /// Roughly how many bytes the values on the stack take up, see [`Object::size`], only counted
+    /// once asked for with [`StackMemory::count_bytes`]
+    bytes: Option<usize>,
 }
 
+/// The variables of every scope
+///
+/// Every name is given a slot the first time it is seen, variables are stored and looked up by
+/// their slot. The global scope is indexed by slot directly, other scopes hold the few variables
+/// they define along with their slots.
 #[derive(Clone, Debug)]
 pub struct HashMemory {
-    ghashs: GHash,
+    names: Vec<String>,
+    slots: HashMap<String, usize>,
+    global: Vec<Option<Object>>,
+    /// Every scope other than the global one, innermost last
+    scopes: Vec<Vec<(usize, Object)>>,
+    /// How many variables there are
+    len: usize,
+    /// Roughly how many bytes the variables take up, see [`Object::size`], only counted once asked
+    /// for with [`HashMemory::count_bytes`]
+    bytes: Option<usize>,
+}
+
+impl Default for StackMemory {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl StackMemory {
     pub fn new() -> Self {
         Self {
             gstack: vec![vec![]],
+            len: 0,
+            bytes: None,
         }
     }
 
+    fn count(&mut self, a: &Object) {
+        self.len += 1;
+        if let Some(bytes) = &mut self.bytes {
+            *bytes += a.size();
+        }
+    }
+    fn uncount(&mut self, a: &Object) {
+        self.len -= 1;
+        if let Some(bytes) = &mut self.bytes {
+            // values can grow in place after being counted
+            *bytes = bytes.saturating_sub(a.size());
+        }
+    }
+
+    /// Starts or stops counting how many bytes the values on the stack take up
+    ///
+    /// Working out the size of every value pushed and popped takes time, so it is only done for
+    /// runs with a [`max_memory`](crate::runtime::RunConfig::max_memory).
+    pub fn count_bytes(&mut self, count: bool) {
+        self.bytes = match (count, self.bytes) {
+            (false, _) => None,
+            (true, None) => Some(self.gstack.iter().flatten().map(Object::size).sum()),
+            (true, bytes) => bytes,
+        };
+    }
+
     // Stack Manipulation
     pub fn pop(&mut self) -> Option<Object> {
         for stack in self.gstack.iter_mut().rev() {
-            match stack.pop() {
-                Some(a) => return Some(a.clone()),
-                None => (),
+            if let Some(a) = stack.pop() {
+                self.uncount(&a);
+                return Some(a);
             }
         }
         None
     }
     pub fn push(&mut self, a: Object) {
+        self.count(&a);
         if let Some(last) = self.gstack.last_mut() {
             last.push(a);
         }
     }
     pub fn push_glob(&mut self, a: Object) {
+        self.count(&a);
         if let Some(first) = self.gstack.first_mut() {
             first.push(a);
         }
     }
     pub fn push_ret(&mut self, a: Object) {
         let glen = self.gstack.len();
-        if let Some(ret) = self.gstack.get_mut(glen - 2) {
-            ret.push(a);
+        if glen >= 2 {
+            self.count(&a);
+            self.gstack[glen - 2].push(a);
         }
     }
     pub fn new_stack(&mut self) {
         self.gstack.push(vec![]);
     }
     pub fn del_stack(&mut self) -> Option<Stack> {
-        self.gstack.pop()
+        let stack = self.gstack.pop()?;
+        for a in stack.iter() {
+            self.uncount(a);
+        }
+        Some(stack)
     }
 
     // Stack Info
+    /// The number of stacks, one for the global scope and one for every function and block being
+    /// run
+    pub fn depth(&self) -> usize {
+        self.gstack.len()
+    }
     pub fn len(&self) -> usize {
-        let mut l: usize = 0;
-        for stack in self.gstack.iter() {
-            l += stack.len();
-        }
-        l
+        self.len
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Roughly how many bytes the values on the stack take up, `None` unless they are being
+    /// counted, see [`StackMemory::count_bytes`]
+    pub fn bytes(&self) -> Option<usize> {
+        self.bytes
     }
 
     // Iter
@@ -71,9 +138,29 @@ impl StackMemory {
         for stack in self.gstack.iter_mut() {
             s.append(stack);
         }
+        self.len = 0;
+        if self.bytes.is_some() {
+            self.bytes = Some(0);
+        }
         s
     }
 
+    /// Every stack, the global one first
+    pub(crate) fn layers(&self) -> &[Stack] {
+        &self.gstack
+    }
+
+    pub(crate) fn from_layers(gstack: Vec<Stack>) -> Self {
+        let mut stack = Self::new();
+        for a in gstack.iter().flatten() {
+            stack.count(a);
+        }
+        if !gstack.is_empty() {
+            stack.gstack = gstack;
+        }
+        stack
+    }
+
     pub fn destroy_ffi_objects(&mut self) {
         for i in self.iter_vec().iter_mut() {
             if let Object::FfiObject(o) = i {
@@ -83,68 +170,223 @@ impl StackMemory {
     }
 }
 
+impl Default for HashMemory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl HashMemory {
     pub fn new() -> Self {
         Self {
-            ghashs: vec![hashmap! {}],
+            names: vec![],
+            slots: HashMap::new(),
+            global: vec![],
+            scopes: vec![],
+            len: 0,
+            bytes: None,
+        }
+    }
+
+    fn count(&mut self, val: &Object) {
+        self.len += 1;
+        if let Some(bytes) = &mut self.bytes {
+            *bytes += val.size();
+        }
+    }
+    fn uncount(&mut self, val: &Object) {
+        self.len -= 1;
+        if let Some(bytes) = &mut self.bytes {
+            *bytes = bytes.saturating_sub(val.size());
+        }
+    }
+
+    /// Starts or stops counting how many bytes the variables take up, like
+    /// [`StackMemory::count_bytes`]
+    pub fn count_bytes(&mut self, count: bool) {
+        self.bytes = match (count, self.bytes) {
+            (false, _) => None,
+            (true, None) => {
+                let scoped = self.scopes.iter().flatten().map(|(_, val)| val);
+                Some(self.global.iter().flatten().chain(scoped).map(Object::size).sum())
+            }
+            (true, bytes) => bytes,
+        };
+    }
+
+    // Slots
+    /// The slot of `name`, giving it one if it doesn't have one
+    pub fn slot(&mut self, name: &str) -> usize {
+        match self.slots.get(name) {
+            Some(slot) => *slot,
+            None => {
+                let slot = self.names.len();
+                self.names.push(name.to_string());
+                self.slots.insert(name.to_string(), slot);
+                slot
+            }
         }
     }
+    /// The name of `slot`
+    pub fn name(&self, slot: usize) -> &str {
+        &self.names[slot]
+    }
 
     // Map Manipulation
     pub fn remove(&mut self, key: String) -> Option<Object> {
-        for hash in self.ghashs.iter_mut().rev() {
-            match hash.remove(&key) {
-                Some(a) => return Some(a.clone()),
-                None => (),
+        let slot = *self.slots.get(&key)?;
+        let mut removed = None;
+        for scope in self.scopes.iter_mut().rev() {
+            if let Some(i) = scope.iter().position(|(s, _)| *s == slot) {
+                removed = Some(scope.swap_remove(i).1);
+                break;
             }
         }
-        None
+        if removed.is_none() {
+            removed = self.global.get_mut(slot).and_then(Option::take);
+        }
+        if let Some(val) = &removed {
+            self.uncount(val);
+        }
+        removed
     }
 
     pub fn insert(&mut self, key: String, val: Object) {
-        if let Some(last) = self.ghashs.last_mut() {
-            last.insert(key, val);
+        let slot = self.slot(&key);
+        self.insert_slot(slot, val);
+    }
+
+    /// Names `val` `slot` in the innermost scope
+    pub fn insert_slot(&mut self, slot: usize, val: Object) {
+        if self.scopes.is_empty() {
+            return self.insert_glob_slot(slot, val);
+        }
+        self.count(&val);
+        let scope = self.scopes.last_mut().unwrap();
+        let old = match scope.iter_mut().find(|(s, _)| *s == slot) {
+            Some((_, v)) => Some(std::mem::replace(v, val)),
+            None => {
+                scope.push((slot, val));
+                None
+            }
+        };
+        if let Some(old) = old {
+            self.uncount(&old);
         }
     }
 
     pub fn insert_glob(&mut self, key: String, val: Object) {
-        if let Some(first) = self.ghashs.first_mut() {
-            first.insert(key, val);
+        let slot = self.slot(&key);
+        self.insert_glob_slot(slot, val);
+    }
+
+    pub fn insert_glob_slot(&mut self, slot: usize, val: Object) {
+        if self.global.len() <= slot {
+            self.global.resize_with(slot + 1, || None);
+        }
+        self.count(&val);
+        if let Some(old) = self.global[slot].replace(val) {
+            self.uncount(&old);
         }
     }
 
+    /// The number of scopes, counting the global one
+    pub fn depth(&self) -> usize {
+        self.scopes.len() + 1
+    }
+
     pub fn new_hash(&mut self) {
-        self.ghashs.push(hashmap! {});
+        self.scopes.push(vec![]);
     }
 
     pub fn del_hash(&mut self) -> Option<HashMap<String, Object>> {
-        self.ghashs.pop()
+        let scope = self.scopes.pop()?;
+        for (_, val) in scope.iter() {
+            self.uncount(val);
+        }
+        Some(scope.into_iter().map(|(s, v)| (self.names[s].clone(), v)).collect())
     }
 
     pub fn get(&mut self, key: &String) -> Option<&Object> {
-        for hash in self.ghashs.iter().rev() {
-            match hash.get(key) {
-                Some(a) => return Some(a),
-                None => (),
+        let slot = *self.slots.get(key)?;
+        self.get_slot(slot)
+    }
+
+    /// The variable in `slot`, looking from the innermost scope to the global one
+    pub fn get_slot(&self, slot: usize) -> Option<&Object> {
+        for scope in self.scopes.iter().rev() {
+            if let Some((_, v)) = scope.iter().find(|(s, _)| *s == slot) {
+                return Some(v);
             }
         }
-        None
+        self.get_glob_slot(slot)
     }
 
+    /// The variable in `slot` of the innermost scope
+    pub fn get_local_slot(&self, slot: usize) -> Option<&Object> {
+        match self.scopes.last() {
+            Some(scope) => scope.iter().find(|(s, _)| *s == slot).map(|(_, v)| v),
+            None => self.get_glob_slot(slot),
+        }
+    }
+
+    /// The variable in `slot` of the global scope
+    pub fn get_glob_slot(&self, slot: usize) -> Option<&Object> {
+        self.global.get(slot).and_then(Option::as_ref)
+    }
+
+    /// The variable named `key`, changes made to it are not counted by [`HashMemory::bytes`]
     pub fn get_mut(&mut self, key: &String) -> Option<&mut Object> {
-        for hash in self.ghashs.iter_mut().rev() {
-            match hash.get_mut(key) {
-                Some(a) => return Some(a),
-                None => (),
+        let slot = *self.slots.get(key)?;
+        for scope in self.scopes.iter_mut().rev() {
+            if let Some((_, v)) = scope.iter_mut().find(|(s, _)| *s == slot) {
+                return Some(v);
             }
         }
-        None
+        self.global.get_mut(slot).and_then(Option::as_mut)
+    }
+
+    /// How many variables there are
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Roughly how many bytes the variables take up, `None` unless they are being counted, see
+    /// [`HashMemory::count_bytes`]
+    pub fn bytes(&self) -> Option<usize> {
+        self.bytes
+    }
+
+    /// The names of the slots, the global scope and the other scopes, innermost last
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn parts(&self) -> (&[String], &[Option<Object>], &[Vec<(usize, Object)>]) {
+        (&self.names, &self.global, &self.scopes)
+    }
+
+    /// The opposite of [`HashMemory::parts`], every slot has to be named
+    pub(crate) fn from_parts(names: Vec<String>, global: Vec<Option<Object>>, scopes: Vec<Vec<(usize, Object)>>) -> Self {
+        let mut hashs = Self::new();
+        for val in global.iter().flatten().chain(scopes.iter().flatten().map(|(_, v)| v)) {
+            hashs.count(val);
+        }
+        hashs.slots = names.iter().enumerate().map(|(slot, name)| (name.clone(), slot)).collect();
+        hashs.names = names;
+        hashs.global = global;
+        hashs.scopes = scopes;
+        hashs
     }
 
     pub fn into_keys(&mut self) -> Vec<String> {
         let mut s: Vec<String> = vec![];
-        for i in self.ghashs.iter_mut().rev() {
-            s.append(&mut i.clone().into_keys().collect::<Vec<String>>());
+        for scope in self.scopes.iter().rev() {
+            s.extend(scope.iter().map(|(slot, _)| self.names[*slot].clone()));
+        }
+        for (slot, v) in self.global.iter().enumerate() {
+            if v.is_some() {
+                s.push(self.names[slot].clone());
+            }
         }
         s
     }
@@ -1,5 +1,8 @@
+#[allow(clippy::module_inception)]
 mod mem;
 mod object;
 
 pub use mem::{HashMemory, Stack, StackMemory};
-pub use object::{Map, Object};
+pub use object::{ConversionError, FromObject, IntoObject, List, Map, Object};
+#[cfg(feature = "derive")]
+pub use tr_lang_derive::{FromObject, IntoObject};
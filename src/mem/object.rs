@@ -1,36 +1,155 @@
 use crate::error::Error;
-use crate::util::{get_lang, SupportedLanguage};
+use crate::util::{bytes_repr, get_lang, SupportedLanguage};
 use crate::ffi::{FfiObject, FfiFunction};
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{self, Serialize, Serializer};
+use std::cell::Cell;
 use std::collections::HashMap;
 use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::rc::Rc;
 
-#[derive(Clone)]
+/// A list, shared between all of its copies until one of them is changed
+///
+/// Copying a list, by pushing it, naming it or with `kpy`, only copies a reference to it. Lists
+/// still behave like values: changing one through [`DerefMut`] first copies its elements if it
+/// is shared, so no other copy ever sees the change. Since a list can only be put in a container
+/// after it is made, and putting a shared container inside itself copies it first, reference
+/// cycles can't be made and nothing has to collect them.
+#[derive(Clone, Default)]
 pub struct List {
-    pub ls: Vec<Object>,
+    ls: Rc<Vec<Object>>,
+    /// [`List::size`], worked out the first time it is needed after a change
+    size: Cell<Option<usize>>,
+}
+
+impl List {
+    pub fn new(ls: Vec<Object>) -> Self {
+        Self {
+            ls: Rc::new(ls),
+            size: Cell::new(None),
+        }
+    }
+
+    /// Roughly how many bytes the elements take up, see [`Object::size`]
+    pub fn size(&self) -> usize {
+        match self.size.get() {
+            Some(size) => size,
+            None => {
+                let size = self.ls.iter().map(Object::size).sum();
+                self.size.set(Some(size));
+                size
+            }
+        }
+    }
+
+    /// The elements of the list, copying them only if it is shared
+    pub fn into_inner(self) -> Vec<Object> {
+        Rc::try_unwrap(self.ls).unwrap_or_else(|ls| (*ls).clone())
+    }
+
+    /// Whether `self` and `other` are copies of the same list
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.ls, &other.ls)
+    }
+}
+
+impl From<Vec<Object>> for List {
+    fn from(ls: Vec<Object>) -> Self {
+        Self::new(ls)
+    }
+}
+
+impl Deref for List {
+    type Target = Vec<Object>;
+    fn deref(&self) -> &Self::Target {
+        &self.ls
+    }
+}
+
+impl DerefMut for List {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.size.set(None);
+        Rc::make_mut(&mut self.ls)
+    }
 }
 
 impl fmt::Debug for List {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        Ok(write!(f, "{:?}", self.ls)?)
+        Ok(write!(f, "{:?}", *self.ls)?)
     }
 }
 
+/// A map, shared between all of its copies until one of them is changed
+///
+/// Copies work the same way as they do for [`List`].
 #[derive(Clone)]
 pub struct Map {
-    pub map: HashMap<String, Object>,
+    map: Rc<HashMap<String, Object>>,
+    /// [`Map::size`], worked out the first time it is needed after a change
+    size: Cell<Option<usize>>,
+}
+
+impl Default for Map {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Map {
     pub fn new() -> Self {
+        Self::from(HashMap::new())
+    }
+
+    /// Roughly how many bytes the keys and values take up, see [`Object::size`]
+    pub fn size(&self) -> usize {
+        match self.size.get() {
+            Some(size) => size,
+            None => {
+                let size = self.map.iter().map(|(k, v)| k.len() + v.size()).sum();
+                self.size.set(Some(size));
+                size
+            }
+        }
+    }
+
+    /// The entries of the map, copying them only if it is shared
+    pub fn into_inner(self) -> HashMap<String, Object> {
+        Rc::try_unwrap(self.map).unwrap_or_else(|map| (*map).clone())
+    }
+
+    /// Whether `self` and `other` are copies of the same map
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.map, &other.map)
+    }
+}
+
+impl From<HashMap<String, Object>> for Map {
+    fn from(map: HashMap<String, Object>) -> Self {
         Self {
-            map: HashMap::new(),
+            map: Rc::new(map),
+            size: Cell::new(None),
         }
     }
 }
 
+impl Deref for Map {
+    type Target = HashMap<String, Object>;
+    fn deref(&self) -> &Self::Target {
+        &self.map
+    }
+}
+
+impl DerefMut for Map {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.size.set(None);
+        Rc::make_mut(&mut self.map)
+    }
+}
+
 impl fmt::Debug for Map {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        Ok(write!(f, "{:?}", self.map)?)
+        Ok(write!(f, "{:?}", *self.map)?)
     }
 }
 
@@ -38,6 +157,7 @@ impl fmt::Debug for Map {
 pub enum Object {
     Sayı(f64),
     Yazı(String),
+    Baytlar(Vec<u8>),
     Bool(bool),
     İşlev(usize),
     Liste(List),
@@ -62,6 +182,7 @@ impl fmt::Debug for Object {
                 false => write!(f, "yanlış")?,
             },
             Self::Yazı(s) => write!(f, "{}", s)?,
+            Self::Baytlar(b) => write!(f, "{}", bytes_repr(b))?,
             Self::İşlev(loc) => write!(f, "<işlev: {:?}>", loc)?,
             Self::FfiFunction(_) => write!(f, "<ffi işlev>")?,
             Self::Liste(ls) => write!(f, "{:?}", ls)?,
@@ -75,50 +196,108 @@ impl fmt::Debug for Object {
 
 type ObjectResult = Result<Object, Error>;
 
+/// An error for the operator `op` not being supported between `a` and `b`
+fn unsupported(a: &Object, op: &str, b: &Object) -> Error {
+    match get_lang() {
+        SupportedLanguage::Turkish => Error::new(
+            "BeklenmedikTip",
+            &format!("`{:?}` `{}` `{:?}` işlemi desteklenmiyor", a, op, b),
+            vec![],
+            None,
+        ),
+        SupportedLanguage::English => Error::new(
+            "BeklenmedikTip",
+            &format!("`{:?}` `{}` `{:?}` operation is not supported", a, op, b),
+            vec![],
+            None,
+        ),
+    }
+}
+
+/// An error for the operator `op` not being supported on `a`
+fn unsupported_unary(op: &str, a: &Object) -> Error {
+    match get_lang() {
+        SupportedLanguage::Turkish => Error::new(
+            "BeklenmedikTip",
+            &format!("`{}` `{:?}` işlemi desteklenmiyor", op, a),
+            vec![],
+            None,
+        ),
+        SupportedLanguage::English => Error::new(
+            "BeklenmedikTip",
+            &format!("`{}` `{:?}` operation is not supported", op, a),
+            vec![],
+            None,
+        ),
+    }
+}
+
 impl Object {
+    /// Roughly how many bytes `self` takes up, along with everything in it
+    ///
+    /// Lists and maps shared between copies are counted once for every copy.
+    pub fn size(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + match self {
+                Self::Yazı(s) => s.len(),
+                Self::Baytlar(b) => b.len(),
+                Self::Liste(l) => l.size(),
+                Self::Harita(m) => m.size(),
+                _ => 0,
+            }
+    }
+
     // Karşılaştırma
     pub fn eşittir(&self, a: Self) -> ObjectResult {
         match self {
             Self::Sayı(f) => match a {
                 Self::Sayı(a) => Ok(Self::Bool(f == &a)),
                 Self::FfiObject(o) => o.equal(self.clone(), &vec![]),
+                b @ Self::Baytlar(_) => Err(unsupported(self, "=", &b)),
                 b => panic!("{:?} `=` {:?} operatörü desteklemiyor", f, b),
             },
             Self::Bool(b) => match a {
                 Self::Bool(a) => Ok(Self::Bool(b == &a)),
                 Self::FfiObject(o) => o.equal(self.clone(), &vec![]),
+                c @ Self::Baytlar(_) => Err(unsupported(self, "=", &c)),
                 c => panic!("{:?} `=` {:?} operatörü desteklemiyor", b, c),
             },
             Self::Yazı(s) => match a {
                 Self::Yazı(a) => Ok(Self::Bool(s == &a)),
                 Self::FfiObject(o) => o.equal(self.clone(), &vec![]),
+                c @ Self::Baytlar(_) => Err(unsupported(self, "=", &c)),
                 c => panic!("{:?} `=` {:?} operatörü desteklemiyor", s, c),
             },
+            Self::Baytlar(s) => match a {
+                Self::Baytlar(a) => Ok(Self::Bool(s == &a)),
+                Self::FfiObject(o) => o.equal(self.clone(), &vec![]),
+                c => Err(unsupported(self, "=", &c)),
+            },
             Self::İşlev(_) | Self::FfiFunction(_) => unreachable!(),
             Self::FfiObject(o) => o.equal(a, &vec![]),
             Self::Liste(l) => match a {
                 Self::Liste(m) => Ok(Self::Bool(
-                    l.ls.len() == m.ls.len()
-                        && l.ls.iter().enumerate().all(|(k, v)| {
-                            match v.eşittir(m.ls.get(k).unwrap().clone()) {
+                    l.len() == m.len()
+                        && l.iter().enumerate().all(|(k, v)| {
+                            match v.eşittir(m.get(k).unwrap().clone()) {
                                 Ok(Self::Bool(b)) => b,
                                 _ => unreachable!(),
                             }
                         }),
                 )),
                 Self::FfiObject(o) => o.equal(self.clone(), &vec![]),
+                c @ Self::Baytlar(_) => Err(unsupported(self, "=", &c)),
                 c => panic!("{:?} `=` {:?} operatörü desteklemiyor", l, c),
             },
             Self::Harita(m) => match a {
                 Self::Harita(n) => Ok(Self::Bool(
-                    m.map.len() == n.map.len()
-                        && m.map.keys().all(|k| {
-                            n.map.contains_key(k)
+                    m.len() == n.len()
+                        && m.keys().all(|k| {
+                            n.contains_key(k)
                                 && match m
-                                    .map
                                     .get(k)
                                     .unwrap()
-                                    .eşittir(n.map.get(k).unwrap().clone())
+                                    .eşittir(n.get(k).unwrap().clone())
                                 {
                                     Ok(Self::Bool(b)) => b,
                                     _ => panic!(),
@@ -126,6 +305,7 @@ impl Object {
                         }),
                 )),
                 Self::FfiObject(o) => o.equal(self.clone(), &vec![]),
+                c @ Self::Baytlar(_) => Err(unsupported(self, "=", &c)),
                 c => panic!("{:?} `=` {:?} operatörü desteklemiyor", m, c),
             },
             Self::Hiç => match a {
@@ -139,43 +319,51 @@ impl Object {
             Self::Sayı(f) => match a {
                 Self::Sayı(a) => Ok(Self::Bool(f != &a)),
                 Self::FfiObject(o) => o.not_equal(self.clone(), &vec![]),
+                b @ Self::Baytlar(_) => Err(unsupported(self, "!=", &b)),
                 b => panic!("{:?} `!=` {:?} operatörü desteklemiyor", f, b),
             },
             Self::Bool(b) => match a {
                 Self::Bool(a) => Ok(Self::Bool(b != &a)),
                 Self::FfiObject(o) => o.not_equal(self.clone(), &vec![]),
+                c @ Self::Baytlar(_) => Err(unsupported(self, "!=", &c)),
                 c => panic!("{:?} `!=` {:?} operatörü desteklemiyor", b, c),
             },
             Self::Yazı(s) => match a {
                 Self::Yazı(a) => Ok(Self::Bool(s != &a)),
                 Self::FfiObject(o) => o.not_equal(self.clone(), &vec![]),
+                c @ Self::Baytlar(_) => Err(unsupported(self, "!=", &c)),
                 c => panic!("{:?} `!=` {:?} operatörü desteklemiyor", s, c),
             },
+            Self::Baytlar(s) => match a {
+                Self::Baytlar(a) => Ok(Self::Bool(s != &a)),
+                Self::FfiObject(o) => o.not_equal(self.clone(), &vec![]),
+                c => Err(unsupported(self, "!=", &c)),
+            },
             Self::İşlev(_) | Self::FfiFunction(_) => unreachable!(),
             Self::FfiObject(o) => o.not_equal(a, &vec![]),
             Self::Liste(l) => match a {
                 Self::Liste(m) => Ok(Self::Bool(
-                    l.ls.len() != m.ls.len()
-                        || l.ls.iter().enumerate().any(|(k, v)| {
-                            match v.eşit_değildir(m.ls.get(k).unwrap().clone()) {
+                    l.len() != m.len()
+                        || l.iter().enumerate().any(|(k, v)| {
+                            match v.eşit_değildir(m.get(k).unwrap().clone()) {
                                 Ok(Self::Bool(b)) => b,
                                 _ => unreachable!(),
                             }
                         }),
                 )),
                 Self::FfiObject(o) => o.equal(self.clone(), &vec![]),
+                c @ Self::Baytlar(_) => Err(unsupported(self, "!=", &c)),
                 c => panic!("{:?} `!=` {:?} operatörü desteklemiyor", l, c),
             },
             Self::Harita(m) => match a {
                 Self::Harita(n) => Ok(Self::Bool(
-                    m.map.len() != n.map.len()
-                        || m.map.keys().any(|k| {
-                            !n.map.contains_key(k)
+                    m.len() != n.len()
+                        || m.keys().any(|k| {
+                            !n.contains_key(k)
                                 || match m
-                                    .map
                                     .get(k)
                                     .unwrap()
-                                    .eşit_değildir(n.map.get(k).unwrap().clone())
+                                    .eşit_değildir(n.get(k).unwrap().clone())
                                 {
                                     Ok(Self::Bool(b)) => b,
                                     _ => unreachable!(),
@@ -183,6 +371,7 @@ impl Object {
                         }),
                 )),
                 Self::FfiObject(o) => o.equal(self.clone(), &vec![]),
+                c @ Self::Baytlar(_) => Err(unsupported(self, "!=", &c)),
                 c => panic!("{:?} `!=` {:?} operatörü desteklemiyor", m, c),
             },
             Self::Hiç => match a {
@@ -196,9 +385,12 @@ impl Object {
             Self::Sayı(f) => match a {
                 Self::Sayı(a) => Ok(Self::Bool(f > &a)),
                 Self::FfiObject(o) => o.greater(self.clone(), &vec![]),
+                b @ Self::Baytlar(_) => Err(unsupported(self, ">", &b)),
                 b => panic!("{:?} `>` {:?} operatörü desteklemiyor", f, b),
             },
             Self::FfiObject(o) => o.greater(self.clone(), &vec![]),
+            Self::Baytlar(_) => Err(unsupported(self, ">", &a)),
+            b if matches!(a, Self::Baytlar(_)) => Err(unsupported(b, ">", &a)),
             b => panic!("{:?} `>` operatörünü desteklemiyor", b),
         }
     }
@@ -207,9 +399,12 @@ impl Object {
             Self::Sayı(f) => match a {
                 Self::Sayı(a) => Ok(Self::Bool(f >= &a)),
                 Self::FfiObject(o) => o.greater_eq(self.clone(), &vec![]),
+                b @ Self::Baytlar(_) => Err(unsupported(self, ">=", &b)),
                 b => panic!("{:?} `>=` {:?} operatörü desteklemiyor", f, b),
             },
             Self::FfiObject(o) => o.greater_eq(self.clone(), &vec![]),
+            Self::Baytlar(_) => Err(unsupported(self, ">=", &a)),
+            b if matches!(a, Self::Baytlar(_)) => Err(unsupported(b, ">=", &a)),
             b => panic!("{:?} `>=` operatörünü desteklemiyor", b),
         }
     }
@@ -218,9 +413,12 @@ impl Object {
             Self::Sayı(f) => match a {
                 Self::Sayı(a) => Ok(Self::Bool(f < &a)),
                 Self::FfiObject(o) => o.lesser(self.clone(), &vec![]),
+                b @ Self::Baytlar(_) => Err(unsupported(self, "<", &b)),
                 b => panic!("{:?} `<` {:?} operatörü desteklemiyor", f, b),
             },
             Self::FfiObject(o) => o.lesser(self.clone(), &vec![]),
+            Self::Baytlar(_) => Err(unsupported(self, "<", &a)),
+            b if matches!(a, Self::Baytlar(_)) => Err(unsupported(b, "<", &a)),
             b => panic!("{:?} `<` operatörünü desteklemiyor", b),
         }
     }
@@ -229,9 +427,12 @@ impl Object {
             Self::Sayı(f) => match a {
                 Self::Sayı(a) => Ok(Self::Bool(f <= &a)),
                 Self::FfiObject(o) => o.lesser_eq(self.clone(), &vec![]),
+                b @ Self::Baytlar(_) => Err(unsupported(self, "<=", &b)),
                 b => panic!("{:?} `<=` {:?} operatörü desteklemiyor", f, b),
             },
             Self::FfiObject(o) => o.lesser_eq(self.clone(), &vec![]),
+            Self::Baytlar(_) => Err(unsupported(self, "<=", &a)),
+            b if matches!(a, Self::Baytlar(_)) => Err(unsupported(b, "<=", &a)),
             b => panic!("{:?} `<=` operatörünü desteklemiyor", b),
         }
     }
@@ -239,6 +440,7 @@ impl Object {
         match self {
             Self::Bool(f) => Ok(Self::Bool(!f)),
             Self::FfiObject(o) => o.not(&vec![]),
+            Self::Baytlar(_) => Err(unsupported_unary("!", self)),
             b => panic!("{:?} `!` operatörünü desteklemiyor", b),
         }
     }
@@ -248,6 +450,7 @@ impl Object {
             Self::Sayı(f) => match a {
                 Self::Sayı(a) => Ok(Self::Sayı(f + a)),
                 Self::FfiObject(o) => o.lesser_eq(self.clone(), &vec![]),
+                a @ Self::Baytlar(_) => Err(unsupported(self, "+", &a)),
                 a => panic!("{:?} `+` {:?} desteklenmiyor", f, a),
             },
             Self::Yazı(s) => match a {
@@ -258,17 +461,29 @@ impl Object {
                     Ok(Self::Yazı(buf))
                 }
                 Self::FfiObject(o) => o.lesser_eq(self.clone(), &vec![]),
+                f @ Self::Baytlar(_) => Err(unsupported(self, "+", &f)),
                 f => panic!("{:?} `+` {:?} desteklenmiyor", s, f),
             },
+            Self::Baytlar(b) => match a {
+                Self::Baytlar(mut c) => {
+                    let mut b = b.clone();
+                    b.append(&mut c);
+                    Ok(Self::Baytlar(b))
+                }
+                Self::FfiObject(o) => o.add(self.clone(), &vec![]),
+                f => Err(unsupported(self, "+", &f)),
+            },
             Self::Liste(l) => match a {
                 Self::Liste(mut m) => {
                     let mut l = l.clone();
-                    l.ls.append(&mut m.ls);
+                    l.append(&mut m);
                     Ok(Self::Liste(l))
                 }
                 Self::FfiObject(o) => o.add(self.clone(), &vec![]),
+                f @ Self::Baytlar(_) => Err(unsupported(self, "+", &f)),
                 f => panic!("{:?} `+` {:?} desteklenmiyor", l, f),
             },
+            b if matches!(a, Self::Baytlar(_)) => Err(unsupported(b, "+", &a)),
             Self::Bool(b) => panic!("{:?} `+` operatörünü desteklemiyor", b),
             Self::FfiObject(o) => o.add(a, &vec![]),
             Self::Harita(m) => panic!("{:?} `+` operatörünü desteklemiyor", m),
@@ -281,9 +496,12 @@ impl Object {
             Self::Sayı(f) => match a {
                 Self::Sayı(a) => Ok(Self::Sayı(f - a)),
                 Self::FfiObject(o) => o.substract(self.clone(), &vec![]),
+                b @ Self::Baytlar(_) => Err(unsupported(self, "-", &b)),
                 b => panic!("{:?} `-` {:?} operatörü desteklemiyor", f, b),
             },
             Self::FfiObject(o) => o.substract(a, &vec![]),
+            Self::Baytlar(_) => Err(unsupported(self, "-", &a)),
+            b if matches!(a, Self::Baytlar(_)) => Err(unsupported(b, "-", &a)),
             b => panic!("{:?} `-` operatörünü desteklemiyor", b),
         }
     }
@@ -302,6 +520,7 @@ impl Object {
                         }
                         Ok(Self::Yazı(buf))
                     }
+                    a @ Self::Baytlar(_) => Err(unsupported(self, "*", &a)),
                     a => panic!("{:?} `*` {:?} desteklenmiyor", s, a),
                 }
             }
@@ -319,9 +538,12 @@ impl Object {
                         }
                         Ok(Self::Yazı(buf))
                     }
+                    b @ Self::Baytlar(_) => Err(unsupported(self, "*", &b)),
                     b => panic!("{:?} `*` {:?} operatörü desteklemiyor", f, b),
                 }
             }
+            Self::Baytlar(_) => Err(unsupported(self, "*", &a)),
+            b if matches!(a, Self::Baytlar(_)) => Err(unsupported(b, "*", &a)),
             b => panic!("{:?} `*` operatörünü desteklemiyor", b),
         }
     }
@@ -329,8 +551,11 @@ impl Object {
         match self {
             Self::Sayı(f) => match a {
                 Self::Sayı(a) => Ok(Self::Sayı(f / a)),
+                b @ Self::Baytlar(_) => Err(unsupported(self, "/", &b)),
                 b => panic!("{:?} `/` {:?} operatörü desteklemiyor", f, b),
             },
+            Self::Baytlar(_) => Err(unsupported(self, "/", &a)),
+            b if matches!(a, Self::Baytlar(_)) => Err(unsupported(b, "/", &a)),
             b => panic!("{:?} `/` operatörünü desteklemiyor", b),
         }
     }
@@ -338,8 +563,11 @@ impl Object {
         match self {
             Self::Sayı(f) => match a {
                 Self::Sayı(a) => Ok(Self::Sayı(f % a)),
+                b @ Self::Baytlar(_) => Err(unsupported(self, "%", &b)),
                 b => panic!("{:?} `/` {:?} desteklenmiyor", f, b),
             },
+            Self::Baytlar(_) => Err(unsupported(self, "%", &a)),
+            b if matches!(a, Self::Baytlar(_)) => Err(unsupported(b, "%", &a)),
             b => panic!("{:?} `/` operatörünü desteklemiyor", b),
         }
     }
@@ -431,6 +659,23 @@ impl Object {
         }
     }
     // Dönüşüm
+    /// An error for `self` not being convertible to the type `to`
+    fn unconvertible(&self, to: &str, line: usize, col: usize, file: String) -> Error {
+        match get_lang() {
+            SupportedLanguage::Turkish => Error::new(
+                "DeğerHatası",
+                &format!("`{:?}` {} tipine dönüştürülemez", self, to),
+                vec![(line, col, file, None)],
+                None,
+            ),
+            SupportedLanguage::English => Error::new(
+                "ValueError",
+                &format!("`{:?}` can't be converted to {}", self, to),
+                vec![(line, col, file, None)],
+                None,
+            ),
+        }
+    }
     pub fn dönüştür(&self, a: String, line: usize, col: usize, file: String) -> ObjectResult {
         match a.to_lowercase().as_str() {
             "yazı" => match self {
@@ -440,11 +685,32 @@ impl Object {
                 },
                 Self::Sayı(n) => Ok(Self::Yazı(if n.fract() == 0. { format!("{:.0?}", n) } else { format!("{:?}", n) })),
                 Self::Yazı(_) => Ok(self.clone()),
+                Self::Baytlar(b) => match String::from_utf8(b.clone()) {
+                    Ok(s) => Ok(Self::Yazı(s)),
+                    Err(_) => Err(match get_lang() {
+                        SupportedLanguage::Turkish => Error::new(
+                            "DeğerHatası",
+                            &format!("`{:?}` geçerli bir UTF-8 yazısı değil", self),
+                            vec![(line,
+                            col,
+                            file, None)],
+                            None,
+                        ),
+                        SupportedLanguage::English => Error::new(
+                            "ValueError",
+                            &format!("`{:?}` is not valid UTF-8", self),
+                            vec![(line,
+                            col,
+                            file, None)],
+                            None,
+                        ),
+                    }),
+                },
                 Self::İşlev(_) | Self::FfiFunction(_) => unreachable!(),
                 Self::Liste(l) => Ok(Self::Yazı(format!("{:?}", l))),
                 Self::Harita(m) => Ok(Self::Yazı(format!("{:?}", m))),
                 Self::Hiç => Ok(Self::Yazı("hiç".to_string())),
-                Self::FfiObject(_) => todo!(),
+                Self::FfiObject(_) => Err(self.unconvertible(&a, line, col, file)),
             },
             "bool" | "boolean" => match self {
                 Self::Bool(_) => Ok(self.clone()),
@@ -477,8 +743,8 @@ impl Object {
                     }),
                 },
                 Self::İşlev(_) | Self::FfiFunction(_) => unreachable!(),
-                Self::FfiObject(_) => todo!(),
-                Self::Liste(_) | Self::Harita(_) => panic!("unsupported conversion"),
+                Self::FfiObject(_) => Err(self.unconvertible(&a, line, col, file)),
+                Self::Liste(_) | Self::Harita(_) | Self::Baytlar(_) => Err(self.unconvertible(&a, line, col, file)),
             },
             "sayı" => match self {
                 Self::Bool(b) => Ok(match b {
@@ -509,8 +775,42 @@ impl Object {
                     }),
                 },
                 Self::İşlev(_) | Self::FfiFunction(_) => unreachable!(),
-                Self::FfiObject(_) => todo!(),
-                Self::Liste(_) | Self::Harita(_) => panic!("unsupported conversion"),
+                Self::FfiObject(_) => Err(self.unconvertible(&a, line, col, file)),
+                Self::Liste(_) | Self::Harita(_) | Self::Baytlar(_) => Err(self.unconvertible(&a, line, col, file)),
+            },
+            "baytlar" => match self {
+                Self::Yazı(s) => Ok(Self::Baytlar(s.clone().into_bytes())),
+                Self::Baytlar(_) => Ok(self.clone()),
+                Self::Liste(l) => {
+                    let mut buf = vec![];
+                    for o in l.iter() {
+                        match o {
+                            Self::Sayı(n) if n.fract() == 0. && (0. ..256.).contains(n) => buf.push(*n as u8),
+                            b => return Err(match get_lang() {
+                                SupportedLanguage::Turkish => Error::new(
+                                    "DeğerHatası",
+                                    &format!("`{:?}` bir bayt değil", b),
+                                    vec![(line,
+                                    col,
+                                    file, None)],
+                                    None,
+                                ),
+                                SupportedLanguage::English => Error::new(
+                                    "ValueError",
+                                    &format!("`{:?}` is not a byte", b),
+                                    vec![(line,
+                                    col,
+                                    file, None)],
+                                    None,
+                                ),
+                            }),
+                        }
+                    }
+                    Ok(Self::Baytlar(buf))
+                }
+                Self::İşlev(_) | Self::FfiFunction(_) => unreachable!(),
+                Self::FfiObject(_) => Err(self.unconvertible(&a, line, col, file)),
+                Self::Bool(_) | Self::Sayı(_) | Self::Harita(_) | Self::Hiç => Err(self.unconvertible(&a, line, col, file)),
             },
             a => Err(match get_lang() {
                 SupportedLanguage::Turkish => Error::new(
@@ -534,3 +834,399 @@ impl Object {
     }
 }
 
+
+impl Object {
+    /// The name of the type of `self` as it is written in tr-lang
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::Sayı(_) => "sayı",
+            Self::Yazı(_) => "yazı",
+            Self::Baytlar(_) => "baytlar",
+            Self::Bool(_) => "bool",
+            Self::İşlev(_) | Self::FfiFunction(_) => "işlev",
+            Self::Liste(_) => "liste",
+            Self::Harita(_) => "harita",
+            Self::Hiç => "hiç",
+            Self::FfiObject(_) => "nesne",
+        }
+    }
+}
+
+/// The English name of a type named by [`Object::type_name`] or expected by [`FromObject`]
+fn type_name_en(name: &str) -> &str {
+    match name {
+        "sayı" => "number",
+        "tam sayı" => "integer",
+        "yazı" => "string",
+        "baytlar" => "bytes",
+        "işlev" => "function",
+        "liste" => "list",
+        "harita" => "map",
+        "hiç" => "nothing",
+        "nesne" => "object",
+        name => name,
+    }
+}
+
+/// An [`Object`] of the wrong type for the Rust value [`FromObject`] was turning it into
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConversionError {
+    /// The type that was expected, as it is written in tr-lang
+    pub expected: &'static str,
+    /// The type that was found, see [`Object::type_name`]
+    pub found: &'static str,
+    /// The keys of the maps and the indices of the lists the object was in, outermost first
+    pub at: Vec<String>,
+}
+
+impl ConversionError {
+    pub fn new(expected: &'static str, found: &Object) -> Self {
+        Self {
+            expected,
+            found: found.type_name(),
+            at: vec![],
+        }
+    }
+
+    /// `self` for an object that was at `key` of a map or list
+    pub fn at(mut self, key: impl ToString) -> Self {
+        self.at.insert(0, key.to_string());
+        self
+    }
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.at.is_empty() {
+            write!(f, "`{}`: ", self.at.join("."))?;
+        }
+        match get_lang() {
+            SupportedLanguage::Turkish => write!(f, "{} beklenmişti ancak {} bulundu", self.expected, self.found),
+            SupportedLanguage::English => write!(
+                f,
+                "expected {} but found {}",
+                type_name_en(self.expected),
+                type_name_en(self.found),
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
+impl From<ConversionError> for Error {
+    fn from(e: ConversionError) -> Self {
+        Error::new("BeklenmedikTip", &e.to_string(), vec![], None)
+    }
+}
+
+/// A Rust value that can be turned into an [`Object`]
+///
+/// Numbers become `Sayı`, `Vec`s become `Liste`, `HashMap`s become `Harita` and `None` becomes
+/// `Hiç`. Structs with named fields can derive it to become a `Harita` of their fields.
+pub trait IntoObject {
+    fn into_object(self) -> Object;
+}
+
+/// A Rust value that can be made from an [`Object`], the opposite of [`IntoObject`]
+///
+/// Structs with named fields can derive it to be made from a `Harita`, missing fields are taken
+/// to be `Hiç` so that `Option` fields can be left out.
+pub trait FromObject: Sized {
+    fn from_object(o: Object) -> Result<Self, ConversionError>;
+}
+
+impl IntoObject for Object {
+    fn into_object(self) -> Object {
+        self
+    }
+}
+
+impl FromObject for Object {
+    fn from_object(o: Object) -> Result<Self, ConversionError> {
+        Ok(o)
+    }
+}
+
+impl IntoObject for bool {
+    fn into_object(self) -> Object {
+        Object::Bool(self)
+    }
+}
+
+impl FromObject for bool {
+    fn from_object(o: Object) -> Result<Self, ConversionError> {
+        match o {
+            Object::Bool(b) => Ok(b),
+            o => Err(ConversionError::new("bool", &o)),
+        }
+    }
+}
+
+macro_rules! float_conversions {
+    ($($t:ty),*) => {$(
+        impl IntoObject for $t {
+            fn into_object(self) -> Object {
+                Object::Sayı(self as f64)
+            }
+        }
+
+        impl FromObject for $t {
+            fn from_object(o: Object) -> Result<Self, ConversionError> {
+                match o {
+                    Object::Sayı(n) => Ok(n as $t),
+                    o => Err(ConversionError::new("sayı", &o)),
+                }
+            }
+        }
+    )*};
+}
+
+float_conversions!(f32, f64);
+
+macro_rules! int_conversions {
+    ($($t:ty),*) => {$(
+        impl IntoObject for $t {
+            fn into_object(self) -> Object {
+                Object::Sayı(self as f64)
+            }
+        }
+
+        /// Only whole numbers that fit are converted
+        impl FromObject for $t {
+            fn from_object(o: Object) -> Result<Self, ConversionError> {
+                match o {
+                    Object::Sayı(n) if n.fract() == 0. && n >= <$t>::MIN as f64 && n < <$t>::MAX as f64 + 1. => {
+                        Ok(n as $t)
+                    }
+                    o => Err(ConversionError::new("tam sayı", &o)),
+                }
+            }
+        }
+    )*};
+}
+
+int_conversions!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl IntoObject for String {
+    fn into_object(self) -> Object {
+        Object::Yazı(self)
+    }
+}
+
+impl IntoObject for &str {
+    fn into_object(self) -> Object {
+        Object::Yazı(self.to_string())
+    }
+}
+
+impl FromObject for String {
+    fn from_object(o: Object) -> Result<Self, ConversionError> {
+        match o {
+            Object::Yazı(s) => Ok(s),
+            o => Err(ConversionError::new("yazı", &o)),
+        }
+    }
+}
+
+impl<T: IntoObject> IntoObject for Vec<T> {
+    fn into_object(self) -> Object {
+        Object::Liste(List::new(self.into_iter().map(IntoObject::into_object).collect()))
+    }
+}
+
+impl<T: FromObject> FromObject for Vec<T> {
+    fn from_object(o: Object) -> Result<Self, ConversionError> {
+        match o {
+            Object::Liste(l) => l
+                .into_inner()
+                .into_iter()
+                .enumerate()
+                .map(|(i, o)| T::from_object(o).map_err(|e| e.at(i)))
+                .collect(),
+            o => Err(ConversionError::new("liste", &o)),
+        }
+    }
+}
+
+impl<T: IntoObject> IntoObject for HashMap<String, T> {
+    fn into_object(self) -> Object {
+        Object::Harita(Map::from(
+            self.into_iter().map(|(k, v)| (k, v.into_object())).collect::<HashMap<_, _>>(),
+        ))
+    }
+}
+
+impl<T: FromObject> FromObject for HashMap<String, T> {
+    fn from_object(o: Object) -> Result<Self, ConversionError> {
+        match o {
+            Object::Harita(m) => m
+                .into_inner()
+                .into_iter()
+                .map(|(k, v)| match T::from_object(v) {
+                    Ok(v) => Ok((k, v)),
+                    Err(e) => Err(e.at(k)),
+                })
+                .collect(),
+            o => Err(ConversionError::new("harita", &o)),
+        }
+    }
+}
+
+impl<T: IntoObject> IntoObject for Option<T> {
+    fn into_object(self) -> Object {
+        match self {
+            Some(t) => t.into_object(),
+            None => Object::Hiç,
+        }
+    }
+}
+
+impl<T: FromObject> FromObject for Option<T> {
+    fn from_object(o: Object) -> Result<Self, ConversionError> {
+        match o {
+            Object::Hiç => Ok(None),
+            o => T::from_object(o).map(Some),
+        }
+    }
+}
+
+impl IntoObject for List {
+    fn into_object(self) -> Object {
+        Object::Liste(self)
+    }
+}
+
+impl FromObject for List {
+    fn from_object(o: Object) -> Result<Self, ConversionError> {
+        match o {
+            Object::Liste(l) => Ok(l),
+            o => Err(ConversionError::new("liste", &o)),
+        }
+    }
+}
+
+impl IntoObject for Map {
+    fn into_object(self) -> Object {
+        Object::Harita(self)
+    }
+}
+
+impl FromObject for Map {
+    fn from_object(o: Object) -> Result<Self, ConversionError> {
+        match o {
+            Object::Harita(m) => Ok(m),
+            o => Err(ConversionError::new("harita", &o)),
+        }
+    }
+}
+
+/// Objects are written as the closest thing to them in the serde data model: numbers as `f64`,
+/// `Hiç` as unit, lists as sequences and maps as maps. Functions and FFI objects can't be written.
+impl Serialize for Object {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Sayı(n) => serializer.serialize_f64(*n),
+            Self::Yazı(s) => serializer.serialize_str(s),
+            Self::Baytlar(b) => serializer.serialize_bytes(b),
+            Self::Bool(b) => serializer.serialize_bool(*b),
+            Self::Liste(l) => l.serialize(serializer),
+            Self::Harita(m) => m.serialize(serializer),
+            Self::Hiç => serializer.serialize_unit(),
+            Self::İşlev(_) | Self::FfiFunction(_) | Self::FfiObject(_) => Err(ser::Error::custom(match get_lang() {
+                SupportedLanguage::Turkish => format!("{} serileştirilemez", self.type_name()),
+                SupportedLanguage::English => format!("{}s can't be serialized", type_name_en(self.type_name())),
+            })),
+        }
+    }
+}
+
+impl Serialize for List {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.ls.serialize(serializer)
+    }
+}
+
+impl Serialize for Map {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.map.serialize(serializer)
+    }
+}
+
+/// Objects are read from whatever the format has, so only self-describing formats can be read
+/// from. Every number becomes a `Sayı` and both unit and `None` become `Hiç`.
+impl<'de> Deserialize<'de> for Object {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ObjectVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for List {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(List::new)
+    }
+}
+
+impl<'de> Deserialize<'de> for Map {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        HashMap::deserialize(deserializer).map(Map::from)
+    }
+}
+
+struct ObjectVisitor;
+
+impl<'de> Visitor<'de> for ObjectVisitor {
+    type Value = Object;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a tr-lang object")
+    }
+
+    fn visit_bool<E: de::Error>(self, b: bool) -> Result<Object, E> {
+        Ok(Object::Bool(b))
+    }
+    fn visit_i64<E: de::Error>(self, n: i64) -> Result<Object, E> {
+        Ok(Object::Sayı(n as f64))
+    }
+    fn visit_u64<E: de::Error>(self, n: u64) -> Result<Object, E> {
+        Ok(Object::Sayı(n as f64))
+    }
+    fn visit_f64<E: de::Error>(self, n: f64) -> Result<Object, E> {
+        Ok(Object::Sayı(n))
+    }
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<Object, E> {
+        Ok(Object::Yazı(s.to_string()))
+    }
+    fn visit_string<E: de::Error>(self, s: String) -> Result<Object, E> {
+        Ok(Object::Yazı(s))
+    }
+    fn visit_bytes<E: de::Error>(self, b: &[u8]) -> Result<Object, E> {
+        Ok(Object::Baytlar(b.to_vec()))
+    }
+    fn visit_byte_buf<E: de::Error>(self, b: Vec<u8>) -> Result<Object, E> {
+        Ok(Object::Baytlar(b))
+    }
+    fn visit_unit<E: de::Error>(self) -> Result<Object, E> {
+        Ok(Object::Hiç)
+    }
+    fn visit_none<E: de::Error>(self) -> Result<Object, E> {
+        Ok(Object::Hiç)
+    }
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Object, D::Error> {
+        Object::deserialize(deserializer)
+    }
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Object, A::Error> {
+        let mut ls = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(o) = seq.next_element()? {
+            ls.push(o);
+        }
+        Ok(Object::Liste(List::new(ls)))
+    }
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Object, A::Error> {
+        let mut m = HashMap::with_capacity(map.size_hint().unwrap_or(0));
+        while let Some((k, v)) = map.next_entry()? {
+            m.insert(k, v);
+        }
+        Ok(Object::Harita(Map::from(m)))
+    }
+}
@@ -72,8 +72,8 @@ impl Parser {
                     current += 1;
                 }
                 Precedence::Reserved => {
-                    while !stack.is_empty() {
-                        tokens.push(stack.pop().unwrap());
+                    while let Some(t) = stack.pop() {
+                        tokens.push(t);
                     }
                     tokens.push(i.clone());
                     current += 1;
@@ -97,10 +97,7 @@ impl Parser {
                 }
                 Precedence::ParenR => {
                     while !stack.is_empty()
-                        && match stack.last().unwrap().precedence {
-                            Precedence::ParenL => false,
-                            _ => true,
-                        }
+                        && !matches!(stack.last().unwrap().precedence, Precedence::ParenL)
                     {
                         tokens.push(stack.pop().unwrap());
                     }
@@ -113,10 +110,7 @@ impl Parser {
                 }
                 Precedence::Comma => {
                     while !stack.is_empty()
-                        && match stack.last().unwrap().precedence {
-                            Precedence::ParenL => false,
-                            _ => true,
-                        }
+                        && !matches!(stack.last().unwrap().precedence, Precedence::ParenL)
                     {
                         tokens.push(stack.pop().unwrap());
                     }
@@ -124,8 +118,8 @@ impl Parser {
                 }
             }
         }
-        while !stack.is_empty() {
-            tokens.push(stack.pop().unwrap());
+        while let Some(t) = stack.pop() {
+            tokens.push(t);
         }
         tokens
     }
@@ -237,6 +231,12 @@ impl Parser {
                     ptoken.col,
                     ptoken.file.clone(),
                 )),
+                LexTokenType::Baytlar(ref val) => parsed.push(Token::new(
+                    TokenType::Baytlar { val: val.clone() },
+                    ptoken.line,
+                    ptoken.col,
+                    ptoken.file.clone(),
+                )),
                 LexTokenType::Identifier => parsed.push(Token::new(
                     TokenType::Identifier {
                         id: ptoken.lexeme.clone(),
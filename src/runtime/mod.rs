@@ -1,8 +1,17 @@
+#[allow(clippy::module_inception)]
 mod runtime;
+mod snapshot;
 
 pub use runtime::{
+    call,
+    CancelHandle,
+    Permission,
+    Permissions,
     Run,
     RunConfig,
+    Status,
     Trace,
 };
+pub(crate) use runtime::{limits_reached, report};
+pub use snapshot::{Snapshot, SNAPSHOT_MAGIC, SNAPSHOT_VERSION};
 
@@ -1,22 +1,273 @@
 use dlopen::symbor::Library;
 
+use crate::bytecode::{Instruction, Program, Span};
 use crate::error::Error;
-use crate::ffi::{load_library, terminate_library};
+use crate::ffi::{load_library, terminate_library, FfiFunction};
 use crate::mem::{HashMemory, Map, Object, StackMemory};
-use crate::token::{tokentypes::ParserTokenType as TokenType, ParserToken as Token};
+use crate::stdlib;
 use crate::util::{get_lang, SupportedLanguage};
-use std::io::{self, prelude::*};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::io::{self, prelude::*, LineWriter};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+/// Runs a [`Program`]
+///
+/// The program is never changed while it is run, everything about the run is kept in the `Run`
+/// and the memories it is given. A program can be shared between any number of `Run`s through an
+/// `Arc`, on any thread, and a `Run` can run its program as many times as needed.
 pub struct Run {
-    program: Vec<Token>,
+    pub(super) program: Arc<Program>,
+    /// The constant pool of `program` as objects
+    consts: Vec<Object>,
     pub(crate) current: usize,
+    /// The functions being run, innermost last
+    pub(super) frames: Vec<Frame>,
+    /// The slot of every name of `program` in the `HashMemory` being run with
+    slots: Vec<usize>,
+    /// The most functions that can be run at once, from [`RunConfig::max_depth`]
+    max_depth: usize,
+    limits: Limits,
+    /// What the program is allowed to do, from [`RunConfig::permissions`]
+    permissions: Permissions,
+    io: Io,
+    /// The slots of the `blok`s being run, innermost last
+    pub(super) namespaces: Vec<usize>,
+    /// The shared libraries the program loaded, along with their names
+    libraries: Vec<(Library, String)>,
+    /// The instructions [`Run::run_until`] stops at before running them
+    breakpoints: BTreeSet<usize>,
+    /// The breakpoint [`Run::run_until`] last stopped at, which the next call runs past
+    paused_at: Option<usize>,
 }
 
+/// Where [`Run::run_until`] stopped
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// It ran as many instructions as it was allowed to, there are more to run
+    Running,
+    /// It is about to run the instruction at this breakpoint
+    Breakpoint(usize),
+    /// The program has ended
+    Finished,
+}
+
+/// Where a [`Run`] reads input from and writes output to, from [`RunConfig`]
+struct Io {
+    stdin: Box<dyn Read>,
+    /// Flushed after every line like the standard output, before reading input, before calling
+    /// `FfiFunction`s and when the run ends
+    stdout: LineWriter<Box<dyn Write>>,
+    stderr: Box<dyn Write>,
+}
+
+impl Io {
+    fn new(stdin: Option<Box<dyn Read>>, stdout: Option<Box<dyn Write>>, stderr: Option<Box<dyn Write>>) -> Self {
+        Self {
+            stdin: stdin.unwrap_or_else(|| Box::new(io::stdin())),
+            stdout: LineWriter::new(stdout.unwrap_or_else(|| Box::new(io::stdout()))),
+            stderr: stderr.unwrap_or_else(|| Box::new(io::stderr())),
+        }
+    }
+
+    /// Reads a line without its line ending, one byte at a time so that nothing after it is read
+    /// and lost when the run ends
+    fn read_line(&mut self) -> io::Result<String> {
+        self.stdout.flush()?;
+        let mut buf = vec![];
+        let mut byte = [0];
+        loop {
+            match self.stdin.read(&mut byte) {
+                Ok(0) => break,
+                Ok(_) if byte[0] == b'\n' => break,
+                Ok(_) => buf.push(byte[0]),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => (),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(String::from_utf8_lossy(&buf).trim_end().to_string())
+    }
+}
+
+impl Default for Io {
+    fn default() -> Self {
+        Self::new(None, None, None)
+    }
+}
+
+/// Stops a [`Run`] from another thread, cloning it gives another handle to the same run
+#[derive(Clone, Debug, Default)]
+pub struct CancelHandle(Arc<AtomicBool>);
+
+impl CancelHandle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stops the runs given this handle with an `İptalEdildi` error, they check it every few
+    /// hundred instructions
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Something a program can be kept from doing, see [`Permissions`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Permission {
+    /// Reading and writing files, through the `dosya` module
+    File,
+    /// Running external programs and looking at the environment, through the `süreç` module
+    Process,
+    /// Connecting over the network, through the `ağ` and `http` modules
+    Network,
+    /// Loading shared libraries
+    Ffi,
+    /// Reading the standard input with `girdi`
+    Input,
+}
+
+/// What a [`Run`] is allowed to do, doing anything else is an `İzinHatası` error
+///
+/// Everything is allowed by default, [`Permissions::none`] is the place to start when running
+/// code that can't be trusted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Permissions {
+    pub file: bool,
+    pub process: bool,
+    pub network: bool,
+    pub ffi: bool,
+    pub input: bool,
+}
+
+impl Permissions {
+    pub fn all() -> Self {
+        Self {
+            file: true,
+            process: true,
+            network: true,
+            ffi: true,
+            input: true,
+        }
+    }
+
+    pub fn none() -> Self {
+        Self {
+            file: false,
+            process: false,
+            network: false,
+            ffi: false,
+            input: false,
+        }
+    }
+
+    /// `self` with `permission` allowed
+    pub fn allow(mut self, permission: Permission) -> Self {
+        *self.get_mut(permission) = true;
+        self
+    }
+
+    /// `self` with `permission` denied
+    pub fn deny(mut self, permission: Permission) -> Self {
+        *self.get_mut(permission) = false;
+        self
+    }
+
+    pub fn allows(&self, permission: Permission) -> bool {
+        match permission {
+            Permission::File => self.file,
+            Permission::Process => self.process,
+            Permission::Network => self.network,
+            Permission::Ffi => self.ffi,
+            Permission::Input => self.input,
+        }
+    }
+
+    fn get_mut(&mut self, permission: Permission) -> &mut bool {
+        match permission {
+            Permission::File => &mut self.file,
+            Permission::Process => &mut self.process,
+            Permission::Network => &mut self.network,
+            Permission::Ffi => &mut self.ffi,
+            Permission::Input => &mut self.input,
+        }
+    }
+}
+
+impl Default for Permissions {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+/// What is left of the limits in [`RunConfig`]
+#[derive(Clone, Default)]
+struct Limits {
+    instructions: Option<u64>,
+    deadline: Option<Instant>,
+    cancel: Option<CancelHandle>,
+    /// Instructions run since the clock and the cancel handle were last checked
+    unchecked: u64,
+    max_stack: Option<usize>,
+    max_values: Option<usize>,
+    max_memory: Option<usize>,
+}
+
+impl Limits {
+    fn new(config: &RunConfig) -> Self {
+        Self {
+            instructions: config.max_instructions,
+            deadline: config.timeout.map(|t| Instant::now() + t),
+            cancel: config.cancel.clone(),
+            unchecked: 0,
+            max_stack: config.max_stack,
+            max_values: config.max_values,
+            max_memory: config.max_memory,
+        }
+    }
+}
+
+/// How many instructions are run between checks of the clock and the cancel handle
+const CHECK_EVERY: u64 = 256;
+
 pub struct RunConfig {
     pub mem: (StackMemory, HashMemory),
     pub repl: bool,
     pub file: String,
     pub supress_warnings: bool,
+    /// What the program is allowed to do
+    pub permissions: Permissions,
+    /// The most functions that can be run at once, calling one more is an `ÖzyinelemeSınırı`
+    /// error. Tail calls don't count since they take over the frame of their caller.
+    pub max_depth: usize,
+    /// The most instructions that can be run, running one more is a `KomutSınırı` error
+    pub max_instructions: Option<u64>,
+    /// How long the program can run for, running longer is a `ZamanAşımı` error
+    pub timeout: Option<Duration>,
+    /// Stops the run with an `İptalEdildi` error when cancelled
+    pub cancel: Option<CancelHandle>,
+    /// The most values that can be on the stack, pushing more is a `BellekSınırı` error
+    pub max_stack: Option<usize>,
+    /// The most values that can be kept on the stack and in variables together, keeping more is
+    /// a `BellekSınırı` error
+    pub max_values: Option<usize>,
+    /// Roughly how many bytes the values on the stack and in variables can take up together,
+    /// taking up more is a `BellekSınırı` error. Strings, bytes and lists are also checked before
+    /// they are made by `+` and `*`, so that a single huge one can't run out of memory.
+    pub max_memory: Option<usize>,
+    /// Where `girdi` reads from, `None` for the standard input
+    pub stdin: Option<Box<dyn Read>>,
+    /// Where `de` writes to, `None` for the standard output. What is written is flushed after
+    /// every line, before `girdi` reads and when the run ends.
+    pub stdout: Option<Box<dyn Write>>,
+    /// Where warnings are written to, `None` for the standard error
+    pub stderr: Option<Box<dyn Write>>,
 }
 impl Default for RunConfig {
     fn default() -> Self {
@@ -25,1981 +276,922 @@ impl Default for RunConfig {
             repl: false,
             file: ".".to_string(),
             supress_warnings: true,
+            permissions: Permissions::all(),
+            max_depth: 10_000,
+            max_instructions: None,
+            timeout: None,
+            cancel: None,
+            max_stack: None,
+            max_values: None,
+            max_memory: None,
+            stdin: None,
+            stdout: None,
+            stderr: None,
         }
     }
 }
 
 pub type Trace = (usize, usize, String, Option<String>);
 
-impl Run {
-    pub fn new(program: Vec<Token>) -> Self {
+/// A function being run
+#[derive(Clone, Serialize, Deserialize)]
+pub(super) struct Frame {
+    /// Where the function returns to
+    pub(super) ret: usize,
+    /// Where the function was called from along with its name, `None` if it was called from Rust
+    pub(super) trace: Option<Trace>,
+    /// How many scopes the memories had when the function was called, they are dropped back to
+    /// this many when it returns
+    pub(super) scope: usize,
+    /// Where the last tail call made in the frame was made from along with its name
+    tail: Option<Trace>,
+    /// How many tail calls were made in the frame
+    tail_calls: usize,
+    /// What the frame gives back once the function being run in it returns
+    returns: Returns,
+}
+
+/// What a frame gives back once the function being run in it returns, which changes with the tail
+/// calls made in it as the callers they replaced would have changed it
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+enum Returns {
+    /// What the function returns, if it returns anything
+    Value,
+    /// Nothing, a tail call was made before a `son` which drops what it returns
+    Nothing,
+    /// What the function returns, or if it returns nothing what is on top of the stack, a tail
+    /// call was made before a `ver`
+    ValueOrTop,
+    /// What is on top of the stack, a tail call was made before a `son` and then one before a
+    /// `ver` which returns what is on top after the first one returns nothing
+    Top,
+}
+
+impl Returns {
+    /// What the frame gives back after another tail call is made in it, which returns `then`
+    fn then(self, then: Returns) -> Returns {
+        match (self, then) {
+            (Returns::Value, then) => then,
+            (Returns::ValueOrTop, Returns::Nothing) => Returns::Top,
+            (this, _) => this,
+        }
+    }
+}
+
+impl Frame {
+    fn new(ret: usize, trace: Option<Trace>, scope: usize) -> Self {
         Self {
-            program,
-            current: 0,
+            ret,
+            trace,
+            scope,
+            tail: None,
+            tail_calls: 0,
+            returns: Returns::Value,
         }
     }
+}
 
-    pub fn run(
-        &mut self,
-        config: RunConfig,
-    ) -> Result<(StackMemory, HashMemory), (StackMemory, HashMemory, Error)> {
-        let (mut stack, mut hashs) = config.mem;
-        let file = config.file;
-        // let mut warnings: Vec<Box<dyn FnOnce()>> = vec![]; // for later use
-        let mut current_namespace: Vec<String> = vec![];
-        let mut traceback: Vec<Trace> = vec![];
-        let mut loaded_libraries: Vec<(Library, String)> = vec![];
-
-        while self.program.len() > self.current {
-            let tokenc = self.program.get(self.current).unwrap().clone();
-            let token = self.program.get_mut(self.current).unwrap();
-
-            match token.typ.clone() {
-                TokenType::LibSymbol(s) => {
-                    let lib = load_library(&s, &mut stack, &mut hashs);
-                    match lib {
-                        Ok(lib) => loaded_libraries.push((lib, s)),
-                        Err(e) => return Err((stack, hashs, Error::new(
-                            "DinamikYüklemeHatası",
-                            &format!("{}", e),
-                            traceback,
-                            None,
-                        ))),
-                    }
-                    self.current += 1;
-                },
-                TokenType::InScopeParentL => {
-                    stack.new_stack();
+/// The program of the `Run` that is calling an `FfiFunction`, lent to the function for the
+/// duration of the call so that it can call back into tr-lang with [`call`]
+struct Caller {
+    program: Arc<Program>,
+    consts: Vec<Object>,
+    permissions: Permissions,
+    /// How many more functions can be run at once
+    max_depth: usize,
+    limits: Limits,
+    io: Io,
+}
 
-                    let tok = self.program.get(self.current + 1).unwrap();
-                    match tok.typ {
-                        TokenType::Identifier { ref id } => {
-                            match hashs.get(id) {
-                                Some(o) => stack.push(o.clone()),
-                                None => return Err((stack, hashs.clone(), match get_lang() {
-                                    SupportedLanguage::Turkish => Error::new(
-                                        "BilinmeyenTanımlayıcı",
-                                        &format!(
-                                            "bilinmeyen değişken: `{}`, bu değişken bulunamamıştır",
-                                            tok.repr()
-                                        ),
-                                        { traceback.push((tok.line, tok.col, tok.file.clone(), None)); traceback },
-                                        {
-                                            let mut hashk = hashs.clone().into_keys();
-                                            hashk.sort();
-                                            let n = hashk.binary_search(id).unwrap_err();
-                                            if hashk.is_empty() {
-                                                None
-                                            } else {
-                                                Some(format!("`{}` demek mi istediniz?", hashk[n]))
-                                            }
-                                        }
-                                    ),
-                                    SupportedLanguage::English => Error::new(
-                                        "BilinmeyenTanımlayıcı",
-                                        &format!("unknown identifier: `{}`, this identifier could not be found", tok.repr()),
-                                        { traceback.push((tok.line, tok.col, tok.file.clone(), None)); traceback },
-                                        {
-                                            let mut hashk = hashs.clone().into_keys();
-                                            hashk.sort();
-                                            let n = hashk.binary_search(id).unwrap_err();
-                                            if hashk.is_empty() {
-                                                None
-                                            } else {
-                                                Some(format!("maybe you meant {}?", hashk[n]))
-                                            }
-                                        },
-                                    ),
-                                })),
-                            }
-                        }
-                        _ => return Err((stack, hashs, match get_lang() {
-                                SupportedLanguage::Turkish => Error::new(
-                                    "BeklenmedikSimge",
-                                    &format!(
-                                        "tanımlayıcı beklenmişti ancak `{}` bulundu",
-                                        tok.repr()
-                                    ),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                ),
-                                SupportedLanguage::English => Error::new(
-                                    "BeklenmedikSimge",
-                                    &format!(
-                                        "expected identifier, but found `{}`",
-                                        tok.repr()
-                                    ),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                ),
-                            },
-                        )),
-                    }
+thread_local! {
+    static CALLER: RefCell<Option<Caller>> = const { RefCell::new(None) };
+}
 
-                    self.current += 2;
-
-                    let mut latest_id = None;
-                    while self.program.len() > self.current {
-                        let c = self.program.get(self.current).unwrap().clone();
-
-                        match c.typ {
-                            TokenType::İkiNokta => {
-                                let a = match stack.pop() {
-                                    Some(a) => a,
-                                    None => return Err((stack, hashs, match get_lang() {
-                                        SupportedLanguage::Turkish => {
-                                            Error::new(
-                                                "KümedeYeterliDeğişkenYok",
-                                                &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                                { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                                None,
-                                            )
-                                        }
-                                        SupportedLanguage::English => {
-                                            Error::new(
-                                                "KümedeYeterliDeğişkenYok",
-                                                &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                                { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                                None,
-                                            )
-                                        }
-                                    })),
-                                };
-                                match a {
-                                    Object::Harita(map) => {
-                                        let tok = self.program.get(self.current + 1).unwrap();
-                                        match tok.typ {
-                                            TokenType::Identifier { ref id } => {
-                                                let o = match map.map.get(id) {
-                                                    Some(o) => o.clone(),
-                                                    None => return Err((stack, hashs.clone(), match get_lang() {
-                                                        SupportedLanguage::Turkish => Error::new(
-                                                            "BilinmeyenTanımlayıcı",
-                                                            &format!(
-                                                                "bilinmeyen değişken: `{}`, bu değişken bulunamamıştır",
-                                                                tok.repr()
-                                                            ),
-                                                            { traceback.push((tok.line, tok.col, tok.file.clone(), None)); traceback },
-                                                            {
-                                                                let mut hashk: Vec<_> = map.map.clone().into_keys().collect();
-                                                                hashk.sort();
-                                                                let n = hashk.binary_search(id).unwrap_err();
-                                                                if hashk.is_empty() {
-                                                                    None
-                                                                } else {
-                                                                    Some(format!("`{}` demek mi istediniz?", hashk[n]))
-                                                                }
-                                                            }
-                                                        ),
-                                                        SupportedLanguage::English => Error::new(
-                                                            "BilinmeyenTanımlayıcı",
-                                                            &format!(
-                                                                "unknown identifier: `{}`, this identifier could not be found",
-                                                                tok.repr()
-                                                            ),
-                                                            { traceback.push((tok.line, tok.col, tok.file.clone(), None)); traceback },
-                                                            {
-                                                                let mut hashk: Vec<_> = map.map.clone().into_keys().collect();
-                                                                hashk.sort();
-                                                                let n = hashk.binary_search(id).unwrap_err();
-                                                                if hashk.is_empty() {
-                                                                    None
-                                                                } else {
-                                                                    Some(format!("maybe you meant {}?", hashk[n]))
-                                                                }
-                                                            },
-                                                        ),
-                                                    })),
-                                                };
-                                                latest_id = Some(id);
-                                                stack.push(o);
-                                                self.current += 2;
-                                            },
-                                            TokenType::Çarpı => {
-                                                for (k, v) in map.map.iter() {
-                                                    hashs.insert(k.clone(), v.clone());
-                                                }
-                                                let next = match self.program.get(self.current + 2) {
-                                                    Some(t) => t,
-                                                    None => return Err((
-                                                        stack,
-                                                        hashs,
-                                                        match get_lang() {
-                                                            SupportedLanguage::Turkish => Error::new(
-                                                                "BeklenmedikSimge",
-                                                                &format!(
-                                                                    "`)` beklenmişti ancak birşey bulunamadı",
-                                                                ),
-                                                                { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                                                None,
-                                                            ),
-                                                            SupportedLanguage::English => Error::new(
-                                                                "BeklenmedikSimge",
-                                                                &format!(
-                                                                    "expected `)`, but found nothing",
-                                                                ),
-                                                                { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                                                None,
-                                                            ),
-                                                        },
-                                                    )),
-                                                };
-                                                match next.typ {
-                                                    TokenType::InScopeParentR => {
-                                                        self.current += 3;
-                                                        break;
-                                                    },
-                                                    _ => return Err((
-                                                        stack,
-                                                        hashs,
-                                                        match get_lang() {
-                                                            SupportedLanguage::Turkish => Error::new(
-                                                                "BeklenmedikSimge",
-                                                                &format!(
-                                                                    "`)` beklenmişti ancak `{}` bulundu",
-                                                                    next.repr()
-                                                                ),
-                                                                { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                                                None,
-                                                            ),
-                                                            SupportedLanguage::English => Error::new(
-                                                                "BeklenmedikSimge",
-                                                                &format!(
-                                                                    "expected `)`, but found `{}`",
-                                                                    next.repr()
-                                                                ),
-                                                                { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                                                None,
-                                                            ),
-                                                        },
-                                                    )),
-                                                }
-                                            },
-                                            _ => return Err((
-                                                stack,
-                                                hashs,
-                                                match get_lang() {
-                                                    SupportedLanguage::Turkish => Error::new(
-                                                        "BeklenmedikSimge",
-                                                        &format!(
-                                                            "`)` beklenmişti ancak `{}` bulundu",
-                                                            tok.repr()
-                                                        ),
-                                                        { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                                        None,
-                                                    ),
-                                                    SupportedLanguage::English => Error::new(
-                                                        "BeklenmedikSimge",
-                                                        &format!(
-                                                            "expected `)`, but found `{}`",
-                                                            tok.repr()
-                                                        ),
-                                                        { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                                        None,
-                                                    ),
-                                                },
-                                            )),
-                                        }
-                                    }
-                                    b => return Err((
-                                        stack,
-                                        hashs,
-                                        match get_lang() {
-                                            SupportedLanguage::Turkish => Error::new(
-                                                "BeklenmedikTip",
-                                                &format!("harita beklenmişti ancak `{:?}` bulundu", b),
-                                                { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                                None,
-                                            ),
-                                            SupportedLanguage::English => Error::new(
-                                                "BeklenmedikTip",
-                                                &format!("expected map but found `{:?}`", b),
-                                                { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                                None,
-                                            ),
-                                        },
-                                    )),
-                                }
-                            }
-                            TokenType::InScopeParentR => {
-                                self.current += 1;
-                                if let Some(id) = latest_id {
-                                    hashs.insert(id.clone(), stack.pop().unwrap());
-                                } else { unreachable!() }
-                                break;
-                            }
-                            _ => todo!(),
-                        }
-                    }
-                    stack.del_stack();
-                }
-                TokenType::InScopeParentR => {},
-                TokenType::İkiNokta => {
-                    let a = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                            "KümedeYeterliDeğişkenYok",
-                            &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                            { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                            None,
-                        )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                            "KümedeYeterliDeğişkenYok",
-                            &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                            { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                            None,
-                        )
-                            }
-                        })),
-                    };
-                    match a {
-                        Object::Harita(map) => {
-                            let id = self.program.get(self.current + 1).unwrap();
-                            match id.typ.clone() {
-                                TokenType::Identifier { id: ident } => {
-                                    let o = match map.map.get(&ident) {
-                                        Some(o) => o,
-                                        None => return Err((stack, hashs.clone(), match get_lang() {
-                                            SupportedLanguage::Turkish => Error::new(
-                                                "BilinmeyenTanımlayıcı",
-                                                &format!(
-                                                    "bilinmeyen değişken: `{}`, bu değişken bulunamamıştır",
-                                                    tokenc.repr()
-                                                ),
-                                                { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                                {
-                                                    let mut hashk = hashs.clone().into_keys();
-                                                    hashk.sort();
-                                                    let n = hashk.binary_search(&ident).unwrap_err();
-                                                    if hashk.is_empty() {
-                                                        None
-                                                    } else {
-                                                        Some(format!("`{}` demek mi istediniz?", hashk[n]))
-                                                    }
-                                                }
-                                            ),
-                                            SupportedLanguage::English => Error::new(
-                                                "BilinmeyenTanımlayıcı",
-                                                &format!("unknown identifier: `{}`, this identifier could not be found", tokenc.repr()),
-                                                { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                                {
-                                                    let mut hashk = hashs.clone().into_keys();
-                                                    hashk.sort();
-                                                    let n = hashk.binary_search(&ident).unwrap_err();
-                                                    if hashk.is_empty() {
-                                                        None
-                                                    } else {
-                                                        Some(format!("maybe you meant {}?", hashk[n]))
-                                                    }
-                                                },
-                                            ),
-                                        })),
-                                    };
-                                    match o {
-                                        Object::Hiç
-                                        | Object::Bool(_)
-                                        | Object::Sayı(_)
-                                        | Object::Yazı(_)
-                                        | Object::Liste(_)
-                                        | Object::FfiObject(_)
-                                        | Object::Harita(_) => {
-                                            stack.push(o.clone());
-                                            self.current += 2;
-                                        }
-                                        Object::FfiFunction(f) => {
-                                            let res = f.call(&mut stack, &mut hashs);
-                                            match res {
-                                                Ok(Some(o)) => stack.push(o),
-                                                Ok(_) => (),
-                                                Err(e) => return Err((stack, hashs, e)),
-                                            }
-                                            self.current += 2;
-                                        }
-                                        Object::İşlev(tp) => {
-                                            let işlev = self.program.get(*tp).unwrap();
-                                            match işlev.typ {
-                                                TokenType::İşlev { sonloc: tpi } => {
-                                                    let loc = match tpi {
-                                                        Some(i) => i,
-                                                        None => unreachable!(),
-                                                    };
-                                                    let işlevson =
-                                                        self.program.get_mut(loc).unwrap();
-                                                    match &mut işlevson.typ {
-                                                        TokenType::İşlevSonlandır {
-                                                            tp: ref mut tps,
-                                                        } => {
-                                                            tps.push(self.current + 1);
-                                                        }
-                                                        _ => unreachable!(),
-                                                    }
-                                                    self.current = *tp + 2;
-                                                }
-                                                _ => unreachable!(),
-                                            }
-                                            if let TokenType::Identifier { id : fname } =
-                                                &self.program.get(*tp + 1).unwrap().typ {
-                                                traceback.push((tokenc.line, tokenc.col, tokenc.file, Some(fname.clone())));
-                                            }
-                                            stack.new_stack();
-                                            hashs.new_hash();
-                                        }
-                                    }
-                                }
-                                _ => {
-                                    return Err((
-                                        stack,
-                                        hashs,
-                                        match get_lang() {
-                                            SupportedLanguage::Turkish => Error::new(
-                                                "BeklenmedikSimge",
-                                                &format!(
-                                                    "tanımlayıcı beklenmişti ancak `{}` bulundu",
-                                                    id.repr()
-                                                ),
-                                                { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                                None,
-                                            ),
-                                            SupportedLanguage::English => Error::new(
-                                                "BeklenmedikSimge",
-                                                &format!(
-                                                    "expected identifier, but found `{}`",
-                                                    id.repr()
-                                                ),
-                                                { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                                None,
-                                            ),
-                                        },
-                                    ))
-                                }
-                            }
-                        }
-                        b => {
-                            return Err((
-                                stack,
-                                hashs,
-                                match get_lang() {
-                                    SupportedLanguage::Turkish => Error::new(
-                                        "BeklenmedikTip",
-                                        &format!("harita beklenmişti ancak `{:?}` bulundu", b),
-                                        { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                        None,
-                                    ),
-                                    SupportedLanguage::English => Error::new(
-                                        "BeklenmedikTip",
-                                        &format!("expected map but found `{:?}`", b),
-                                        { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                        None,
-                                    ),
-                                },
-                            ))
-                        }
-                    }
-                }
-                TokenType::BlokSonlandır => {
-                    let mut map = Map::new();
-                    if let Some(last_ele) = stack.pop() {
-                        stack.push_ret(last_ele);
-                    }
-                    stack.del_stack();
-                    let fhash = hashs.del_hash().unwrap();
-                    for (k, v) in fhash.into_iter() {
-                        map.map.insert(k, v);
-                    }
-                    hashs.insert(current_namespace.pop().unwrap(), Object::Harita(map));
-                    self.current += 1;
-                }
-                TokenType::Blok => {
-                    stack.new_stack();
-                    hashs.new_hash();
-                    let id = self.program.get(self.current + 1).unwrap();
-                    match id.typ.clone() {
-                        TokenType::Identifier { id: ident } => {
-                            current_namespace.push(ident);
-                        }
-                        _ => {
-                            return Err((
-                                stack,
-                                hashs,
-                                match get_lang() {
-                                    SupportedLanguage::Turkish => Error::new(
-                                        "BeklenmedikSimge",
-                                        &format!(
-                                            "tanımlayıcı beklenmişti ancak `{}` bulundu",
-                                            id.repr()
-                                        ),
-                                        { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                        None,
-                                    ),
-                                    SupportedLanguage::English => Error::new(
-                                        "BeklenmedikSimge",
-                                        &format!("expected identifier, but found `{}`", id.repr()),
-                                        { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                        None,
-                                    ),
-                                },
-                            ))
-                        }
-                    }
-                    self.current += 2;
-                }
-                TokenType::Hiç => {
-                    stack.push(Object::Hiç);
-                    self.current += 1;
-                }
-                TokenType::ParenL => unreachable!(),
-                TokenType::Ver { tp } => {
-                    let a = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                            "KümedeYeterliDeğişkenYok",
-                            &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                            { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                            None,
-                        )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                            "KümedeYeterliDeğişkenYok",
-                            &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                            { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                            None,
-                        )
-                            }
-                        })),
-                    };
-                    stack.push_ret(a);
-                    if let Some(i) = tp {
-                        match self.program.get_mut(i).unwrap().typ {
-                            TokenType::İşlevSonlandır { ref mut tp } => {
-                                if let Some(u) = tp.pop() {
-                                    self.current = u;
-                                }
-                            }
-                            _ => unreachable!(),
-                        };
-                        self.current += 1;
-                        traceback.pop();
-                        stack.del_stack();
-                        hashs.del_hash();
-                    } // error
-                }
-                TokenType::İşlev { sonloc } => {
-                    let id = self.program.get(self.current + 1).unwrap();
-                    match id.typ.clone() {
-                        TokenType::Identifier { id: ident } => {
-                            hashs.insert(ident, Object::İşlev(self.current));
-                        }
-                        _ => {
-                            return Err((
-                                stack,
-                                hashs,
-                                match get_lang() {
-                                    SupportedLanguage::Turkish => Error::new(
-                                        "BeklenmedikSimge",
-                                        &format!(
-                                            "tanımlayıcı beklenmişti ancak `{}` bulundu",
-                                            id.repr()
-                                        ),
-                                        { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                        None,
-                                    ),
-                                    SupportedLanguage::English => Error::new(
-                                        "BeklenmedikSimge",
-                                        &format!("expected identifier, but found `{}`", id.repr()),
-                                        { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                        None,
-                                    ),
-                                },
-                            ))
-                        }
-                    }
-                    let loc = match sonloc {
-                        Some(a) => a,
-                        None => unreachable!(),
-                    };
-                    match self.program.get_mut(loc).unwrap().typ {
-                        TokenType::İşlevSonlandır { ref mut tp } => {
-                            tp.push(loc);
-                        }
-                        _ => unreachable!(),
-                    }
-                    self.current = loc + 1;
-                }
-                TokenType::İşlevSonlandır { .. } => {
-                    let loc = match token.typ {
-                        TokenType::İşlevSonlandır { ref mut tp } => tp.pop().unwrap(), // Safe to unwrap
-                        _ => unreachable!(),
-                    };
-                    self.current = loc;
-                    self.current += 1;
-                    traceback.pop();
-                    stack.del_stack();
-                    hashs.del_hash();
-                }
-                TokenType::De => {
-                    print!(
-                        "{:?}",
-                        match stack.pop() {
-                            Some(a) => a,
-                            None =>
-                                return Err((
-                                    stack,
-                                    hashs,
-                                    match get_lang() {
-                                        SupportedLanguage::Turkish => {
-                                            Error::new(
-                                "KümedeYeterliDeğişkenYok",
-                                &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                None,
-                            )
-                                        }
-                                        SupportedLanguage::English => {
-                                            Error::new(
-                                "KümedeYeterliDeğişkenYok",
-                                &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                None,
-                            )
-                                        }
-                                    }
-                                )),
-                        }
-                    );
-                    self.current += 1;
-                }
-                TokenType::Artı => {
-                    let b = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                "KümedeYeterliDeğişkenYok",
-                                &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                None,
-                            )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                "KümedeYeterliDeğişkenYok",
-                                &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                None,
-                            )
-                            }
-                        })),
-                    };
-                    let a = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                "KümedeYeterliDeğişkenYok",
-                                &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                None,
-                            )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                "KümedeYeterliDeğişkenYok",
-                                &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                None,
-                            )
-                            }
-                        })),
-                    };
-                    stack.push(match a.ekle(b) {
-                        Ok(a) => a,
-                        Err(e) => return Err((stack, hashs, e)),
-                    });
-                    self.current += 1;
-                }
-                TokenType::ArtıArtı => {
-                    let a = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    stack.push(match a.ekle(Object::Sayı(1.0)) {
-                        Ok(a) => a,
-                        Err(e) => return Err((stack, hashs, e)),
-                    });
-                    self.current += 1;
-                }
-                TokenType::Eksi => {
-                    let a = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    let b = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    stack.push(match b.çıkar(a) {
-                        Ok(a) => a,
-                        Err(e) => return Err((stack, hashs, e)),
-                    });
-                    self.current += 1;
-                }
-                TokenType::EksiEksi => {
-                    let a = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    stack.push(match a.çıkar(Object::Sayı(1.0)) {
-                        Ok(a) => a,
-                        Err(e) => return Err((stack, hashs, e)),
-                    });
-                    self.current += 1;
-                }
-                TokenType::Çarpı => {
-                    let b = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    let a = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    stack.push(match a.çarp(b) {
-                        Ok(a) => a,
-                        Err(e) => return Err((stack, hashs, e)),
-                    });
-                    self.current += 1;
-                }
-                TokenType::Bölü => {
-                    let a = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    let b = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    stack.push(match b.böl(a) {
-                        Ok(a) => a,
-                        Err(e) => return Err((stack, hashs, e)),
-                    });
-                    self.current += 1;
-                }
-                TokenType::Sayı { val } => {
-                    let n = Object::Sayı(val);
-                    stack.push(n);
-                    self.current += 1;
-                }
-                TokenType::Yazı { val } => {
-                    let s = Object::Yazı(val);
-                    stack.push(s);
-                    self.current += 1;
-                }
-                TokenType::Bool { val } => {
-                    let b = Object::Bool(val);
-                    stack.push(b);
-                    self.current += 1;
-                }
-                TokenType::İse(yoksa) | TokenType::İken(yoksa) => {
-                    if let Some(tp) = yoksa {
-                        let a = match stack.pop() {
-                            Some(a) => a,
-                            None => return Err((stack, hashs, match get_lang() {
-                                SupportedLanguage::Turkish => {
-                                    Error::new(
-                                            "KümedeYeterliDeğişkenYok",
-                                            &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                            { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                            None,
-                                        )
-                                }
-                                SupportedLanguage::English => {
-                                    Error::new(
-                                            "KümedeYeterliDeğişkenYok",
-                                            &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                            { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                            None,
-                                        )
-                                }
-                            })),
-                        };
-                        match a {
-                            Object::Bool(b) => {
-                                if b {
-                                    self.current += 1;
-                                } else {
-                                    self.current = tp;
-                                }
-                            }
-                            _ => {
-                                let b = match stack.pop() {
-                                    Some(a) => a,
-                                    None => return Err((stack, hashs, match get_lang() {
-                                        SupportedLanguage::Turkish => {
-                                            Error::new(
-                                                    "KümedeYeterliDeğişkenYok",
-                                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` anahtar kelimesi uygulanamamıştır", tokenc.repr()),
-                                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                                    None,
-                                                )
-                                        }
-                                        SupportedLanguage::English => {
-                                            Error::new(
-                                                    "KümedeYeterliDeğişkenYok",
-                                                    &format!("because there weren't enough variables in the stack, the keyword `{}` couldn't be used", tokenc.repr()),
-                                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                                    None,
-                                                )
-                                        }
-                                    })),
-                                };
-                                match b.eşittir(a) {
-                                    Ok(Object::Bool(true)) => self.current += 1,
-                                    Ok(Object::Bool(false)) => self.current = tp,
-                                    Ok(_) => unreachable!(),
-                                    Err(e) => return Err((stack, hashs, e)),
-                                }
-                            }
-                        }
-                    } else {
-                        unreachable!()
-                    }
-                }
-                TokenType::Sına  => {
-                    let a = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                        "KümedeYeterliDeğişkenYok",
-                                        &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                        { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                        None,
-                                    )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                        "KümedeYeterliDeğişkenYok",
-                                        &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                        { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                        None,
-                                    )
-                            }
-                        })),
-                    };
-                    match a {
-                        Object::Bool(b) => {
-                            if b {
-                                self.current += 1;
-                            } else {
-                                return Err((stack, hashs, match get_lang() {
-                                    SupportedLanguage::Turkish => Error::new(
-                                        "BaşarısızSınama",
-                                        "Sınama başarısız oldu",
-                                        { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                        None,
-                                    ),
-                                    SupportedLanguage::English => Error::new(
-                                        "BaşarısızSınama",
-                                        "Test was unsuccessful",
-                                        { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                        None,
-                                    ),
-                                }));
-                            }
-                        }
-                        b => return Err((
-                             stack,
-                             hashs,
-                             match get_lang() {
-                                 SupportedLanguage::Turkish => Error::new(
-                                     "BeklenmedikTip",
-                                     &format!("bool beklenmişti ancak `{:?}` bulundu", b),
-                                     { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                     None,
-                                 ),
-                                 SupportedLanguage::English => Error::new(
-                                     "BeklenmedikTip",
-                                     &format!("expected bool but found `{:?}`", b),
-                                     { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                     None,
-                                 ),
-                             },
-                        )),
-                    }
-                }
-                TokenType::Kopya => {
-                    let last = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    stack.push(last.clone());
-                    stack.push(last);
-                    self.current += 1;
-                }
-                TokenType::Büyüktür => {
-                    let a = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    let b = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    stack.push(match b.büyüktür(a) {
-                        Ok(a) => a,
-                        Err(e) => return Err((stack, hashs, e)),
-                    });
-                    self.current += 1;
-                }
-                TokenType::BüyükEşittir => {
-                    let a = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    let b = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    stack.push(match b.büyük_eşittir(a) {
-                        Ok(a) => a,
-                        Err(e) => return Err((stack, hashs, e)),
-                    });
-                    self.current += 1;
-                }
-                TokenType::Küçüktür => {
-                    let a = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    let b = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    stack.push(match b.küçüktür(a) {
-                        Ok(a) => a,
-                        Err(e) => return Err((stack, hashs, e)),
-                    });
-                    self.current += 1;
-                }
-                TokenType::KüçükEşittir => {
-                    let a = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    let b = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    stack.push(match b.küçük_eşittir(a) {
-                        Ok(a) => a,
-                        Err(e) => return Err((stack, hashs, e)),
-                    });
-                    self.current += 1;
-                }
-                TokenType::Eşittir => {
-                    let a = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    let b = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    stack.push(match b.eşittir(a) {
-                        Ok(a) => a,
-                        Err(e) => return Err((stack, hashs, e)),
-                    });
-                    self.current += 1;
-                }
-                TokenType::EşitDeğildir => {
-                    let a = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    let b = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    stack.push(match b.eşit_değildir(a) {
-                        Ok(a) => a,
-                        Err(e) => return Err((stack, hashs, e)),
-                    });
-                    self.current += 1;
-                }
-                TokenType::Değildir => {
-                    let a = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    stack.push(match a.değildir() {
-                        Ok(a) => a,
-                        Err(e) => return Err((stack, hashs, e)),
-                    });
-                    self.current += 1;
-                }
-                TokenType::Son { tp } => {
-                    self.current = tp;
-                }
-                TokenType::Yoksa(yoksa) => {
-                    if let Some(tp) = yoksa {
-                        self.current = tp;
-                    } else {
-                        unreachable!()
-                    }
-                }
-                TokenType::Modulo => {
-                    let a = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    let b = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    stack.push(match b.modulo(a) {
-                        Ok(a) => a,
-                        Err(e) => return Err((stack, hashs, e)),
-                    });
-                    self.current += 1;
-                }
-                TokenType::Takas => {
-                    let a = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` anahtar kelimesi uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the keyword `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    let b = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` anahtar kelimesi uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the keyword `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    stack.push(a);
-                    stack.push(b);
-                    self.current += 1;
-                }
-                TokenType::Döndür => {
-                    let a = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` anahtar kelimesi uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the keyword `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    let b = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` anahtar kelimesi uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the keyword `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    let c = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` anahtar kelimesi uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the keyword `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    stack.push(a);
-                    stack.push(b);
-                    stack.push(c);
-                    self.current += 1;
-                }
-                TokenType::At => {
-                    match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` anahtar kelimesi uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the keyword `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    self.current += 1;
-                }
-                TokenType::Üst => {
-                    let a = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` anahtar kelimesi uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the keyword `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    let b = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` anahtar kelimesi uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the keyword `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    stack.push(b.clone());
-                    stack.push(a);
-                    stack.push(b);
-                    self.current += 1;
-                }
-                TokenType::Girdi => {
-                    io::stdout().flush().unwrap();
-                    let mut buf = String::new();
-                    io::stdin().read_line(&mut buf).unwrap();
-                    stack.push(Object::Yazı(buf.trim_end().to_string()));
-                    self.current += 1;
-                }
-                TokenType::İkiNoktaNokta | TokenType::EOF => self.current += 1,
-                TokenType::Identifier { id } => match hashs.clone().get_mut(&id) {
-                    Some(val) => match val {
-                        Object::Hiç
-                        | Object::Bool(_)
-                        | Object::Sayı(_)
-                        | Object::Yazı(_)
-                        | Object::Liste(_)
-                        | Object::FfiObject(_)
-                        | Object::Harita(_) => {
-                            stack.push(val.clone());
-                            self.current += 1;
-                        }
-                        Object::FfiFunction(f) => {
-                            let res = f.call(&mut stack, &mut hashs);
-                            match res {
-                                Ok(Some(o)) => stack.push(o),
-                                Ok(_) => (),
-                                Err(e) => return Err((stack, hashs, e)),
-                            }
-                            self.current += 1;
-                        }
-                        Object::İşlev(tp) => {
-                            let işlev = self.program.get(*tp).unwrap();
-                            match işlev.typ {
-                                TokenType::İşlev { sonloc: tpi } => {
-                                    let loc = match tpi {
-                                        Some(i) => i,
-                                        None => unreachable!(),
-                                    };
-                                    let işlevson = self.program.get_mut(loc).unwrap();
-                                    match &mut işlevson.typ {
-                                        TokenType::İşlevSonlandır { tp: ref mut tps } => {
-                                            tps.push(self.current);
-                                        }
-                                        _ => unreachable!(),
-                                    }
-                                    self.current = *tp + 2;
-                                }
-                                _ => unreachable!(),
-                            }
-                            
-                            if let TokenType::Identifier { id : fname } =
-                                &self.program.get(*tp + 1).unwrap().typ {
-                                traceback.push((tokenc.line, tokenc.col, tokenc.file, Some(fname.clone())));
-                            }
-                            stack.new_stack();
-                            hashs.new_hash();
-                        }
-                    },
-                    None => {
-                        return Err((stack, hashs.clone(), match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "BilinmeyenTanımlayıcı",
-                                    &format!(
-                                        "bilinmeyen değişken: `{}`, bu değişken bulunamamıştır",
-                                        tokenc.repr()
-                                    ),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    {
-                                        let mut hashk = hashs.clone().into_keys();
-                                        hashk.sort();
-                                        let n = hashk.binary_search(&id).unwrap_err();
-                                        if hashk.is_empty() {
-                                            None
-                                        } else {
-                                            Some(format!("`{}` demek mi istediniz?", hashk[n]))
-                                        }
-                                    }
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                        "BilinmeyenTanımlayıcı",
-                                        &format!("unknown identifier: `{}`, this identifier could not be found", tokenc.repr()),
-                                        { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                        {
-                                            let mut hashk = hashs.clone().into_keys();
-                                            hashk.sort();
-                                            let n = hashk.binary_search(&id).unwrap_err();
-                                            if hashk.is_empty() {
-                                                None
-                                            } else {
-                                                Some(format!("maybe you meant {}?", hashk[n]))
-                                            }
-                                        },
-                                    )
-                            }
-                        }));
-                    }
-                },
-                TokenType::Koy => {
-                    let a = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    let id = self.program.get(self.current + 1).unwrap();
-                    hashs.insert(
-                        match id.typ.clone() {
-                            TokenType::Identifier { id: i } => i,
-                            t => {
-                                return Err((
-                                    stack,
-                                    hashs,
-                                    match get_lang() {
-                                        SupportedLanguage::Turkish => Error::new(
-                                            "BeklenmedikSimge",
-                                            &format!(
-                                            "Tanımlayıcı simgesi beklenmişti ancak {:?} bulundu",
-                                            t
-                                        ),
-                                            { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                            None,
-                                        ),
-                                        SupportedLanguage::English => Error::new(
-                                            "BeklenmedikSimge",
-                                            &format!("expected Identifier but found {:?}", t),
-                                            { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                            None,
-                                        ),
-                                    },
-                                ))
-                            }
-                        },
-                        a,
-                    );
-                    self.current += 2;
-                }
-                TokenType::Ve => {
-                    let a = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    let b = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    stack.push(
-                        match b.ve(a, tokenc.line, tokenc.col, tokenc.file.clone()) {
-                            Ok(a) => a,
-                            Err(e) => return Err((stack, hashs, e)),
-                        },
-                    );
-                    self.current += 1;
-                }
-                TokenType::Veya => {
-                    let a = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    let b = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    stack.push(
-                        match b.veya(a, tokenc.line, tokenc.col, tokenc.file.clone()) {
-                            Ok(a) => a,
-                            Err(e) => return Err((stack, hashs, e)),
-                        },
-                    );
-                    self.current += 1;
+/// The name of the function defined at `tp` of `program`, or an error at what `traceback` gives if
+/// there is none, like when the function was defined by another program
+fn defined_at(program: &Program, tp: usize, traceback: impl FnOnce() -> Vec<Trace>) -> Result<u32, Error> {
+    match program.code.get(tp) {
+        Some(Instruction::İşlev { ad, .. }) => Ok(*ad),
+        _ => Err(match get_lang() {
+            SupportedLanguage::Turkish => Error::new(
+                "ÇağrıHatası",
+                &format!("{} numaralı komutta bir işlev tanımlanmıyor, işlev başka bir programda tanımlanmış olabilir", tp),
+                traceback(),
+                None,
+            ),
+            SupportedLanguage::English => Error::new(
+                "CallError",
+                &format!("no function is defined at instruction {}, it may have been defined by another program", tp),
+                traceback(),
+                None,
+            ),
+        }),
+    }
+}
+
+/// Calls the function `f` with its arguments already pushed to `stack`, what it returns is left on
+/// `stack`
+///
+/// tr-lang functions can only be called this way from inside an `FfiFunction`, while the program
+/// that defined them is running.
+pub fn call(f: &Object, stack: &mut StackMemory, hashs: &mut HashMemory) -> Result<(), Error> {
+    match f {
+        Object::FfiFunction(f) => {
+            if let Some(o) = f.call(stack, hashs)? {
+                stack.push(o);
+            }
+            Ok(())
+        }
+        Object::İşlev(tp) => {
+            let caller = match CALLER.with(|c| c.borrow_mut().take()) {
+                Some(c) => c,
+                None => return Err(match get_lang() {
+                    SupportedLanguage::Turkish => Error::new(
+                        "ÇağrıHatası",
+                        "işlevler yalnızca onları tanımlayan program çalışırken çağrılabilir",
+                        vec![],
+                        None,
+                    ),
+                    SupportedLanguage::English => Error::new(
+                        "CallError",
+                        "functions can only be called while the program defining them is running",
+                        vec![],
+                        None,
+                    ),
+                }),
+            };
+            if let Err(e) = defined_at(&caller.program, *tp, Vec::new) {
+                CALLER.with(|c| *c.borrow_mut() = Some(caller));
+                return Err(e);
+            }
+            let permissions = caller.permissions;
+            let max_depth = caller.max_depth;
+            let limits = caller.limits;
+            // returning past the last instruction ends the run right after the function returns
+            let mut run = Run {
+                frames: vec![Frame::new(caller.program.code.len(), None, hashs.depth())],
+                program: caller.program,
+                consts: caller.consts,
+                current: *tp + 1,
+                slots: vec![],
+                max_depth,
+                limits: Limits::default(),
+                permissions,
+                io: caller.io,
+                namespaces: vec![],
+                libraries: vec![],
+                breakpoints: BTreeSet::new(),
+                paused_at: None,
+            };
+            stack.new_stack();
+            hashs.new_hash();
+            let res = match run.proceed(RunConfig {
+                mem: (std::mem::take(stack), std::mem::take(hashs)),
+                permissions,
+                max_depth,
+                max_instructions: limits.instructions,
+                timeout: limits.deadline.map(|d| d.saturating_duration_since(Instant::now())),
+                cancel: limits.cancel,
+                max_stack: limits.max_stack,
+                max_values: limits.max_values,
+                max_memory: limits.max_memory,
+                ..Default::default()
+            }) {
+                Ok((s, h)) => {
+                    *stack = s;
+                    *hashs = h;
+                    Ok(())
                 }
-                TokenType::Tipinde => {
-                    let a = match stack.pop() {
-                        Some(a) => a,
-                        None => return Err((stack, hashs, match get_lang() {
-                            SupportedLanguage::Turkish => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("kümede yeterli değişken bulunmadığından dolayı `{}` operatörü uygulanamamıştır", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                            SupportedLanguage::English => {
-                                Error::new(
-                                    "KümedeYeterliDeğişkenYok",
-                                    &format!("because there weren't enough variables in the stack, the operator `{}` couldn't be used", tokenc.repr()),
-                                    { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                    None,
-                                )
-                            }
-                        })),
-                    };
-                    self.current += 1;
-                    let b = self.program.get_mut(self.current).unwrap();
-                    match &b.typ {
-                        TokenType::Identifier { id } => {
-                            stack.push(
-                                match a.dönüştür(id.clone(), b.line, b.col, b.file.clone()) {
-                                    Ok(a) => a,
-                                    Err(e) => return Err((stack, hashs, e)),
-                                },
-                            );
-                        }
-                        _ => {
-                            return Err((
-                                stack,
-                                hashs,
-                                match get_lang() {
-                                    SupportedLanguage::Turkish => Error::new(
-                                        "BeklenmedikSimge",
-                                        &format!(
-                                            "tanımlayıcı beklenmişti ancak `{}` bulundu",
-                                            b.repr()
-                                        ),
-                                        { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                        None,
-                                    ),
-                                    SupportedLanguage::English => Error::new(
-                                        "BeklenmedikSimge",
-                                        &format!("expected identifier, but found `{}`", b.repr()),
-                                        { traceback.push((tokenc.line, tokenc.col, tokenc.file, None)); traceback },
-                                        None,
-                                    ),
-                                },
-                            ))
-                        }
-                    };
-                    self.current += 1;
+                Err((s, h, e)) => {
+                    *stack = s;
+                    *hashs = h;
+                    Err(e)
                 }
+            };
+            CALLER.with(|c| {
+                *c.borrow_mut() = Some(Caller {
+                    program: run.program,
+                    consts: run.consts,
+                    permissions,
+                    max_depth,
+                    limits: run.limits,
+                    io: run.io,
+                })
+            });
+            res
+        }
+        b => Err(match get_lang() {
+            SupportedLanguage::Turkish => Error::new(
+                "BeklenmedikTip",
+                &format!("işlev beklenmişti ancak `{:?}` bulundu", b),
+                vec![],
+                None,
+            ),
+            SupportedLanguage::English => Error::new(
+                "BeklenmedikTip",
+                &format!("expected function but found `{:?}`", b),
+                vec![],
+                None,
+            ),
+        }),
+    }
+}
+
+/// Whether the run calling the `FfiFunction` being run is out of instructions or time or was
+/// cancelled, for functions that keep calling back into tr-lang to know when to stop
+pub(crate) fn limits_reached() -> bool {
+    CALLER.with(|c| match &*c.borrow() {
+        Some(caller) => {
+            let limits = &caller.limits;
+            limits.instructions == Some(0)
+                || limits.cancel.as_ref().is_some_and(|c| c.is_cancelled())
+                || limits.deadline.is_some_and(|d| Instant::now() >= d)
+        }
+        None => false,
+    })
+}
+
+/// Writes `e` where the run calling the `FfiFunction` being run writes warnings, for errors that
+/// don't stop the run
+pub(crate) fn report(e: &Error) {
+    CALLER.with(|c| match &mut *c.borrow_mut() {
+        Some(caller) => {
+            let _ = caller.io.stdout.flush();
+            e.error_to(&mut caller.io.stderr);
+        }
+        None => e.error_print(),
+    })
+}
+
+/// Roughly how many bytes `a + b` takes up when it makes a new string, bytes or list
+fn sum_size(a: &Object, b: &Object) -> usize {
+    match (a, b) {
+        (Object::Yazı(_), Object::Yazı(_))
+        | (Object::Baytlar(_), Object::Baytlar(_))
+        | (Object::Liste(_), Object::Liste(_)) => a.size() + b.size(),
+        _ => 0,
+    }
+}
+
+/// Roughly how many bytes `a * b` takes up when it repeats a string
+fn product_size(a: &Object, b: &Object) -> usize {
+    match (a, b) {
+        (Object::Yazı(s), Object::Sayı(n)) | (Object::Sayı(n), Object::Yazı(s)) => {
+            // saturates rather than overflowing
+            (s.len() as f64 * n.max(0.0)) as usize
+        }
+        _ => 0,
+    }
+}
+
+/// `traceback` with calls that repeat one after another shown once, like the calls of a recursive
+/// function
+///
+/// Runs of up to 8 calls are looked for, the last call of a run that repeats notes how many times
+/// it did.
+fn collapse(traceback: Vec<Trace>) -> Vec<Trace> {
+    let mut collapsed: Vec<Trace> = vec![];
+    let mut i = 0;
+    while i < traceback.len() {
+        let mut best = (1, 1);
+        for len in 1..=8 {
+            let run = &traceback[i..(i + len).min(traceback.len())];
+            let mut times = 1;
+            while traceback.get(i + len * times..i + len * (times + 1)) == Some(run) {
+                times += 1;
+            }
+            if times > 1 && len * (times - 1) > best.0 * (best.1 - 1) {
+                best = (len, times);
             }
         }
+        let (len, times) = best;
+        collapsed.extend_from_slice(&traceback[i..i + len]);
+        if times > 1 {
+            let (_, _, _, name) = collapsed.last_mut().unwrap();
+            let note = match (get_lang(), len) {
+                (SupportedLanguage::Turkish, 1) => format!("{} kez tekrarlandı", times),
+                (SupportedLanguage::Turkish, _) => format!("son {} çağrı {} kez tekrarlandı", len, times),
+                (SupportedLanguage::English, 1) => format!("repeated {} times", times),
+                (SupportedLanguage::English, _) => format!("last {} calls repeated {} times", len, times),
+            };
+            *name = Some(match name {
+                Some(name) => format!("{} ({})", name, note),
+                None => format!("({})", note),
+            });
+        }
+        i += len * times;
+    }
+    collapsed
+}
 
-        let mut edt = Ok(());
-        for i in loaded_libraries.into_iter() {
-            if let Err(e) = terminate_library(&i.1, i.0, &mut stack, &mut hashs) {
-                if let Ok(()) = edt {
-                    edt = Err(e);
-                }
+/// Pops a value off `$stack`, returning a `KümedeYeterliDeğişkenYok` error if it is empty
+macro_rules! pop {
+    ($self:ident, $stack:ident) => {
+        pop!($self, $stack, false)
+    };
+    ($self:ident, $stack:ident, $keyword:expr) => {
+        match $stack.pop() {
+            Some(a) => a,
+            None => return Err($self.not_enough($keyword)),
+        }
+    };
+}
+
+impl Run {
+    pub fn new(program: impl Into<Arc<Program>>) -> Self {
+        let program = program.into();
+        Self {
+            consts: program.consts.iter().map(|c| c.to_object()).collect(),
+            program,
+            current: 0,
+            frames: vec![],
+            slots: vec![],
+            max_depth: usize::MAX,
+            limits: Limits::default(),
+            permissions: Permissions::all(),
+            io: Io::default(),
+            namespaces: vec![],
+            libraries: vec![],
+            breakpoints: BTreeSet::new(),
+            paused_at: None,
+        }
+    }
+
+    /// Calls `f`, lending it the program so that it can use [`call`]
+    fn call_ffi(
+        &mut self,
+        f: &FfiFunction,
+        permissions: Permissions,
+        stack: &mut StackMemory,
+        hashs: &mut HashMemory,
+    ) -> Result<Option<Object>, Error> {
+        // the function may write to the standard output itself
+        if let Err(e) = self.io.stdout.flush() {
+            return Err(self.io_error(e));
+        }
+        let caller = Caller {
+            program: self.program.clone(),
+            consts: std::mem::take(&mut self.consts),
+            permissions,
+            max_depth: self.max_depth.saturating_sub(self.frames.len()),
+            limits: self.limits.clone(),
+            io: std::mem::take(&mut self.io),
+        };
+        let prev = CALLER.with(|c| c.replace(Some(caller)));
+        let res = f.call(stack, hashs);
+        let caller = CALLER.with(|c| c.replace(prev)).unwrap();
+        self.consts = caller.consts;
+        // what the callbacks ran is taken out of the limits
+        self.limits = caller.limits;
+        self.io = caller.io;
+        res
+    }
+
+    /// Enters the function defined at `tp`, called from the current instruction
+    ///
+    /// A call right before the end of a function or a `ver`, maybe through jumps, is a tail call:
+    /// the function being called takes over the frame of the caller instead of getting a new one.
+    /// It shares the caller's scope and stack, which the caller would never use again, and
+    /// returns straight to where the caller would. So recursion through tail calls runs in
+    /// constant memory. What a function tail called before the end returns is dropped, as it
+    /// would have been by the caller, and what one tail called before `ver` returns is returned
+    /// by the caller. If it returns nothing the caller's `ver` returns what is on top of the
+    /// stack instead, which is what was under the arguments unless the function left anything
+    /// else.
+    ///
+    /// Calls in a `blok` are not tail calls, the `blok` has a scope of its own.
+    fn call_işlev(
+        &mut self,
+        tp: usize,
+        stack: &mut StackMemory,
+        hashs: &mut HashMemory,
+    ) -> Result<(), Error> {
+        let ad = defined_at(&self.program, tp, || self.here())?;
+        let (line, col, file) = self.program.position(self.program.spans[self.current]);
+        let trace = (line, col, file, Some(self.program.names[ad as usize].clone()));
+        // the call is followed by whatever the jumps right after it lead to, like the end of an
+        // `ise` at the end of a function
+        let mut next = self.current + 1;
+        for _ in 0..self.program.code.len() {
+            match self.program.code.get(next) {
+                Some(Instruction::Atla(tp)) => next = *tp as usize,
+                _ => break,
             }
         }
-        if let Err(e) = edt {
-            return Err((stack, hashs, Error::new(
-                "DinamikYüklemeHatası",
-                &format!("{}", e),
+        let returns = match self.program.code.get(next) {
+            Some(Instruction::İşlevSonlandır) => Some(Returns::Nothing),
+            Some(Instruction::Ver) => Some(Returns::ValueOrTop),
+            _ => None,
+        };
+        // `ver` can be used outside of functions, where there is no frame to take over
+        if let (Some(returns), Some(frame)) = (returns, self.frames.last_mut()) {
+            if hashs.depth() == frame.scope + 1 {
+                frame.tail = Some(trace);
+                frame.tail_calls += 1;
+                frame.returns = frame.returns.then(returns);
+                self.current = tp + 1;
+                return Ok(());
+            }
+        }
+        if self.frames.len() >= self.max_depth {
+            return Err(self.recursion_limit(trace));
+        }
+        self.frames.push(Frame::new(self.current + 1, Some(trace), hashs.depth()));
+        self.current = tp + 1;
+        stack.new_stack();
+        hashs.new_hash();
+        Ok(())
+    }
+
+    /// Gives back what the function being run returns, `ret` being what it returned itself
+    fn give_back(&self, ret: Option<Object>, stack: &mut StackMemory) -> Result<(), Error> {
+        let returns = self.frames.last().map_or(Returns::Value, |frame| frame.returns);
+        let ret = match (returns, ret) {
+            (Returns::Nothing, _) => None,
+            (Returns::Value | Returns::ValueOrTop, Some(a)) => Some(a),
+            (Returns::Value, None) => None,
+            (Returns::ValueOrTop | Returns::Top, _) => Some(pop!(self, stack)),
+        };
+        if let Some(a) = ret {
+            stack.push_ret(a);
+        }
+        Ok(())
+    }
+
+    /// Returns from the function being run
+    fn ret(&mut self, stack: &mut StackMemory, hashs: &mut HashMemory) {
+        let scope = match self.frames.pop() {
+            Some(frame) => {
+                self.current = frame.ret;
+                frame.scope
+            }
+            None => {
+                self.current += 1;
+                hashs.depth() - 1
+            }
+        };
+        while stack.depth() > scope {
+            stack.del_stack();
+        }
+        while hashs.depth() > scope {
+            hashs.del_hash();
+        }
+    }
+
+    /// Where every function being run was called from
+    ///
+    /// Only the last tail call of every frame is kept, along with how many were left out.
+    pub fn traceback(&self) -> Vec<Trace> {
+        let mut traceback = vec![];
+        for frame in self.frames.iter() {
+            traceback.extend(frame.trace.clone());
+            if let Some((line, col, file, name)) = frame.tail.clone() {
+                let name = match frame.tail_calls - 1 {
+                    0 => name,
+                    n => name.map(|name| match get_lang() {
+                        SupportedLanguage::Turkish => format!("{} ({} kuyruk çağrısı gösterilmiyor)", name, n),
+                        SupportedLanguage::English => format!("{} ({} tail calls elided)", name, n),
+                    }),
+                };
+                traceback.push((line, col, file, name));
+            }
+        }
+        traceback
+    }
+
+    /// The traceback along with where `span` is
+    fn trace(&self, span: Span) -> Vec<Trace> {
+        let (line, col, file) = self.program.position(span);
+        let mut traceback = self.traceback();
+        traceback.push((line, col, file, None));
+        traceback
+    }
+
+    /// The traceback along with where the current instruction is, if the program hasn't ended
+    fn here(&self) -> Vec<Trace> {
+        match self.program.spans.get(self.current) {
+            Some(span) => self.trace(*span),
+            None => self.traceback(),
+        }
+    }
+
+    fn not_enough(&self, keyword: bool) -> Error {
+        let repr = self.program.repr(self.current);
+        match get_lang() {
+            SupportedLanguage::Turkish => Error::new(
+                "KümedeYeterliDeğişkenYok",
+                &format!(
+                    "kümede yeterli değişken bulunmadığından dolayı `{}` {} uygulanamamıştır",
+                    repr,
+                    if keyword { "anahtar kelimesi" } else { "operatörü" },
+                ),
+                self.here(),
+                None,
+            ),
+            SupportedLanguage::English => Error::new(
+                "KümedeYeterliDeğişkenYok",
+                &format!(
+                    "because there weren't enough variables in the stack, the {} `{}` couldn't be used",
+                    if keyword { "keyword" } else { "operator" },
+                    repr,
+                ),
+                self.here(),
+                None,
+            ),
+        }
+    }
+
+    /// An error for the unknown identifier `id` at `span`, suggesting the closest of `known`
+    fn unknown(&self, span: Span, id: &str, mut known: Vec<String>) -> Error {
+        known.sort();
+        let near = match known.binary_search_by(|k| k.as_str().cmp(id)) {
+            Ok(n) | Err(n) => known.get(n),
+        };
+        match get_lang() {
+            SupportedLanguage::Turkish => Error::new(
+                "BilinmeyenTanımlayıcı",
+                &format!("bilinmeyen değişken: `{}`, bu değişken bulunamamıştır", id),
+                self.trace(span),
+                near.map(|n| format!("`{}` demek mi istediniz?", n)),
+            ),
+            SupportedLanguage::English => Error::new(
+                "BilinmeyenTanımlayıcı",
+                &format!("unknown identifier: `{}`, this identifier could not be found", id),
+                self.trace(span),
+                near.map(|n| format!("maybe you meant {}?", n)),
+            ),
+        }
+    }
+
+    /// An error for calling one more function than [`RunConfig::max_depth`] allows, `trace` being
+    /// the call
+    fn recursion_limit(&self, trace: Trace) -> Error {
+        let mut traceback = self.traceback();
+        traceback.push(trace);
+        let traceback = collapse(traceback);
+        match get_lang() {
+            SupportedLanguage::Turkish => Error::new(
+                "ÖzyinelemeSınırı",
+                &format!("aynı anda en fazla {} işlev çalışabilir", self.max_depth),
+                traceback,
+                None,
+            ),
+            SupportedLanguage::English => Error::new(
+                "RecursionLimit",
+                &format!("at most {} functions can be run at once", self.max_depth),
                 traceback,
                 None,
-            )));
+            ),
+        }
+    }
+
+    /// Takes the instruction about to be run out of the limits, or an error if it can't be run
+    fn check_limits(&mut self) -> Result<(), Error> {
+        let limits = &mut self.limits;
+        let (tr, en) = if limits.instructions == Some(0) {
+            (
+                ("KomutSınırı", "çalıştırılabilecek komut sayısı aşıldı"),
+                ("InstructionLimit", "ran out of instructions that can be run"),
+            )
+        } else {
+            if let Some(n) = &mut limits.instructions {
+                *n -= 1;
+            }
+            limits.unchecked += 1;
+            if limits.unchecked < CHECK_EVERY {
+                return Ok(());
+            }
+            limits.unchecked = 0;
+            if limits.cancel.as_ref().is_some_and(|c| c.is_cancelled()) {
+                (
+                    ("İptalEdildi", "program iptal edildi"),
+                    ("Cancelled", "the program was cancelled"),
+                )
+            } else if limits.deadline.is_some_and(|d| Instant::now() >= d) {
+                (
+                    ("ZamanAşımı", "program zaman aşımına uğradı"),
+                    ("Timeout", "the program timed out"),
+                )
+            } else {
+                return Ok(());
+            }
+        };
+        Err(match get_lang() {
+            SupportedLanguage::Turkish => Error::new(tr.0, tr.1, self.here(), None),
+            SupportedLanguage::English => Error::new(en.0, en.1, self.here(), None),
+        })
+    }
+
+    /// An error if the memories, with `extra` more bytes about to be taken up, hold more than the
+    /// limits in [`RunConfig`] allow
+    ///
+    /// `extra` is only worked out when there is a memory limit, like the bytes the memories count.
+    fn check_memory(
+        &self,
+        extra: impl FnOnce() -> usize,
+        stack: &StackMemory,
+        hashs: &HashMemory,
+    ) -> Result<(), Error> {
+        let limits = &self.limits;
+        let values = stack.len() + hashs.len();
+        let bytes = || stack.bytes().unwrap_or(0) + hashs.bytes().unwrap_or(0) + extra();
+        let (tr, en) = match (limits.max_stack, limits.max_values, limits.max_memory) {
+            (Some(max), _, _) if stack.len() > max => (
+                format!("kümede en fazla {} değer olabilir", max),
+                format!("there can be at most {} values on the stack", max),
+            ),
+            (_, Some(max), _) if values > max => (
+                format!("en fazla {} değer tutulabilir", max),
+                format!("at most {} values can be kept", max),
+            ),
+            (_, _, Some(max)) if bytes() > max => (
+                format!("değerler en fazla {} bayt yer kaplayabilir", max),
+                format!("values can take up at most {} bytes", max),
+            ),
+            _ => return Ok(()),
+        };
+        Err(match get_lang() {
+            SupportedLanguage::Turkish => Error::new("BellekSınırı", &tr, self.here(), None),
+            SupportedLanguage::English => Error::new("MemoryLimit", &en, self.here(), None),
+        })
+    }
+
+    /// An error for failing to read input or write output
+    fn io_error(&self, e: io::Error) -> Error {
+        match get_lang() {
+            SupportedLanguage::Turkish => Error::new("GirdiÇıktıHatası", &format!("{}", e), self.here(), None),
+            SupportedLanguage::English => Error::new("IoError", &format!("{}", e), self.here(), None),
+        }
+    }
+
+    /// An error for doing something [`RunConfig::permissions`] doesn't allow
+    fn denied(&self, tr: &str, en: &str) -> Error {
+        match get_lang() {
+            SupportedLanguage::Turkish => Error::new("İzinHatası", tr, self.here(), None),
+            SupportedLanguage::English => Error::new("İzinHatası", en, self.here(), None),
+        }
+    }
+
+    fn expected_map(&self, found: &Object) -> Error {
+        match get_lang() {
+            SupportedLanguage::Turkish => Error::new(
+                "BeklenmedikTip",
+                &format!("harita beklenmişti ancak `{:?}` bulundu", found),
+                self.here(),
+                None,
+            ),
+            SupportedLanguage::English => Error::new(
+                "BeklenmedikTip",
+                &format!("expected map but found `{:?}`", found),
+                self.here(),
+                None,
+            ),
+        }
+    }
+
+    /// Pushes `o`, or calls it if it is a function
+    fn push_or_call(
+        &mut self,
+        o: Object,
+        permissions: Permissions,
+        stack: &mut StackMemory,
+        hashs: &mut HashMemory,
+    ) -> Result<(), Error> {
+        match o {
+            Object::İşlev(tp) => {
+                return self.call_işlev(tp, stack, hashs);
+            }
+            Object::FfiFunction(f) => {
+                if let Some(o) = self.call_ffi(&f, permissions, stack, hashs)? {
+                    stack.push(o);
+                }
+            }
+            o => stack.push(o),
+        }
+        self.current += 1;
+        Ok(())
+    }
+
+    /// Runs the program from the start with the memories in `config`, which are given back
+    /// whether it succeeds or not
+    #[allow(clippy::result_large_err)]
+    pub fn run(
+        &mut self,
+        config: RunConfig,
+    ) -> Result<(StackMemory, HashMemory), (StackMemory, HashMemory, Error)> {
+        self.reset();
+        self.resume(config)
+    }
+
+    /// Runs the program from where it stopped with the memories it stopped with, after a
+    /// `KomutSınırı`, `ZamanAşımı` or `İptalEdildi` error or being restored from a
+    /// [`Snapshot`](super::Snapshot)
+    ///
+    /// The instruction the run stopped at is run again, so resuming after any other error is
+    /// likely to fail the same way.
+    #[allow(clippy::result_large_err)]
+    pub fn resume(
+        &mut self,
+        mut config: RunConfig,
+    ) -> Result<(StackMemory, HashMemory), (StackMemory, HashMemory, Error)> {
+        self.io = Io::new(config.stdin.take(), config.stdout.take(), config.stderr.take());
+        self.proceed(config)
+    }
+
+    /// Calls the function called `name` in the memories in `config` with `args` pushed to the
+    /// stack, returning the values it leaves on the stack along with the memories
+    ///
+    /// The function has to be defined in the program of the `Run` or be an `FfiFunction`, like the
+    /// functions in the memories returned by [`Run::run`]. The memories are given back with the
+    /// scopes of the call taken off whether it succeeds or not.
+    #[allow(clippy::result_large_err, clippy::type_complexity)]
+    pub fn call(
+        &mut self,
+        name: &str,
+        args: Vec<Object>,
+        mut config: RunConfig,
+    ) -> Result<(Vec<Object>, StackMemory, HashMemory), (StackMemory, HashMemory, Error)> {
+        self.frames.clear();
+        self.namespaces.clear();
+        self.io = Io::new(config.stdin.take(), config.stdout.take(), config.stderr.take());
+        let (mut stack, mut hashs) = std::mem::take(&mut config.mem);
+        let f = match hashs.get(&name.to_string()) {
+            Some(Object::İşlev(tp)) => match defined_at(&self.program, *tp, Vec::new) {
+                Ok(_) => Object::İşlev(*tp),
+                Err(e) => return Err((stack, hashs, e)),
+            },
+            Some(f @ Object::FfiFunction(_)) => f.clone(),
+            found => {
+                let (tr, en) = match found {
+                    Some(_) => (
+                        ("BeklenmedikTip", format!("`{}` bir işlev değil", name)),
+                        ("BeklenmedikTip", format!("`{}` is not a function", name)),
+                    ),
+                    None => (
+                        ("BilinmeyenTanımlayıcı", format!("bilinmeyen işlev: `{}`", name)),
+                        ("BilinmeyenTanımlayıcı", format!("unknown function: `{}`", name)),
+                    ),
+                };
+                return Err((stack, hashs, match get_lang() {
+                    SupportedLanguage::Turkish => Error::new(tr.0, &tr.1, vec![], None),
+                    SupportedLanguage::English => Error::new(en.0, &en.1, vec![], None),
+                }));
+            }
+        };
+        // the arguments and what the function returns are kept in a scope of their own
+        let scope = hashs.depth();
+        stack.new_stack();
+        hashs.new_hash();
+        for arg in args {
+            stack.push(arg);
+        }
+        let res = match f {
+            Object::İşlev(tp) => {
+                // returning past the last instruction ends the run right after the function
+                // returns
+                self.frames.push(Frame::new(self.program.code.len(), None, hashs.depth()));
+                self.current = tp + 1;
+                stack.new_stack();
+                hashs.new_hash();
+                config.mem = (stack, hashs);
+                self.proceed(config)
+            }
+            Object::FfiFunction(f) => {
+                self.max_depth = config.max_depth;
+                self.limits = Limits::new(&config);
+                let res = self.call_ffi(&f, config.permissions, &mut stack, &mut hashs);
+                let res = res.and_then(|o| {
+                    if let Some(o) = o {
+                        stack.push(o);
+                    }
+                    self.io.stdout.flush().map_err(|e| self.io_error(e))
+                });
+                match res {
+                    Ok(()) => Ok((stack, hashs)),
+                    Err(e) => Err((stack, hashs, e)),
+                }
+            }
+            _ => unreachable!(),
+        };
+        let unwind = |stack: &mut StackMemory, hashs: &mut HashMemory| {
+            let mut values = vec![];
+            while stack.depth() > scope {
+                values = stack.del_stack().unwrap_or_default();
+            }
+            while hashs.depth() > scope {
+                hashs.del_hash();
+            }
+            values
+        };
+        match res {
+            Ok((mut stack, mut hashs)) => {
+                let values = unwind(&mut stack, &mut hashs);
+                Ok((values, stack, hashs))
+            }
+            Err((mut stack, mut hashs, e)) => {
+                unwind(&mut stack, &mut hashs);
+                Err((stack, hashs, e))
+            }
+        }
+    }
+
+    /// Gets the run ready to be run from the start a few instructions at a time with
+    /// [`Run::run_until`], giving back the memories in `config` to run it with
+    ///
+    /// The stack and the variables can be looked at and changed between calls, as long as the
+    /// same memories keep being passed.
+    pub fn start(&mut self, mut config: RunConfig) -> (StackMemory, HashMemory) {
+        self.reset();
+        self.io = Io::new(config.stdin.take(), config.stdout.take(), config.stderr.take());
+        self.configure(config)
+    }
+
+    /// Runs the next instruction, see [`Run::run_until`]
+    pub fn step(&mut self, stack: &mut StackMemory, hashs: &mut HashMemory) -> Result<Status, Error> {
+        self.run_until(1, stack, hashs)
+    }
+
+    /// Runs at most `steps` instructions, stopping before the next instruction at a breakpoint
+    ///
+    /// The breakpoint the last call stopped at is run past, so it is only stopped at once each time
+    /// it is reached.
+    /// The limits in the [`RunConfig`] given to [`Run::start`] count every instruction run since.
+    /// After an error the instruction that failed is run again, like [`Run::resume`].
+    pub fn run_until(
+        &mut self,
+        steps: u64,
+        stack: &mut StackMemory,
+        hashs: &mut HashMemory,
+    ) -> Result<Status, Error> {
+        let res = match self.execute(Some(steps), stack, hashs) {
+            Ok(Status::Finished) => self.terminate_libraries(stack, hashs).map(|()| Status::Finished),
+            res => res,
+        };
+        match (res, self.io.stdout.flush()) {
+            (Ok(_), Err(e)) => Err(self.io_error(e)),
+            (res, _) => res,
+        }
+    }
+
+    /// Makes [`Run::run_until`] stop before running the instruction at `at`
+    pub fn add_breakpoint(&mut self, at: usize) {
+        self.breakpoints.insert(at);
+    }
+
+    /// Removes the breakpoint at `at`, returning whether there was one
+    pub fn remove_breakpoint(&mut self, at: usize) -> bool {
+        self.breakpoints.remove(&at)
+    }
+
+    /// Every breakpoint, in order
+    pub fn breakpoints(&self) -> impl Iterator<Item = usize> + '_ {
+        self.breakpoints.iter().copied()
+    }
+
+    /// The instruction about to be run, the length of the program once it has ended
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    /// The program being run
+    pub fn program(&self) -> &Program {
+        &self.program
+    }
+
+    /// Runs the program from the current instruction with the I/O it already has, flushing its
+    /// output when it stops
+    #[allow(clippy::result_large_err)]
+    fn proceed(
+        &mut self,
+        config: RunConfig,
+    ) -> Result<(StackMemory, HashMemory), (StackMemory, HashMemory, Error)> {
+        let res = self.proceed_unflushed(config);
+        match (res, self.io.stdout.flush()) {
+            (Ok((stack, hashs)), Err(e)) => Err((stack, hashs, self.io_error(e))),
+            (res, _) => res,
+        }
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn proceed_unflushed(
+        &mut self,
+        config: RunConfig,
+    ) -> Result<(StackMemory, HashMemory), (StackMemory, HashMemory, Error)> {
+        let file = config.file.clone();
+        let (supress_warnings, repl) = (config.supress_warnings, config.repl);
+        let (mut stack, mut hashs) = self.configure(config);
+
+        if let Err(e) = self.execute(None, &mut stack, &mut hashs) {
+            return Err((stack, hashs, e));
+        }
+        if let Err(e) = self.terminate_libraries(&mut stack, &mut hashs) {
+            return Err((stack, hashs, e));
         }
+        let mut traceback = self.traceback();
 
-        if stack.len() > 0 && !config.supress_warnings && !config.repl {
+        if !stack.is_empty() && !supress_warnings && !repl {
+            let _ = self.io.stdout.flush();
+            let out = &mut self.io.stdout;
             match get_lang() {
                 SupportedLanguage::Turkish => {
                     Error::warning(
@@ -2008,29 +1200,30 @@ impl Run {
                         { traceback.push((0, 0, file, None)); traceback },
                         None
                     );
-                    print!("    kümede kalan değişkenler({:?}) [", stack.len());
+                    let _ = write!(out, "    kümede kalan değişkenler({:?}) [", stack.len());
                     for (i, o) in stack.iter_vec().iter().rev().take(3).rev().enumerate() {
                         let o = match o {
                             Object::Yazı(s) => format!("{:?}", s),
                             Object::Hiç
                             | Object::Bool(_)
                             | Object::Sayı(_)
+                            | Object::Baytlar(_)
                             | Object::Liste(_)
                             | Object::Harita(_) => format!("{:?}", o),
                             Object::FfiObject(o) => o.repr(),
                             Object::İşlev(_) | Object::FfiFunction(_) => unreachable!(),
                         };
                         if i > 0 {
-                            print!(", {}", o);
+                            let _ = write!(out, ", {}", o);
                         } else {
                             if stack.len() > 3 {
-                                print!("... {}", o);
+                                let _ = write!(out, "... {}", o);
                             } else {
-                                print!("{}", o);
+                                let _ = write!(out, "{}", o);
                             }
                         }
                     }
-                    println!("]");
+                    let _ = writeln!(out, "]");
                 }
                 SupportedLanguage::English => {
                     Error::warning(
@@ -2038,33 +1231,426 @@ impl Run {
                         "stack is not empty, if you aren't sure about why, you might want to take a look at you code",
                         { traceback.push((0, 0, file, None)); traceback },
                         None
-                    ).warn();
-                    print!("    variables left in the stack({:?}) [", stack.len());
+                    ).warn_to(&mut self.io.stderr);
+                    let _ = write!(out, "    variables left in the stack({:?}) [", stack.len());
                     for (i, o) in stack.iter_vec().iter().rev().take(3).rev().enumerate() {
                         let o = match o {
                             Object::Yazı(s) => format!("{:?}", s),
                             Object::Hiç
                             | Object::Bool(_)
                             | Object::Sayı(_)
+                            | Object::Baytlar(_)
                             | Object::Liste(_)
                             | Object::Harita(_) => format!("{:?}", o),
                             Object::FfiObject(o) => o.repr(),
                             Object::İşlev(_) | Object::FfiFunction(_) => unreachable!(),
                         };
                         if i > 0 {
-                            print!(", {}", o);
+                            let _ = write!(out, ", {}", o);
                         } else {
                             if stack.len() > 3 {
-                                print!("...{}", o);
+                                let _ = write!(out, "...{}", o);
                             } else {
-                                print!("{}", o);
+                                let _ = write!(out, "{}", o);
                             }
                         }
                     }
-                    println!("]");
+                    let _ = writeln!(out, "]");
                 }
             }
         }
         Ok((stack, hashs))
     }
+
+    /// Starts the run over from the first instruction
+    fn reset(&mut self) {
+        self.current = 0;
+        self.paused_at = None;
+        self.frames.clear();
+        self.namespaces.clear();
+        self.libraries.clear();
+    }
+
+    /// Takes the limits and permissions in `config`, giving back its memories
+    fn configure(&mut self, config: RunConfig) -> (StackMemory, HashMemory) {
+        self.max_depth = config.max_depth;
+        self.limits = Limits::new(&config);
+        self.permissions = config.permissions;
+        let (mut stack, mut hashs) = config.mem;
+        stack.count_bytes(config.max_memory.is_some());
+        hashs.count_bytes(config.max_memory.is_some());
+        self.slots = self.program.names.iter().map(|name| hashs.slot(name)).collect();
+        (stack, hashs)
+    }
+
+    /// Terminates the shared libraries the program loaded, once it has ended
+    fn terminate_libraries(&mut self, stack: &mut StackMemory, hashs: &mut HashMemory) -> Result<(), Error> {
+        let mut edt = Ok(());
+        // the libraries may write to the standard output themselves
+        let _ = self.io.stdout.flush();
+        for i in std::mem::take(&mut self.libraries).into_iter() {
+            if let Err(e) = terminate_library(&i.1, i.0, stack, hashs) {
+                if let Ok(()) = edt {
+                    edt = Err(e);
+                }
+            }
+        }
+        edt.map_err(|e| Error::new(
+            "DinamikYüklemeHatası",
+            &format!("{}", e),
+            self.traceback(),
+            None,
+        ))
+    }
+
+    /// Runs the program until it ends, or if `steps` is given until that many instructions are
+    /// run or a breakpoint is reached
+    fn execute(
+        &mut self,
+        mut steps: Option<u64>,
+        stack: &mut StackMemory,
+        hashs: &mut HashMemory,
+    ) -> Result<Status, Error> {
+        let permissions = self.permissions;
+
+        while self.program.code.len() > self.current {
+            if let Some(n) = &mut steps {
+                if *n == 0 {
+                    return Ok(Status::Running);
+                }
+                // the breakpoint the last call stopped at is run past
+                let paused_at = self.paused_at.take();
+                if self.breakpoints.contains(&self.current) && paused_at != Some(self.current) {
+                    self.paused_at = Some(self.current);
+                    return Ok(Status::Breakpoint(self.current));
+                }
+                *n -= 1;
+            }
+            self.check_limits()?;
+            self.check_memory(|| 0, stack, hashs)?;
+            match self.program.code[self.current] {
+                Instruction::Sabit(i) => stack.push(self.consts[i as usize].clone()),
+                Instruction::Bool(b) => stack.push(Object::Bool(b)),
+                Instruction::Hiç => stack.push(Object::Hiç),
+                ins @ (Instruction::Identifier(i) | Instruction::Yerel(i) | Instruction::Global(i)) => {
+                    let slot = self.slots[i as usize];
+                    let o = match ins {
+                        Instruction::Yerel(_) => hashs.get_local_slot(slot),
+                        Instruction::Global(_) => hashs.get_glob_slot(slot),
+                        _ => hashs.get_slot(slot),
+                    };
+                    let o = match o {
+                        Some(o) => o.clone(),
+                        None => {
+                            let known = hashs.into_keys();
+                            let id = &self.program.names[i as usize];
+                            return Err(self.unknown(self.program.spans[self.current], id, known));
+                        }
+                    };
+                    self.push_or_call(o, permissions, stack, hashs)?;
+                    continue;
+                }
+                Instruction::Koy(i) => {
+                    let a = pop!(self, stack);
+                    hashs.insert_slot(self.slots[i as usize], a);
+                }
+                Instruction::İkiNokta(i) => {
+                    let map = match pop!(self, stack) {
+                        Object::Harita(map) => map,
+                        b => return Err(self.expected_map(&b)),
+                    };
+                    let id = &self.program.names[i as usize];
+                    let o = match map.get(id) {
+                        Some(o) => o.clone(),
+                        None => {
+                            let known = map.keys().cloned().collect();
+                            return Err(self.unknown(self.program.spans[self.current], id, known));
+                        }
+                    };
+                    self.push_or_call(o, permissions, stack, hashs)?;
+                    continue;
+                }
+                Instruction::Tipinde(i) => {
+                    let a = pop!(self, stack);
+                    let (line, col, file) = self.program.position(self.program.spans[self.current]);
+                    stack.push(a.dönüştür(self.program.names[i as usize].clone(), line, col, file)?);
+                }
+                Instruction::İşlev { ad, son } => {
+                    hashs.insert_slot(self.slots[ad as usize], Object::İşlev(self.current));
+                    self.current = son as usize + 1;
+                    continue;
+                }
+                Instruction::İşlevSonlandır => {
+                    self.give_back(None, stack)?;
+                    self.ret(stack, hashs);
+                    continue;
+                }
+                Instruction::Ver => {
+                    let a = pop!(self, stack);
+                    self.give_back(Some(a), stack)?;
+                    self.ret(stack, hashs);
+                    continue;
+                }
+                Instruction::Atla(tp) => {
+                    self.current = tp as usize;
+                    continue;
+                }
+                Instruction::İse(tp) | Instruction::İken(tp) => {
+                    let a = pop!(self, stack);
+                    let holds = match a {
+                        Object::Bool(b) => b,
+                        a => {
+                            let b = pop!(self, stack, true);
+                            match b.eşittir(a)? {
+                                Object::Bool(b) => b,
+                                _ => unreachable!(),
+                            }
+                        }
+                    };
+                    if !holds {
+                        self.current = tp as usize;
+                        continue;
+                    }
+                }
+                Instruction::Blok(i) => {
+                    stack.new_stack();
+                    hashs.new_hash();
+                    self.namespaces.push(self.slots[i as usize]);
+                }
+                Instruction::BlokSonlandır => {
+                    if let Some(last_ele) = stack.pop() {
+                        stack.push_ret(last_ele);
+                    }
+                    stack.del_stack();
+                    let map = Map::from(hashs.del_hash().unwrap());
+                    hashs.insert_slot(self.namespaces.pop().unwrap(), Object::Harita(map));
+                }
+                Instruction::İçeAktar(i) => {
+                    let import = &self.program.imports[i as usize];
+                    let (root, span) = import.root;
+                    let mut o = match hashs.get_slot(self.slots[root as usize]) {
+                        Some(o) => o.clone(),
+                        None => {
+                            let known = hashs.into_keys();
+                            let id = &self.program.names[root as usize];
+                            return Err(self.unknown(span, id, known));
+                        }
+                    };
+                    for (member, span) in import.members.iter() {
+                        let map = match o {
+                            Object::Harita(map) => map,
+                            b => return Err(self.expected_map(&b)),
+                        };
+                        let id = &self.program.names[*member as usize];
+                        o = match map.get(id) {
+                            Some(o) => o.clone(),
+                            None => {
+                                let known = map.keys().cloned().collect();
+                                return Err(self.unknown(*span, id, known));
+                            }
+                        };
+                    }
+                    if import.all {
+                        match o {
+                            Object::Harita(map) => {
+                                for (k, v) in map.into_inner().into_iter() {
+                                    hashs.insert(k, v);
+                                }
+                            }
+                            b => return Err(self.expected_map(&b)),
+                        }
+                    } else {
+                        let (last, _) = import.members.last().unwrap();
+                        hashs.insert_slot(self.slots[*last as usize], o);
+                    }
+                }
+                Instruction::LibSymbol(i) => {
+                    let s = &self.program.names[i as usize];
+                    if let Some(module) = stdlib::module(s) {
+                        if module.permission.is_some_and(|p| !permissions.allows(p)) {
+                            return Err(self.denied(
+                                &format!("`{}` modülünü yükleme izni yok", s),
+                                &format!("no permission to load the module `{}`", s),
+                            ));
+                        }
+                        (module.init)(stack, hashs);
+                    } else {
+                        if !permissions.allows(Permission::Ffi) {
+                            return Err(self.denied(
+                                &format!("`{}` kütüphanesini yükleme izni yok", s),
+                                &format!("no permission to load the library `{}`", s),
+                            ));
+                        }
+                        match load_library(s, stack, hashs) {
+                            Ok(lib) => self.libraries.push((lib, s.clone())),
+                            Err(e) => return Err(Error::new(
+                                "DinamikYüklemeHatası",
+                                &format!("{}", e),
+                                self.traceback(),
+                                None,
+                            )),
+                        }
+                    }
+                }
+                Instruction::Artı => {
+                    let b = pop!(self, stack);
+                    let a = pop!(self, stack);
+                    self.check_memory(|| sum_size(&a, &b), stack, hashs)?;
+                    stack.push(a.ekle(b)?);
+                }
+                Instruction::ArtıArtı => {
+                    let a = pop!(self, stack);
+                    stack.push(a.ekle(Object::Sayı(1.0))?);
+                }
+                Instruction::Eksi => {
+                    let b = pop!(self, stack);
+                    let a = pop!(self, stack);
+                    stack.push(a.çıkar(b)?);
+                }
+                Instruction::EksiEksi => {
+                    let a = pop!(self, stack);
+                    stack.push(a.çıkar(Object::Sayı(1.0))?);
+                }
+                Instruction::Çarpı => {
+                    let b = pop!(self, stack);
+                    let a = pop!(self, stack);
+                    self.check_memory(|| product_size(&a, &b), stack, hashs)?;
+                    stack.push(a.çarp(b)?);
+                }
+                Instruction::Bölü => {
+                    let b = pop!(self, stack);
+                    let a = pop!(self, stack);
+                    stack.push(a.böl(b)?);
+                }
+                Instruction::Modulo => {
+                    let b = pop!(self, stack);
+                    let a = pop!(self, stack);
+                    stack.push(a.modulo(b)?);
+                }
+                Instruction::De => {
+                    let o = pop!(self, stack);
+                    if let Err(e) = write!(self.io.stdout, "{:?}", o) {
+                        return Err(self.io_error(e));
+                    }
+                }
+                Instruction::Büyüktür => {
+                    let b = pop!(self, stack);
+                    let a = pop!(self, stack);
+                    stack.push(a.büyüktür(b)?);
+                }
+                Instruction::BüyükEşittir => {
+                    let b = pop!(self, stack);
+                    let a = pop!(self, stack);
+                    stack.push(a.büyük_eşittir(b)?);
+                }
+                Instruction::Küçüktür => {
+                    let b = pop!(self, stack);
+                    let a = pop!(self, stack);
+                    stack.push(a.küçüktür(b)?);
+                }
+                Instruction::KüçükEşittir => {
+                    let b = pop!(self, stack);
+                    let a = pop!(self, stack);
+                    stack.push(a.küçük_eşittir(b)?);
+                }
+                Instruction::Eşittir => {
+                    let b = pop!(self, stack);
+                    let a = pop!(self, stack);
+                    stack.push(a.eşittir(b)?);
+                }
+                Instruction::EşitDeğildir => {
+                    let b = pop!(self, stack);
+                    let a = pop!(self, stack);
+                    stack.push(a.eşit_değildir(b)?);
+                }
+                Instruction::Değildir => {
+                    let a = pop!(self, stack);
+                    stack.push(a.değildir()?);
+                }
+                Instruction::Ve => {
+                    let b = pop!(self, stack);
+                    let a = pop!(self, stack);
+                    let (line, col, file) = self.program.position(self.program.spans[self.current]);
+                    stack.push(a.ve(b, line, col, file)?);
+                }
+                Instruction::Veya => {
+                    let b = pop!(self, stack);
+                    let a = pop!(self, stack);
+                    let (line, col, file) = self.program.position(self.program.spans[self.current]);
+                    stack.push(a.veya(b, line, col, file)?);
+                }
+                Instruction::Sına => match pop!(self, stack) {
+                    Object::Bool(true) => (),
+                    Object::Bool(false) => return Err(match get_lang() {
+                        SupportedLanguage::Turkish => Error::new(
+                            "BaşarısızSınama",
+                            "Sınama başarısız oldu",
+                            self.here(),
+                            None,
+                        ),
+                        SupportedLanguage::English => Error::new(
+                            "BaşarısızSınama",
+                            "Test was unsuccessful",
+                            self.here(),
+                            None,
+                        ),
+                    }),
+                    b => return Err(match get_lang() {
+                        SupportedLanguage::Turkish => Error::new(
+                            "BeklenmedikTip",
+                            &format!("bool beklenmişti ancak `{:?}` bulundu", b),
+                            self.here(),
+                            None,
+                        ),
+                        SupportedLanguage::English => Error::new(
+                            "BeklenmedikTip",
+                            &format!("expected bool but found `{:?}`", b),
+                            self.here(),
+                            None,
+                        ),
+                    }),
+                },
+                Instruction::Kopya => {
+                    let last = pop!(self, stack);
+                    stack.push(last.clone());
+                    stack.push(last);
+                }
+                Instruction::Takas => {
+                    let a = pop!(self, stack, true);
+                    let b = pop!(self, stack, true);
+                    stack.push(a);
+                    stack.push(b);
+                }
+                Instruction::Döndür => {
+                    let a = pop!(self, stack, true);
+                    let b = pop!(self, stack, true);
+                    let c = pop!(self, stack, true);
+                    stack.push(a);
+                    stack.push(b);
+                    stack.push(c);
+                }
+                Instruction::Üst => {
+                    let a = pop!(self, stack, true);
+                    let b = pop!(self, stack, true);
+                    stack.push(b.clone());
+                    stack.push(a);
+                    stack.push(b);
+                }
+                Instruction::At => {
+                    pop!(self, stack, true);
+                }
+                Instruction::Girdi => {
+                    if !permissions.allows(Permission::Input) {
+                        return Err(self.denied("girdi okuma izni yok", "no permission to read input"));
+                    }
+                    match self.io.read_line() {
+                        Ok(line) => stack.push(Object::Yazı(line)),
+                        Err(e) => return Err(self.io_error(e)),
+                    }
+                }
+            }
+            self.current += 1;
+        }
+        Ok(Status::Finished)
+    }
 }
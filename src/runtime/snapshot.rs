@@ -0,0 +1,433 @@
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use super::runtime::{Frame, Run};
+use super::Permissions;
+use crate::bytecode::{from_bytecode, nesting, to_bytecode, Instruction, Program};
+use crate::error::Error;
+use crate::mem::{HashMemory, List, Map, Object, StackMemory};
+use crate::stdlib;
+use crate::util::{get_lang, SupportedLanguage};
+
+/// The first bytes of every snapshot
+pub const SNAPSHOT_MAGIC: &[u8; 4] = b"TRSN";
+/// Version of the snapshot layout, bumped whenever [`Snapshot`] changes shape
+pub const SNAPSHOT_VERSION: u16 = 1;
+
+/// An object as it is kept in a [`Snapshot`]
+#[derive(Serialize, Deserialize)]
+enum Saved {
+    Sayı(f64),
+    Yazı(String),
+    Baytlar(Vec<u8>),
+    Bool(bool),
+    İşlev(usize),
+    Liste(Vec<Saved>),
+    Harita(Vec<(String, Saved)>),
+    Hiç,
+    /// A function of a built-in module, found again by its name when restored
+    Yerleşik { module: String, name: String },
+}
+
+/// Everything about a [`Run`] and its memories, to be saved and picked up later
+///
+/// Functions of the built-in modules the program loads are saved by the name they are kept under,
+/// and found again in those modules when the snapshot is restored. FFI objects, like open files,
+/// can't be saved, nor can programs that load shared libraries.
+#[derive(Serialize, Deserialize)]
+pub struct Snapshot {
+    /// The program as a .trbyt file
+    program: Vec<u8>,
+    current: usize,
+    frames: Vec<Frame>,
+    namespaces: Vec<usize>,
+    stack: Vec<Vec<Saved>>,
+    names: Vec<String>,
+    global: Vec<Option<Saved>>,
+    scopes: Vec<Vec<(usize, Saved)>>,
+    /// The built-in modules the program loads
+    modules: Vec<String>,
+}
+
+fn snapshot_error(tr: &str, en: &str, note: Option<String>) -> Error {
+    match get_lang() {
+        SupportedLanguage::Turkish => Error::new("KayıtHatası", tr, vec![], note),
+        SupportedLanguage::English => Error::new("SnapshotError", en, vec![], note),
+    }
+}
+
+fn corrupt() -> Error {
+    snapshot_error("kayıt bozuk", "the snapshot is corrupt", None)
+}
+
+/// Turns objects into what they are saved as, noting the ones that can't be saved
+struct Saver {
+    /// The names every built-in module that is loaded defines
+    modules: Vec<(String, Vec<String>)>,
+    /// The module the map being saved was made by, if its functions all belong to one
+    within: Option<usize>,
+    /// What couldn't be saved
+    unsaved: Vec<String>,
+}
+
+impl Saver {
+    /// Saves `o`, which is found at `path`
+    fn save(&mut self, o: &Object, path: &mut Vec<String>) -> Saved {
+        match o {
+            Object::Sayı(n) => Saved::Sayı(*n),
+            Object::Yazı(s) => Saved::Yazı(s.clone()),
+            Object::Baytlar(b) => Saved::Baytlar(b.clone()),
+            Object::Bool(b) => Saved::Bool(*b),
+            Object::İşlev(tp) => Saved::İşlev(*tp),
+            Object::Hiç => Saved::Hiç,
+            Object::Liste(l) => Saved::Liste(
+                l.iter()
+                    .enumerate()
+                    .map(|(i, o)| {
+                        path.push(i.to_string());
+                        let saved = self.save(o, path);
+                        path.pop();
+                        saved
+                    })
+                    .collect(),
+            ),
+            Object::Harita(m) => {
+                // names like `yaz` are defined by more than one module, the other functions next
+                // to them tell which one
+                let functions: Vec<&String> =
+                    m.iter().filter(|(_, o)| matches!(o, Object::FfiFunction(_))).map(|(k, _)| k).collect();
+                let mut found = self
+                    .modules
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, (_, names))| functions.iter().all(|k| names.contains(k)));
+                let within = match (functions.is_empty(), found.next(), found.next()) {
+                    (false, Some((i, _)), None) => Some(i),
+                    _ => None,
+                };
+                let outer = std::mem::replace(&mut self.within, within);
+                let saved = m
+                    .iter()
+                    .map(|(k, o)| {
+                        path.push(k.clone());
+                        let saved = self.save(o, path);
+                        path.pop();
+                        (k.clone(), saved)
+                    })
+                    .collect();
+                self.within = outer;
+                Saved::Harita(saved)
+            }
+            Object::FfiFunction(_) => {
+                let name = path.last().cloned().unwrap_or_default();
+                let module = match self.within {
+                    Some(i) if self.modules[i].1.contains(&name) => Some(&self.modules[i].0),
+                    _ => {
+                        let mut found = self.modules.iter().filter(|(_, names)| names.contains(&name));
+                        match (found.next(), found.next()) {
+                            (Some((module, _)), None) => Some(module),
+                            _ => None,
+                        }
+                    }
+                };
+                match module {
+                    Some(module) => Saved::Yerleşik { module: module.clone(), name },
+                    None => {
+                        self.unsave(o, path);
+                        Saved::Hiç
+                    }
+                }
+            }
+            Object::FfiObject(_) => {
+                self.unsave(o, path);
+                Saved::Hiç
+            }
+        }
+    }
+
+    fn unsave(&mut self, o: &Object, path: &[String]) {
+        self.unsaved.push(format!("`{}` ({})", path.join("."), o.type_name()));
+    }
+}
+
+/// Turns what was saved back into objects
+struct Restorer<'a> {
+    program: &'a Program,
+    /// What every built-in module that is loaded defines
+    modules: HashMap<String, HashMemory>,
+}
+
+impl Restorer<'_> {
+    fn restore(&mut self, saved: Saved) -> Result<Object, Error> {
+        Ok(match saved {
+            Saved::Sayı(n) => Object::Sayı(n),
+            Saved::Yazı(s) => Object::Yazı(s),
+            Saved::Baytlar(b) => Object::Baytlar(b),
+            Saved::Bool(b) => Object::Bool(b),
+            Saved::İşlev(tp) => match self.program.code.get(tp) {
+                Some(Instruction::İşlev { .. }) => Object::İşlev(tp),
+                _ => return Err(corrupt()),
+            },
+            Saved::Hiç => Object::Hiç,
+            Saved::Liste(l) => Object::Liste(List::new(
+                l.into_iter().map(|s| self.restore(s)).collect::<Result<_, _>>()?,
+            )),
+            Saved::Harita(m) => Object::Harita(Map::from(
+                m.into_iter()
+                    .map(|(k, s)| Ok((k, self.restore(s)?)))
+                    .collect::<Result<HashMap<_, _>, Error>>()?,
+            )),
+            Saved::Yerleşik { module, name } => {
+                let hashs = match self.modules.get_mut(&module) {
+                    Some(hashs) => hashs,
+                    None => return Err(corrupt()),
+                };
+                match hashs.get(&name) {
+                    Some(f @ Object::FfiFunction(_)) => f.clone(),
+                    _ => return Err(corrupt()),
+                }
+            }
+        })
+    }
+}
+
+/// Whether the scopes, stacks and `blok`s of `snapshot` are nested as deep as `program` is where
+/// the run and every function being run are, so that returning and ending blocks can't go past
+/// the global scope
+fn nested(snapshot: &Snapshot, program: &Program) -> bool {
+    let nesting = nesting(program);
+    let depth = snapshot.scopes.len() + 1;
+    // the scopes there are when the function being looked at starts, and whether it is the
+    // program itself
+    let (mut base, mut top, frames) = match snapshot.frames.split_first() {
+        // functions called from Rust are called in a scope of their own on top of the global one
+        Some((first, rest)) if first.trace.is_none() => {
+            if first.scope < 2 {
+                return false;
+            }
+            (first.scope + 1, false, rest)
+        }
+        _ => (1, true, &snapshot.frames[..]),
+    };
+    let mut blocks = 0;
+    // where every function was called from, then where the run is
+    let sites = frames.iter().map(|f| f.ret.checked_sub(1)).chain([Some(snapshot.current)]);
+    for (i, site) in sites.enumerate() {
+        let (inner, function) = match site {
+            Some(site) => nesting[site],
+            None => return false,
+        };
+        let scope = match frames.get(i) {
+            Some(f) => f.scope,
+            None => depth,
+        };
+        // only the program itself is run outside of functions
+        if top != function.is_none() || scope != base + inner {
+            return false;
+        }
+        blocks += inner;
+        base = scope + 1;
+        top = false;
+    }
+    snapshot.namespaces.len() == blocks && snapshot.stack.len() == depth
+}
+
+impl Snapshot {
+    /// Serializes the snapshot, laid out as [`SNAPSHOT_MAGIC`], [`SNAPSHOT_VERSION`] as a little
+    /// endian `u16` and the snapshot serialized with bincode
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = SNAPSHOT_MAGIC.to_vec();
+        buf.extend_from_slice(&SNAPSHOT_VERSION.to_le_bytes());
+        buf.extend(bincode::serialize(self).unwrap());
+        buf
+    }
+
+    /// Reads a snapshot written by [`Snapshot::to_bytes`]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() < 6 || &bytes[..4] != SNAPSHOT_MAGIC {
+            return Err(snapshot_error(
+                "dosya bir tr-lang kaydı değil",
+                "file is not a tr-lang snapshot",
+                None,
+            ));
+        }
+        let version = u16::from_le_bytes(bytes[4..6].try_into().unwrap());
+        if version != SNAPSHOT_VERSION {
+            return Err(snapshot_error(
+                &format!("kayıt sürümü {} desteklenmiyor, bu tr-lang sürüm {} kayıtlarını okuyabilir", version, SNAPSHOT_VERSION),
+                &format!("snapshot version {} is not supported, this tr-lang reads version {}", version, SNAPSHOT_VERSION),
+                None,
+            ));
+        }
+        bincode::deserialize(&bytes[6..]).map_err(|e| {
+            snapshot_error(
+                &format!("kayıt okunamadı: {}", e),
+                &format!("couldn't read the snapshot: {}", e),
+                None,
+            )
+        })
+    }
+}
+
+impl Run {
+    /// Saves where the run is along with `stack` and `hashs`, the memories it was run with
+    ///
+    /// Everything that can't be saved is listed in the error, before anything is saved.
+    pub fn snapshot(&self, stack: &StackMemory, hashs: &HashMemory) -> Result<Snapshot, Error> {
+        let program = &self.program;
+        let mut modules = vec![];
+        let mut libraries = vec![];
+        for ins in program.code.iter() {
+            if let Instruction::LibSymbol(i) = ins {
+                let name = &program.names[*i as usize];
+                match stdlib::module(name) {
+                    Some(module) if !modules.iter().any(|(m, _)| m == name) => {
+                        let (mut s, mut h) = (StackMemory::new(), HashMemory::new());
+                        (module.init)(&mut s, &mut h);
+                        modules.push((name.clone(), h.into_keys()));
+                    }
+                    Some(_) => (),
+                    None => libraries.push(name.clone()),
+                }
+            }
+        }
+        let mut saver = Saver { modules, within: None, unsaved: vec![] };
+        for library in libraries {
+            saver.unsaved.push(match get_lang() {
+                SupportedLanguage::Turkish => format!("`{}` kütüphanesi", library),
+                SupportedLanguage::English => format!("the library `{}`", library),
+            });
+        }
+
+        let mut path = vec![];
+        let saved_stack = stack
+            .layers()
+            .iter()
+            .map(|layer| {
+                layer
+                    .iter()
+                    .map(|o| {
+                        path.push(match get_lang() {
+                            SupportedLanguage::Turkish => "<küme>".to_string(),
+                            SupportedLanguage::English => "<stack>".to_string(),
+                        });
+                        let saved = saver.save(o, &mut path);
+                        path.pop();
+                        saved
+                    })
+                    .collect()
+            })
+            .collect();
+        let (names, global, scopes) = hashs.parts();
+        let mut save_var = |slot: usize, o: &Object| {
+            path.push(names[slot].clone());
+            let saved = saver.save(o, &mut path);
+            path.pop();
+            saved
+        };
+        let saved_global = global
+            .iter()
+            .enumerate()
+            .map(|(slot, o)| o.as_ref().map(|o| save_var(slot, o)))
+            .collect();
+        let saved_scopes = scopes
+            .iter()
+            .map(|scope| scope.iter().map(|(slot, o)| (*slot, save_var(*slot, o))).collect())
+            .collect();
+
+        if !saver.unsaved.is_empty() {
+            let count = saver.unsaved.len();
+            return Err(snapshot_error(
+                &format!("{} değer kaydedilemez", count),
+                &match count {
+                    1 => "1 value can't be saved".to_string(),
+                    _ => format!("{} values can't be saved", count),
+                },
+                Some(saver.unsaved.join("\n")),
+            ));
+        }
+        Ok(Snapshot {
+            program: to_bytecode(program),
+            current: self.current,
+            frames: self.frames.clone(),
+            namespaces: self.namespaces.clone(),
+            stack: saved_stack,
+            names: names.to_vec(),
+            global: saved_global,
+            scopes: saved_scopes,
+            modules: saver.modules.into_iter().map(|(m, _)| m).collect(),
+        })
+    }
+
+    /// Picks up a run saved with [`Run::snapshot`], along with its memories
+    ///
+    /// The built-in modules the program loaded have to be allowed by `permissions`, which should
+    /// be those the run carries on with. The run carries on from where it was with
+    /// [`Run::resume`].
+    pub fn restore(
+        snapshot: Snapshot,
+        permissions: Permissions,
+    ) -> Result<(Run, StackMemory, HashMemory), Error> {
+        let program = from_bytecode(&snapshot.program, "<kayıt>")?;
+        let len = program.code.len();
+        let names = snapshot.names.len();
+        if snapshot.current > len
+            || snapshot.global.len() > names
+            || snapshot.scopes.iter().flatten().any(|(slot, _)| *slot >= names)
+            || snapshot.namespaces.iter().any(|slot| *slot >= names)
+            || snapshot.frames.iter().any(|f| f.ret > len)
+            || !nested(&snapshot, &program)
+        {
+            return Err(corrupt());
+        }
+
+        let mut modules = HashMap::new();
+        for name in snapshot.modules {
+            let module = stdlib::module(&name).ok_or_else(corrupt)?;
+            if module.permission.is_some_and(|p| !permissions.allows(p)) {
+                return Err(match get_lang() {
+                    SupportedLanguage::Turkish => Error::new(
+                        "İzinHatası",
+                        &format!("`{}` modülünü yükleme izni yok", name),
+                        vec![],
+                        None,
+                    ),
+                    SupportedLanguage::English => Error::new(
+                        "İzinHatası",
+                        &format!("no permission to load the module `{}`", name),
+                        vec![],
+                        None,
+                    ),
+                });
+            }
+            let (mut s, mut h) = (StackMemory::new(), HashMemory::new());
+            (module.init)(&mut s, &mut h);
+            modules.insert(name, h);
+        }
+        let mut restorer = Restorer { program: &program, modules };
+        let stack = snapshot
+            .stack
+            .into_iter()
+            .map(|layer| layer.into_iter().map(|s| restorer.restore(s)).collect())
+            .collect::<Result<_, _>>()?;
+        let global = snapshot
+            .global
+            .into_iter()
+            .map(|s| s.map(|s| restorer.restore(s)).transpose())
+            .collect::<Result<_, _>>()?;
+        let scopes = snapshot
+            .scopes
+            .into_iter()
+            .map(|scope| scope.into_iter().map(|(slot, s)| Ok((slot, restorer.restore(s)?))).collect())
+            .collect::<Result<_, Error>>()?;
+
+        let mut run = Run::new(Arc::new(program));
+        run.current = snapshot.current;
+        run.frames = snapshot.frames;
+        run.namespaces = snapshot.namespaces;
+        Ok((run, StackMemory::from_layers(stack), HashMemory::from_parts(snapshot.names, global, scopes)))
+    }
+}
@@ -0,0 +1,136 @@
+//! `baytlar` module, works on `Baytlar`
+//!
+//! ```text
+//! yükle "baytlar" -> baytlar
+//! b"\x00\xffabc" 1 baytlar:al de
+//! "ğüş" "windows-1254" baytlar:kodla -> b
+//! b "windows-1254" baytlar:çöz de
+//! ```
+//!
+//! Indices start from 0 and negative indices count from the end. Encodings are named by their
+//! WHATWG labels like `utf-8`, `windows-1254` or `iso-8859-9`; `utf-16le` and `utf-16be` can
+//! only be decoded.
+
+use encoding_rs::Encoding;
+
+use crate::error::Error;
+use crate::make_function;
+use crate::mem::{HashMemory, List, Object, StackMemory};
+use crate::util::{get_lang, SupportedLanguage};
+
+use super::{encoding_error, pop, pop_yazı, unexpected_type};
+
+pub const NAME: &str = "baytlar";
+
+pub fn init(_stack: &mut StackMemory, hashs: &mut HashMemory) {
+    hashs.insert("uzunluk".to_string(), make_function!(uzunluk));
+    hashs.insert("al".to_string(), make_function!(al));
+    hashs.insert("dilim".to_string(), make_function!(dilim));
+    hashs.insert("liste".to_string(), make_function!(liste));
+    hashs.insert("kodla".to_string(), make_function!(kodla));
+    hashs.insert("çöz".to_string(), make_function!(çöz));
+}
+
+fn pop_baytlar(stack: &mut StackMemory, fname: &str) -> Result<Vec<u8>, Error> {
+    match pop(stack, fname)? {
+        Object::Baytlar(b) => Ok(b),
+        b => Err(unexpected_type("baytlar", "bytes", &b)),
+    }
+}
+
+/// Pops an index into `len` bytes, `end` allows the index right after the last byte
+fn pop_index(stack: &mut StackMemory, fname: &str, len: usize, end: bool) -> Result<usize, Error> {
+    let n = match pop(stack, fname)? {
+        Object::Sayı(n) if n.fract() == 0. => n,
+        b => return Err(unexpected_type("tam sayı", "integer", &b)),
+    };
+    let i = if n < 0. { len as f64 + n } else { n };
+    if i < 0. || i > len as f64 || (!end && i == len as f64) {
+        return Err(match get_lang() {
+            SupportedLanguage::Turkish => Error::new(
+                "SıraHatası",
+                &format!("{} uzunluğundaki baytlarda `{}` sırası bulunmuyor", len, n),
+                vec![],
+                None,
+            ),
+            SupportedLanguage::English => Error::new(
+                "IndexError",
+                &format!("index `{}` is out of range for bytes of length {}", n, len),
+                vec![],
+                None,
+            ),
+        });
+    }
+    Ok(i as usize)
+}
+
+fn encoding(label: &str) -> Result<&'static Encoding, Error> {
+    Encoding::for_label(label.as_bytes()).ok_or_else(|| match get_lang() {
+        SupportedLanguage::Turkish => encoding_error(format!("bilinmeyen kodlama: `{}`", label)),
+        SupportedLanguage::English => encoding_error(format!("unknown encoding: `{}`", label)),
+    })
+}
+
+/// `<baytlar> uzunluk -> <sayı>`
+fn uzunluk(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let b = pop_baytlar(stack, "uzunluk")?;
+    Ok(Some(Object::Sayı(b.len() as f64)))
+}
+
+/// `<baytlar> <sıra> al -> <sayı>`
+fn al(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let i = pop(stack, "al")?;
+    let b = pop_baytlar(stack, "al")?;
+    stack.push(i);
+    let i = pop_index(stack, "al", b.len(), false)?;
+    Ok(Some(Object::Sayı(b[i] as f64)))
+}
+
+/// `<baytlar> <baş> <son> dilim -> <baytlar>`, `son` is not included
+fn dilim(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let end = pop(stack, "dilim")?;
+    let start = pop(stack, "dilim")?;
+    let b = pop_baytlar(stack, "dilim")?;
+    stack.push(end);
+    let end = pop_index(stack, "dilim", b.len(), true)?;
+    stack.push(start);
+    let start = pop_index(stack, "dilim", b.len(), true)?.min(end);
+    Ok(Some(Object::Baytlar(b[start..end].to_vec())))
+}
+
+/// `<baytlar> liste -> <liste>`, a list of the bytes as numbers
+fn liste(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let b = pop_baytlar(stack, "liste")?;
+    Ok(Some(Object::Liste(List::new(
+        b.into_iter().map(|c| Object::Sayı(c as f64)).collect(),
+    ))))
+}
+
+/// `<yazı> <kodlama> kodla -> <baytlar>`
+fn kodla(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let label = pop_yazı(stack, "kodla")?;
+    let s = pop_yazı(stack, "kodla")?;
+    let enc = encoding(&label)?;
+    let (b, used, had_errors) = enc.encode(&s);
+    if used != enc || had_errors {
+        return Err(match get_lang() {
+            SupportedLanguage::Turkish => encoding_error(format!("yazı `{}` kodlamasıyla kodlanamıyor", label)),
+            SupportedLanguage::English => encoding_error(format!("the string can't be encoded with `{}`", label)),
+        });
+    }
+    Ok(Some(Object::Baytlar(b.into_owned())))
+}
+
+/// `<baytlar> <kodlama> çöz -> <yazı>`
+fn çöz(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let label = pop_yazı(stack, "çöz")?;
+    let b = pop_baytlar(stack, "çöz")?;
+    let enc = encoding(&label)?;
+    match enc.decode_without_bom_handling_and_without_replacement(&b) {
+        Some(s) => Ok(Some(Object::Yazı(s.into_owned()))),
+        None => Err(match get_lang() {
+            SupportedLanguage::Turkish => encoding_error(format!("baytlar geçerli bir `{}` yazısı değil", label)),
+            SupportedLanguage::English => encoding_error(format!("the bytes are not valid `{}`", label)),
+        }),
+    }
+}
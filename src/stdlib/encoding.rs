@@ -0,0 +1,90 @@
+//! `kodlama` module, base64, hexadecimal and url encodings
+//!
+//! ```text
+//! yükle "kodlama" -> kodlama
+//! "merhaba" kodlama:base64-kodla de
+//! ```
+//!
+//! Every encoding has a `-kodla` function which encodes its argument and a `-çöz` function which
+//! decodes it back, decoding fails with `KodlamaHatası` on malformed input. `-kodla` takes either
+//! a `Yazı` or `Baytlar` and `-bayt-çöz` decodes binary data into `Baytlar`.
+
+use base64::Engine;
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+use crate::error::Error;
+use crate::make_function;
+use crate::mem::{HashMemory, Object, StackMemory};
+use super::{encoding_error, pop_bytes, pop_yazı};
+
+pub const NAME: &str = "kodlama";
+
+/// Characters left as they are by `url-kodla`, the unreserved characters of RFC 3986
+const URL_SET: &AsciiSet = &NON_ALPHANUMERIC.remove(b'-').remove(b'_').remove(b'.').remove(b'~');
+
+pub fn init(_stack: &mut StackMemory, hashs: &mut HashMemory) {
+    hashs.insert("base64-kodla".to_string(), make_function!(base64_kodla));
+    hashs.insert("base64-çöz".to_string(), make_function!(base64_çöz));
+    hashs.insert("base64-bayt-çöz".to_string(), make_function!(base64_bayt_çöz));
+    hashs.insert("hex-kodla".to_string(), make_function!(hex_kodla));
+    hashs.insert("hex-çöz".to_string(), make_function!(hex_çöz));
+    hashs.insert("hex-bayt-çöz".to_string(), make_function!(hex_bayt_çöz));
+    hashs.insert("url-kodla".to_string(), make_function!(url_kodla));
+    hashs.insert("url-çöz".to_string(), make_function!(url_çöz));
+}
+
+fn decoded(data: Vec<u8>) -> Result<Option<Object>, Error> {
+    match String::from_utf8(data) {
+        Ok(s) => Ok(Some(Object::Yazı(s))),
+        Err(e) => Err(encoding_error(e)),
+    }
+}
+
+/// `<veri> base64-kodla -> <yazı>`
+fn base64_kodla(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let data = pop_bytes(stack, "base64-kodla")?;
+    Ok(Some(Object::Yazı(base64::engine::general_purpose::STANDARD.encode(data))))
+}
+
+/// `<yazı> base64-çöz -> <veri>`
+fn base64_çöz(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let s = pop_yazı(stack, "base64-çöz")?;
+    decoded(base64::engine::general_purpose::STANDARD.decode(s.trim()).map_err(encoding_error)?)
+}
+
+/// `<yazı> base64-bayt-çöz -> <baytlar>`
+fn base64_bayt_çöz(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let s = pop_yazı(stack, "base64-bayt-çöz")?;
+    let b = base64::engine::general_purpose::STANDARD.decode(s.trim()).map_err(encoding_error)?;
+    Ok(Some(Object::Baytlar(b)))
+}
+
+/// `<veri> hex-kodla -> <yazı>`
+fn hex_kodla(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let data = pop_bytes(stack, "hex-kodla")?;
+    Ok(Some(Object::Yazı(hex::encode(data))))
+}
+
+/// `<yazı> hex-çöz -> <veri>`
+fn hex_çöz(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let s = pop_yazı(stack, "hex-çöz")?;
+    decoded(hex::decode(s.trim()).map_err(encoding_error)?)
+}
+
+/// `<yazı> hex-bayt-çöz -> <baytlar>`
+fn hex_bayt_çöz(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let s = pop_yazı(stack, "hex-bayt-çöz")?;
+    Ok(Some(Object::Baytlar(hex::decode(s.trim()).map_err(encoding_error)?)))
+}
+
+/// `<yazı> url-kodla -> <yazı>`
+fn url_kodla(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let s = pop_yazı(stack, "url-kodla")?;
+    Ok(Some(Object::Yazı(utf8_percent_encode(&s, URL_SET).to_string())))
+}
+
+/// `<yazı> url-çöz -> <yazı>`
+fn url_çöz(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let s = pop_yazı(stack, "url-çöz")?;
+    decoded(percent_decode_str(&s).collect())
+}
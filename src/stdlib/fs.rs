@@ -0,0 +1,66 @@
+//! `dosya` module, reads and writes files
+//!
+//! ```text
+//! yükle "dosya" -> dosya
+//! "not.txt" "merhaba\n" dosya:yaz
+//! "not.txt" dosya:oku de
+//! "resim.png" dosya:bayt-oku -> resim
+//! ```
+//!
+//! `yaz` and `ekle` take either a `Yazı` or `Baytlar`. `oku` requires the file to be valid UTF-8
+//! while `bayt-oku` returns its content as `Baytlar`.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+
+use crate::error::Error;
+use crate::make_function;
+use crate::mem::{HashMemory, Object, StackMemory};
+
+use super::{io_error, pop_bytes, pop_yazı};
+
+pub const NAME: &str = "dosya";
+
+pub fn init(_stack: &mut StackMemory, hashs: &mut HashMemory) {
+    hashs.insert("oku".to_string(), make_function!(oku));
+    hashs.insert("bayt-oku".to_string(), make_function!(bayt_oku));
+    hashs.insert("yaz".to_string(), make_function!(yaz));
+    hashs.insert("ekle".to_string(), make_function!(ekle));
+}
+
+fn fs_error(e: std::io::Error) -> Error {
+    io_error("DosyaHatası", "FSError", e)
+}
+
+/// `<yol> oku -> <yazı>`
+fn oku(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let path = pop_yazı(stack, "oku")?;
+    Ok(Some(Object::Yazı(std::fs::read_to_string(path).map_err(fs_error)?)))
+}
+
+/// `<yol> bayt-oku -> <baytlar>`
+fn bayt_oku(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let path = pop_yazı(stack, "bayt-oku")?;
+    Ok(Some(Object::Baytlar(std::fs::read(path).map_err(fs_error)?)))
+}
+
+/// `<yol> <veri> yaz`, replaces the content of the file
+fn yaz(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let data = pop_bytes(stack, "yaz")?;
+    let path = pop_yazı(stack, "yaz")?;
+    std::fs::write(path, data).map_err(fs_error)?;
+    Ok(None)
+}
+
+/// `<yol> <veri> ekle`, appends to the end of the file
+fn ekle(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let data = pop_bytes(stack, "ekle")?;
+    let path = pop_yazı(stack, "ekle")?;
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut f| f.write_all(&data))
+        .map_err(fs_error)?;
+    Ok(None)
+}
@@ -0,0 +1,53 @@
+//! `şifreleme` module, message digests and checksums
+//!
+//! ```text
+//! yükle "şifreleme" -> şifreleme
+//! "merhaba" şifreleme:sha256 de
+//! ```
+//!
+//! Every function takes the data to be hashed and returns its digest as lowercase hexadecimal.
+
+use md5::Md5;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+
+use crate::error::Error;
+use crate::make_function;
+use crate::mem::{HashMemory, Object, StackMemory};
+
+use super::pop_bytes;
+
+pub const NAME: &str = "şifreleme";
+
+pub fn init(_stack: &mut StackMemory, hashs: &mut HashMemory) {
+    hashs.insert("sha256".to_string(), make_function!(sha256));
+    hashs.insert("sha1".to_string(), make_function!(sha1));
+    hashs.insert("md5".to_string(), make_function!(md5));
+    hashs.insert("crc32".to_string(), make_function!(crc32));
+}
+
+fn digest<D: Digest>(stack: &mut StackMemory, fname: &str) -> Result<Option<Object>, Error> {
+    let data = pop_bytes(stack, fname)?;
+    Ok(Some(Object::Yazı(hex::encode(D::digest(&data)))))
+}
+
+/// `<veri> sha256 -> <özet>`
+fn sha256(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    digest::<Sha256>(stack, "sha256")
+}
+
+/// `<veri> sha1 -> <özet>`
+fn sha1(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    digest::<Sha1>(stack, "sha1")
+}
+
+/// `<veri> md5 -> <özet>`
+fn md5(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    digest::<Md5>(stack, "md5")
+}
+
+/// `<veri> crc32 -> <sağlama>`
+fn crc32(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let data = pop_bytes(stack, "crc32")?;
+    Ok(Some(Object::Yazı(format!("{:08x}", crc32fast::hash(&data)))))
+}
@@ -0,0 +1,504 @@
+//! `http` module, a minimal HTTP/1.1 client and server
+//!
+//! ```text
+//! yükle "http" -> http
+//! "http://127.0.0.1:8080/selam?ad=dünya" http:al -> yanıt
+//! yanıt:gövde de
+//!
+//! işlev işleyici -> istek
+//!     "merhaba " istek:sorgu:ad + ver
+//! son
+//! "127.0.0.1:8080" http:sunucu "işleyici" http:sun
+//! ```
+//!
+//! Requests and responses are `Harita`s. A request has the keys `yöntem`, `yol`, `sorgu`,
+//! `başlıklar` and `gövde` while a response has `durum`, `başlıklar` and `gövde`; header names are
+//! lowercased and bodies are `Yazı`.
+//! A server is given the name of a handler function which is called with each request and returns
+//! either a response or just its body. Only plain `http://` urls are supported and every
+//! connection is closed after a single response.
+//!
+//! Lines are limited to 8 KiB, messages to 100 headers and bodies to 16 MiB. `sun` answers
+//! requests it can't read with `400` and requests its handler fails on with `500`, writing the
+//! error to the standard error, and goes on to the next request until its handler closes the
+//! server with `kapat`. Connections that send nothing for 30 seconds are dropped, on either side.
+//! Start lines and headers can't contain line breaks.
+
+use std::cell::RefCell;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::rc::Rc;
+use std::time::Duration;
+
+use percent_encoding::percent_decode_str;
+
+use crate::error::Error;
+use crate::ffi::FfiObject;
+use crate::make_function;
+use crate::make_object;
+use crate::mem::{HashMemory, Map, Object, StackMemory};
+use crate::runtime;
+use crate::util::{get_lang, SupportedLanguage};
+
+use super::{io_error, pop, pop_object, pop_yazı, unexpected_type};
+
+pub const NAME: &str = "http";
+
+/// The longest a start or header line can be
+const MAX_LINE: usize = 8 * 1024;
+/// The most headers a message can have
+const MAX_HEADERS: usize = 100;
+/// The largest a body can be
+const MAX_BODY: usize = 16 * 1024 * 1024;
+/// How long a server or a client waits for the other side to send or receive anything
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+pub fn init(_stack: &mut StackMemory, hashs: &mut HashMemory) {
+    hashs.insert("al".to_string(), make_function!(al));
+    hashs.insert("gönder".to_string(), make_function!(gönder));
+    hashs.insert("istek".to_string(), make_function!(istek));
+    hashs.insert("sunucu".to_string(), make_function!(sunucu));
+    hashs.insert("adres".to_string(), make_function!(adres));
+    hashs.insert("karşıla".to_string(), make_function!(karşıla));
+    hashs.insert("sun".to_string(), make_function!(sun));
+    hashs.insert("kapat".to_string(), make_function!(kapat));
+}
+
+#[derive(Clone)]
+pub struct Server {
+    addr: String,
+    inner: Rc<RefCell<Option<TcpListener>>>,
+}
+
+impl FfiObject for Server {
+    fn repr(&self) -> String {
+        format!("<http-sunucu: {}>", self.addr)
+    }
+    fn destroy(&mut self) {
+        self.inner.borrow_mut().take();
+    }
+}
+
+fn http_error(tr: &str, en: &str) -> Error {
+    match get_lang() {
+        SupportedLanguage::Turkish => Error::new("HttpHatası", tr, vec![], None),
+        SupportedLanguage::English => Error::new("HttpError", en, vec![], None),
+    }
+}
+
+fn net_error(e: io::Error) -> Error {
+    io_error("HttpHatası", "HttpError", e)
+}
+
+fn malformed() -> Error {
+    http_error("bozuk http mesajı", "malformed http message")
+}
+
+fn too_large() -> Error {
+    http_error(
+        &format!("http mesajı çok büyük, gövdeler en fazla {} bayt olabilir", MAX_BODY),
+        &format!("http message too large, bodies can be at most {} bytes", MAX_BODY),
+    )
+}
+
+fn pop_server(stack: &mut StackMemory, fname: &str) -> Result<Server, Error> {
+    pop_object(stack, fname, "http sunucusu", "http server")
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        204 => "No Content",
+        301 => "Moved Permanently",
+        302 => "Found",
+        304 => "Not Modified",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        500 => "Internal Server Error",
+        _ => "",
+    }
+}
+
+fn decode(s: &str) -> String {
+    percent_decode_str(&s.replace('+', " ")).decode_utf8_lossy().to_string()
+}
+
+fn parse_query(query: &str) -> Map {
+    let mut map = Map::new();
+    for pair in query.split('&').filter(|p| !p.is_empty()) {
+        let (k, v) = pair.split_once('=').unwrap_or((pair, ""));
+        map.insert(decode(k), Object::Yazı(decode(v)));
+    }
+    map
+}
+
+fn header_value(o: &Object) -> Result<String, Error> {
+    match o {
+        Object::Yazı(_) | Object::Sayı(_) | Object::Bool(_) => Ok(format!("{:?}", o)),
+        b => Err(unexpected_type("yazı", "string", b)),
+    }
+}
+
+/// `line` if it has no line breaks, which would let it end the line it is written on early
+fn single_line(line: String) -> Result<String, Error> {
+    if line.contains(['\r', '\n']) {
+        return Err(http_error(
+            &format!("başlangıç satırı ve başlıklar satır sonu içeremez: {:?}", line),
+            &format!("start lines and headers can't contain line breaks: {:?}", line),
+        ));
+    }
+    Ok(line)
+}
+
+/// Reads a line of at most [`MAX_LINE`] bytes to `line`, returning how many bytes were read
+fn read_line<R: BufRead>(r: &mut R, line: &mut String) -> Result<usize, Error> {
+    let n = r.by_ref().take(MAX_LINE as u64).read_line(line).map_err(net_error)?;
+    if n == MAX_LINE && !line.ends_with('\n') {
+        return Err(malformed());
+    }
+    Ok(n)
+}
+
+/// Reads exactly `len` bytes to the end of `body`, which can't grow past [`MAX_BODY`]
+fn read_exact<R: BufRead>(r: &mut R, body: &mut Vec<u8>, len: usize) -> Result<(), Error> {
+    if body.len().checked_add(len).is_none_or(|n| n > MAX_BODY) {
+        return Err(too_large());
+    }
+    if r.by_ref().take(len as u64).read_to_end(body).map_err(net_error)? < len {
+        return Err(malformed());
+    }
+    Ok(())
+}
+
+/// Reads the start line and the headers of a message
+fn read_head<R: BufRead>(r: &mut R) -> Result<Option<(String, Map)>, Error> {
+    let mut start = String::new();
+    if read_line(r, &mut start)? == 0 {
+        return Ok(None);
+    }
+    let mut headers = Map::new();
+    loop {
+        let mut line = String::new();
+        if read_line(r, &mut line)? == 0 {
+            return Err(malformed());
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if headers.len() >= MAX_HEADERS {
+            return Err(malformed());
+        }
+        let (k, v) = line.split_once(':').ok_or_else(malformed)?;
+        headers.insert(k.trim().to_lowercase(), Object::Yazı(v.trim().to_string()));
+    }
+    Ok(Some((start.trim_end().to_string(), headers)))
+}
+
+/// Reads the body of a message, a body without a length is read until the connection is closed
+/// when `until_eof` is set and is empty otherwise
+fn read_body<R: BufRead>(r: &mut R, headers: &Map, until_eof: bool) -> Result<String, Error> {
+    let header = |k: &str| match headers.get(k) {
+        Some(Object::Yazı(v)) => Some(v.to_lowercase()),
+        _ => None,
+    };
+    let mut body = vec![];
+    if header("transfer-encoding").is_some_and(|v| v.contains("chunked")) {
+        loop {
+            let mut size = String::new();
+            read_line(r, &mut size)?;
+            let size = size.trim().split(';').next().unwrap_or("");
+            let size = usize::from_str_radix(size, 16).map_err(|_| malformed())?;
+            read_exact(r, &mut body, size)?;
+            let mut end = String::new();
+            read_line(r, &mut end)?;
+            if size == 0 {
+                break;
+            }
+        }
+    } else if let Some(len) = header("content-length") {
+        read_exact(r, &mut body, len.parse().map_err(|_| malformed())?)?;
+    } else if until_eof {
+        r.by_ref().take(MAX_BODY as u64 + 1).read_to_end(&mut body).map_err(net_error)?;
+        if body.len() > MAX_BODY {
+            return Err(too_large());
+        }
+    }
+    Ok(String::from_utf8_lossy(&body).to_string())
+}
+
+/// Writes the headers and the body of a message after its start line
+fn write_message<W: Write>(w: &mut W, start: &str, headers: &Map, body: &str) -> Result<(), Error> {
+    let mut msg = format!("{}\r\n", single_line(start.to_string())?);
+    for (k, v) in headers.iter() {
+        if !matches!(k.to_lowercase().as_str(), "content-length" | "connection") {
+            msg.push_str(&format!("{}: {}\r\n", single_line(k.clone())?, single_line(header_value(v)?)?));
+        }
+    }
+    msg.push_str(&format!("Content-Length: {}\r\nConnection: close\r\n\r\n{}", body.len(), body));
+    w.write_all(msg.as_bytes()).and_then(|_| w.flush()).map_err(net_error)
+}
+
+fn request(method: &str, url: &str, headers: Map, body: &str) -> Result<Object, Error> {
+    let rest = match url.strip_prefix("http://") {
+        Some(rest) => rest,
+        None => return Err(http_error(
+            &format!("desteklenmeyen adres: `{}`, yalnızca `http://` adresleri desteklenir", url),
+            &format!("unsupported url: `{}`, only `http://` urls are supported", url),
+        )),
+    };
+    let (host, path) = match rest.find(['/', '?']) {
+        Some(i) => (&rest[..i], rest[i..].to_string()),
+        None => (rest, "/".to_string()),
+    };
+    let path = if path.starts_with('?') { format!("/{}", path) } else { path };
+    let addr = if host.contains(':') { host.to_string() } else { format!("{}:80", host) };
+
+    let mut stream = TcpStream::connect(&addr).map_err(net_error)?;
+    let set = stream.set_read_timeout(Some(TIMEOUT));
+    set.and_then(|_| stream.set_write_timeout(Some(TIMEOUT))).map_err(net_error)?;
+    let mut headers = headers;
+    if !headers.keys().any(|k| k.to_lowercase() == "host") {
+        headers.insert("Host".to_string(), Object::Yazı(host.to_string()));
+    }
+    write_message(&mut stream, &format!("{} {} HTTP/1.1", method, path), &headers, body)?;
+
+    let mut reader = BufReader::new(stream);
+    let (status, headers) = read_head(&mut reader)?.ok_or_else(malformed)?;
+    let status: f64 = status
+        .split_whitespace()
+        .nth(1)
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(malformed)?;
+    let body = read_body(&mut reader, &headers, true)?;
+
+    let mut res = Map::new();
+    res.insert("durum".to_string(), Object::Sayı(status));
+    res.insert("başlıklar".to_string(), Object::Harita(headers));
+    res.insert("gövde".to_string(), Object::Yazı(body));
+    Ok(Object::Harita(res))
+}
+
+/// Looks up the handler called `name`, which can be inside a namespace like `api:işleyici`
+fn handler(hashs: &mut HashMemory, name: &str) -> Result<Object, Error> {
+    let unknown = || match get_lang() {
+        SupportedLanguage::Turkish => Error::new(
+            "BilinmeyenTanımlayıcı",
+            &format!("bilinmeyen değişken: `{}`, bu değişken bulunamamıştır", name),
+            vec![],
+            None,
+        ),
+        SupportedLanguage::English => Error::new(
+            "BilinmeyenTanımlayıcı",
+            &format!("unknown identifier: `{}`, this identifier could not be found", name),
+            vec![],
+            None,
+        ),
+    };
+    let mut path = name.split(':');
+    let mut o = hashs.get(&path.next().unwrap().to_string()).cloned().ok_or_else(unknown)?;
+    for p in path {
+        o = match o {
+            Object::Harita(m) => m.get(p).cloned().ok_or_else(unknown)?,
+            _ => return Err(unknown()),
+        };
+    }
+    Ok(o)
+}
+
+/// Reads a request to be given to a handler, `None` if the client sent nothing
+fn read_request<R: BufRead>(r: &mut R) -> Result<Option<Object>, Error> {
+    let (start, headers) = match read_head(r)? {
+        Some(head) => head,
+        None => return Ok(None),
+    };
+    let mut parts = start.split_whitespace();
+    let (method, target) = match (parts.next(), parts.next()) {
+        (Some(m), Some(t)) => (m.to_string(), t.to_string()),
+        _ => return Err(malformed()),
+    };
+    let (path, query) = target.split_once('?').unwrap_or((&target, ""));
+    let body = read_body(r, &headers, false)?;
+
+    let mut req = Map::new();
+    req.insert("yöntem".to_string(), Object::Yazı(method));
+    req.insert("yol".to_string(), Object::Yazı(decode(path)));
+    req.insert("sorgu".to_string(), Object::Harita(parse_query(query)));
+    req.insert("başlıklar".to_string(), Object::Harita(headers));
+    req.insert("gövde".to_string(), Object::Yazı(body));
+    Ok(Some(Object::Harita(req)))
+}
+
+/// The status, headers and body of the response a handler left on `stack`
+fn pop_response(stack: &mut StackMemory) -> Result<(u16, Map, String), Error> {
+    match pop(stack, "sun")? {
+        Object::Yazı(body) => Ok((200, Map::new(), body)),
+        Object::Harita(mut res) => {
+            let status = match res.remove("durum") {
+                Some(Object::Sayı(n)) => n as u16,
+                None => 200,
+                Some(b) => return Err(unexpected_type("sayı", "number", &b)),
+            };
+            let headers = match res.remove("başlıklar") {
+                Some(Object::Harita(h)) => {
+                    // checked here as well so that the client is told the handler failed
+                    for (k, v) in h.iter() {
+                        single_line(k.clone())?;
+                        single_line(header_value(v)?)?;
+                    }
+                    h
+                }
+                None => Map::new(),
+                Some(b) => return Err(unexpected_type("harita", "map", &b)),
+            };
+            let body = match res.remove("gövde") {
+                Some(Object::Yazı(s)) => s,
+                None | Some(Object::Hiç) => String::new(),
+                Some(b) => return Err(unexpected_type("yazı", "string", &b)),
+            };
+            Ok((status, headers, body))
+        }
+        b => Err(unexpected_type("yazı veya harita", "string or map", &b)),
+    }
+}
+
+fn write_response<W: Write>(w: &mut W, status: u16, mut headers: Map, body: &str) -> Result<(), Error> {
+    if !headers.keys().any(|k| k.to_lowercase() == "content-type") {
+        headers.insert(
+            "Content-Type".to_string(),
+            Object::Yazı("text/plain; charset=utf-8".to_string()),
+        );
+    }
+    write_message(w, &format!("HTTP/1.1 {} {}", status, reason(status)), &headers, body)
+}
+
+/// Accepts a single connection and answers its request with `handler`
+///
+/// When `keep_going` is set, requests that can't be read or answered only fail their own
+/// connection, unless the run is out of instructions or time or was cancelled.
+fn serve_one(
+    server: &Server,
+    handler: &Object,
+    keep_going: bool,
+    stack: &mut StackMemory,
+    hashs: &mut HashMemory,
+) -> Result<(), Error> {
+    let stream = {
+        let inner = server.inner.borrow();
+        let listener = inner.as_ref().ok_or_else(|| http_error("sunucu kapatılmış", "the server has been closed"))?;
+        listener.accept().map_err(net_error)?.0
+    };
+    let mut reader = BufReader::new(stream);
+    let set = reader.get_ref().set_read_timeout(Some(TIMEOUT));
+    set.and_then(|_| reader.get_ref().set_write_timeout(Some(TIMEOUT))).map_err(net_error)?;
+
+    let req = match read_request(&mut reader) {
+        Ok(Some(req)) => req,
+        Ok(None) => return Ok(()),
+        Err(e) => {
+            let _ = write_response(reader.get_mut(), 400, Map::new(), "");
+            return if keep_going { Ok(()) } else { Err(e) };
+        }
+    };
+    // what the handler leaves under its response is left alone
+    let depth = stack.len();
+    stack.push(req);
+    let res = runtime::call(handler, stack, hashs).and_then(|()| pop_response(stack));
+    let (status, headers, body) = match res {
+        Ok(res) => res,
+        Err(e) => {
+            let _ = write_response(reader.get_mut(), 500, Map::new(), "");
+            if !keep_going || runtime::limits_reached() {
+                return Err(e);
+            }
+            runtime::report(&e);
+            while stack.len() > depth {
+                stack.pop();
+            }
+            return Ok(());
+        }
+    };
+    match write_response(reader.get_mut(), status, headers, &body) {
+        Err(e) if !keep_going => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// `<adres> al -> <yanıt>`, sends a `GET` request
+fn al(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let url = pop_yazı(stack, "al")?;
+    Ok(Some(request("GET", &url, Map::new(), "")?))
+}
+
+/// `<adres> <gövde> gönder -> <yanıt>`, sends a `POST` request
+fn gönder(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let body = pop_yazı(stack, "gönder")?;
+    let url = pop_yazı(stack, "gönder")?;
+    Ok(Some(request("POST", &url, Map::new(), &body)?))
+}
+
+/// `<yöntem> <adres> <başlıklar | hiç> <gövde | hiç> istek -> <yanıt>`
+fn istek(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let body = match pop(stack, "istek")? {
+        Object::Yazı(s) => s,
+        Object::Hiç => String::new(),
+        b => return Err(unexpected_type("yazı", "string", &b)),
+    };
+    let headers = match pop(stack, "istek")? {
+        Object::Harita(m) => m,
+        Object::Hiç => Map::new(),
+        b => return Err(unexpected_type("harita", "map", &b)),
+    };
+    let url = pop_yazı(stack, "istek")?;
+    let method = pop_yazı(stack, "istek")?;
+    Ok(Some(request(&method.to_uppercase(), &url, headers, &body)?))
+}
+
+/// `<adres> sunucu -> <sunucu>`
+fn sunucu(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let addr = pop_yazı(stack, "sunucu")?;
+    let listener = TcpListener::bind(&addr).map_err(net_error)?;
+    Ok(Some(make_object!(Server {
+        addr,
+        inner: Rc::new(RefCell::new(Some(listener))),
+    })))
+}
+
+/// `<sunucu> adres -> <adres>`
+fn adres(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let server = pop_server(stack, "adres")?;
+    let inner = server.inner.borrow();
+    let listener = inner.as_ref().ok_or_else(|| http_error("sunucu kapatılmış", "the server has been closed"))?;
+    Ok(Some(Object::Yazı(listener.local_addr().map_err(net_error)?.to_string())))
+}
+
+/// `<sunucu> <işleyici adı> karşıla`, answers a single request
+fn karşıla(stack: &mut StackMemory, hashs: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let name = pop_yazı(stack, "karşıla")?;
+    let server = pop_server(stack, "karşıla")?;
+    let handler = handler(hashs, &name)?;
+    serve_one(&server, &handler, false, stack, hashs)?;
+    Ok(None)
+}
+
+/// `<sunucu> <işleyici adı> sun`, answers requests until the server is closed
+fn sun(stack: &mut StackMemory, hashs: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let name = pop_yazı(stack, "sun")?;
+    let server = pop_server(stack, "sun")?;
+    let handler = handler(hashs, &name)?;
+    while server.inner.borrow().is_some() {
+        serve_one(&server, &handler, true, stack, hashs)?;
+    }
+    Ok(None)
+}
+
+/// `<sunucu> kapat`
+fn kapat(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let mut server = pop_server(stack, "kapat")?;
+    server.destroy();
+    Ok(None)
+}
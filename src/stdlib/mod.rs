@@ -0,0 +1,124 @@
+//! Built-in modules of tr-lang
+//!
+//! These are loaded the same way shared libraries are, with `yükle "<ad>" -> ad` or
+//! `yükle "<ad>"*`, but they are compiled into the interpreter instead of being looked up on disk.
+
+pub mod bytes;
+pub mod encoding;
+pub mod fs;
+pub mod hash;
+pub mod http;
+pub mod net;
+pub mod process;
+
+use crate::error::Error;
+use crate::ffi::FfiObject;
+use crate::mem::{HashMemory, Object, StackMemory};
+use crate::runtime::Permission;
+use crate::util::{get_lang, SupportedLanguage};
+
+pub struct Module {
+    pub name: &'static str,
+    pub init: fn(&mut StackMemory, &mut HashMemory),
+    /// What a program needs to be allowed to load the module
+    pub permission: Option<Permission>,
+}
+
+const MODULES: &[Module] = &[
+    Module { name: process::NAME, init: process::init, permission: Some(Permission::Process) },
+    Module { name: net::NAME, init: net::init, permission: Some(Permission::Network) },
+    Module { name: http::NAME, init: http::init, permission: Some(Permission::Network) },
+    Module { name: hash::NAME, init: hash::init, permission: None },
+    Module { name: encoding::NAME, init: encoding::init, permission: None },
+    Module { name: bytes::NAME, init: bytes::init, permission: None },
+    Module { name: fs::NAME, init: fs::init, permission: Some(Permission::File) },
+];
+
+/// Returns the built-in module called `name` if there is one
+pub fn module(name: &str) -> Option<&'static Module> {
+    MODULES.iter().find(|m| m.name == name)
+}
+
+/// Pops an argument for the built-in function `fname`
+pub(crate) fn pop(stack: &mut StackMemory, fname: &str) -> Result<Object, Error> {
+    match stack.pop() {
+        Some(a) => Ok(a),
+        None => Err(match get_lang() {
+            SupportedLanguage::Turkish => Error::new(
+                "KümedeYeterliDeğişkenYok",
+                &format!("kümede yeterli değişken bulunmadığından dolayı `{}` işlevi uygulanamamıştır", fname),
+                vec![],
+                None,
+            ),
+            SupportedLanguage::English => Error::new(
+                "KümedeYeterliDeğişkenYok",
+                &format!("because there weren't enough variables in the stack, the function `{}` couldn't be used", fname),
+                vec![],
+                None,
+            ),
+        }),
+    }
+}
+
+/// Pops a `Yazı` argument for the built-in function `fname`
+pub(crate) fn pop_yazı(stack: &mut StackMemory, fname: &str) -> Result<String, Error> {
+    match pop(stack, fname)? {
+        Object::Yazı(s) => Ok(s),
+        b => Err(unexpected_type("yazı", "string", &b)),
+    }
+}
+
+/// Pops the data argument of the built-in function `fname` as bytes, both `Yazı` and `Baytlar`
+/// are accepted
+pub(crate) fn pop_bytes(stack: &mut StackMemory, fname: &str) -> Result<Vec<u8>, Error> {
+    match pop(stack, fname)? {
+        Object::Yazı(s) => Ok(s.into_bytes()),
+        Object::Baytlar(b) => Ok(b),
+        b => Err(unexpected_type("yazı veya baytlar", "string or bytes", &b)),
+    }
+}
+
+/// Pops an `FfiObject` of type `T` for the built-in function `fname`, `tr` and `en` name the
+/// expected type in error messages
+pub(crate) fn pop_object<T: FfiObject + Clone>(stack: &mut StackMemory, fname: &str, tr: &str, en: &str) -> Result<T, Error> {
+    match pop(stack, fname)? {
+        Object::FfiObject(o) => match o.downcast_ref::<T>() {
+            Some(t) => Ok(t.clone()),
+            None => Err(unexpected_type(tr, en, &Object::FfiObject(o))),
+        },
+        b => Err(unexpected_type(tr, en, &b)),
+    }
+}
+
+pub(crate) fn unexpected_type(tr: &str, en: &str, found: &Object) -> Error {
+    match get_lang() {
+        SupportedLanguage::Turkish => Error::new(
+            "BeklenmedikTip",
+            &format!("{} beklenmişti ancak `{:?}` bulundu", tr, found),
+            vec![],
+            None,
+        ),
+        SupportedLanguage::English => Error::new(
+            "BeklenmedikTip",
+            &format!("expected {} but found `{:?}`", en, found),
+            vec![],
+            None,
+        ),
+    }
+}
+
+/// An error about malformed or unsupported encoded data
+pub(crate) fn encoding_error(e: impl std::fmt::Display) -> Error {
+    match get_lang() {
+        SupportedLanguage::Turkish => Error::new("KodlamaHatası", &format!("{}", e), vec![], None),
+        SupportedLanguage::English => Error::new("EncodingError", &format!("{}", e), vec![], None),
+    }
+}
+
+/// Turns an `io::Error` coming from a built-in function into a tr-lang error
+pub(crate) fn io_error(tr: &str, en: &str, e: std::io::Error) -> Error {
+    match get_lang() {
+        SupportedLanguage::Turkish => Error::new(tr, &format!("{}", e), vec![], None),
+        SupportedLanguage::English => Error::new(en, &format!("{}", e), vec![], None),
+    }
+}
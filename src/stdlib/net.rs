@@ -0,0 +1,334 @@
+//! `ağ` module, TCP and UDP sockets
+//!
+//! ```text
+//! yükle "ağ" -> ağ
+//! "127.0.0.1:8080" ağ:bağlan -> bağlantı
+//! bağlantı "merhaba\n" ağ:yaz
+//! bağlantı ağ:satır-oku de
+//! bağlantı ağ:kapat
+//! ```
+//!
+//! `dinle` opens a TCP listener which hands out connections with `kabul`, `bağlan` connects to
+//! one and `udp` binds a UDP socket which sends with `gönder` and receives with `al`.
+//! Connections are read with `oku` and `satır-oku` and written with `yaz`; `zaman-aşımı` sets
+//! how long, in seconds, reads and writes may block before an `AğZamanAşımı` error is raised.
+//! Data is written from either a `Yazı` or `Baytlar` and `bayt-oku` and `bayt-al` read it as
+//! `Baytlar` instead of a `Yazı`.
+
+use std::cell::RefCell;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, UdpSocket};
+use std::rc::Rc;
+use std::time::Duration;
+
+use crate::error::Error;
+use crate::ffi::FfiObject;
+use crate::make_function;
+use crate::make_object;
+use crate::mem::{HashMemory, Map, Object, StackMemory};
+use crate::util::{get_lang, SupportedLanguage};
+
+use super::{io_error, pop, pop_bytes, pop_object, pop_yazı, unexpected_type};
+
+pub const NAME: &str = "ağ";
+
+/// Size of the buffer `oku` and `al` read into
+const BUFFER_SIZE: usize = 4096;
+
+pub fn init(_stack: &mut StackMemory, hashs: &mut HashMemory) {
+    hashs.insert("dinle".to_string(), make_function!(dinle));
+    hashs.insert("kabul".to_string(), make_function!(kabul));
+    hashs.insert("bağlan".to_string(), make_function!(bağlan));
+    hashs.insert("udp".to_string(), make_function!(udp));
+    hashs.insert("oku".to_string(), make_function!(oku));
+    hashs.insert("bayt-oku".to_string(), make_function!(bayt_oku));
+    hashs.insert("satır-oku".to_string(), make_function!(satır_oku));
+    hashs.insert("yaz".to_string(), make_function!(yaz));
+    hashs.insert("gönder".to_string(), make_function!(gönder));
+    hashs.insert("al".to_string(), make_function!(al));
+    hashs.insert("bayt-al".to_string(), make_function!(bayt_al));
+    hashs.insert("adres".to_string(), make_function!(adres));
+    hashs.insert("uzak-adres".to_string(), make_function!(uzak_adres));
+    hashs.insert("zaman-aşımı".to_string(), make_function!(zaman_aşımı));
+    hashs.insert("kapat".to_string(), make_function!(kapat));
+}
+
+#[derive(Clone)]
+pub struct Listener {
+    addr: String,
+    inner: Rc<RefCell<Option<TcpListener>>>,
+}
+
+impl FfiObject for Listener {
+    fn repr(&self) -> String {
+        format!("<tcp-dinleyici: {}>", self.addr)
+    }
+    fn destroy(&mut self) {
+        self.inner.borrow_mut().take();
+    }
+}
+
+#[derive(Clone)]
+pub struct Stream {
+    addr: String,
+    inner: Rc<RefCell<Option<BufReader<TcpStream>>>>,
+}
+
+impl FfiObject for Stream {
+    fn repr(&self) -> String {
+        format!("<tcp-bağlantı: {}>", self.addr)
+    }
+    fn destroy(&mut self) {
+        if let Some(s) = self.inner.borrow_mut().take() {
+            let _ = s.get_ref().shutdown(Shutdown::Both);
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Socket {
+    addr: String,
+    inner: Rc<RefCell<Option<UdpSocket>>>,
+}
+
+impl FfiObject for Socket {
+    fn repr(&self) -> String {
+        format!("<udp-soket: {}>", self.addr)
+    }
+    fn destroy(&mut self) {
+        self.inner.borrow_mut().take();
+    }
+}
+
+fn net_error(e: io::Error) -> Error {
+    match e.kind() {
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => match get_lang() {
+            SupportedLanguage::Turkish => Error::new("AğZamanAşımı", "işlem zaman aşımına uğradı", vec![], None),
+            SupportedLanguage::English => Error::new("NetworkTimeout", "the operation timed out", vec![], None),
+        },
+        _ => io_error("AğHatası", "NetworkError", e),
+    }
+}
+
+fn closed_error() -> Error {
+    match get_lang() {
+        SupportedLanguage::Turkish => Error::new("AğHatası", "bağlantı kapatılmış", vec![], None),
+        SupportedLanguage::English => Error::new("NetworkError", "the connection has been closed", vec![], None),
+    }
+}
+
+fn addr_to_object(addr: io::Result<SocketAddr>) -> Result<Object, Error> {
+    addr.map(|a| Object::Yazı(a.to_string())).map_err(net_error)
+}
+
+fn pop_listener(stack: &mut StackMemory, fname: &str) -> Result<Listener, Error> {
+    pop_object(stack, fname, "tcp dinleyicisi", "tcp listener")
+}
+
+fn pop_stream(stack: &mut StackMemory, fname: &str) -> Result<Stream, Error> {
+    pop_object(stack, fname, "tcp bağlantısı", "tcp connection")
+}
+
+fn pop_socket(stack: &mut StackMemory, fname: &str) -> Result<Socket, Error> {
+    pop_object(stack, fname, "udp soketi", "udp socket")
+}
+
+/// `<adres> dinle -> <dinleyici>`
+fn dinle(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let addr = pop_yazı(stack, "dinle")?;
+    let listener = TcpListener::bind(&addr).map_err(net_error)?;
+    Ok(Some(make_object!(Listener {
+        addr,
+        inner: Rc::new(RefCell::new(Some(listener))),
+    })))
+}
+
+/// `<dinleyici> kabul -> <bağlantı>`, waits for the next connection
+fn kabul(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let listener = pop_listener(stack, "kabul")?;
+    let inner = listener.inner.borrow();
+    let (stream, addr) = inner.as_ref().ok_or_else(closed_error)?.accept().map_err(net_error)?;
+    Ok(Some(make_object!(Stream {
+        addr: addr.to_string(),
+        inner: Rc::new(RefCell::new(Some(BufReader::new(stream)))),
+    })))
+}
+
+/// `<adres> bağlan -> <bağlantı>`
+fn bağlan(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let addr = pop_yazı(stack, "bağlan")?;
+    let stream = TcpStream::connect(&addr).map_err(net_error)?;
+    Ok(Some(make_object!(Stream {
+        addr,
+        inner: Rc::new(RefCell::new(Some(BufReader::new(stream)))),
+    })))
+}
+
+/// `<adres> udp -> <soket>`
+fn udp(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let addr = pop_yazı(stack, "udp")?;
+    let socket = UdpSocket::bind(&addr).map_err(net_error)?;
+    Ok(Some(make_object!(Socket {
+        addr,
+        inner: Rc::new(RefCell::new(Some(socket))),
+    })))
+}
+
+fn read(stack: &mut StackMemory, fname: &str) -> Result<Vec<u8>, Error> {
+    let stream = pop_stream(stack, fname)?;
+    let mut inner = stream.inner.borrow_mut();
+    let reader = inner.as_mut().ok_or_else(closed_error)?;
+    let mut buf = [0; BUFFER_SIZE];
+    let n = reader.read(&mut buf).map_err(net_error)?;
+    Ok(buf[..n].to_vec())
+}
+
+/// `<bağlantı> oku -> <veri>`, returns whatever is available, `""` once the other side is closed
+fn oku(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let data = read(stack, "oku")?;
+    Ok(Some(Object::Yazı(String::from_utf8_lossy(&data).to_string())))
+}
+
+/// `<bağlantı> bayt-oku -> <baytlar>`, like `oku` but returns `Baytlar`
+fn bayt_oku(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    Ok(Some(Object::Baytlar(read(stack, "bayt-oku")?)))
+}
+
+/// `<bağlantı> satır-oku -> <satır>`, the line is returned without its line ending, `hiç` once
+/// the other side is closed
+fn satır_oku(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let stream = pop_stream(stack, "satır-oku")?;
+    let mut inner = stream.inner.borrow_mut();
+    let reader = inner.as_mut().ok_or_else(closed_error)?;
+    let mut buf = vec![];
+    if reader.read_until(b'\n', &mut buf).map_err(net_error)? == 0 {
+        return Ok(Some(Object::Hiç));
+    }
+    if buf.ends_with(b"\n") {
+        buf.pop();
+        if buf.ends_with(b"\r") {
+            buf.pop();
+        }
+    }
+    Ok(Some(Object::Yazı(String::from_utf8_lossy(&buf).to_string())))
+}
+
+/// `<bağlantı> <yazı | baytlar> yaz`
+fn yaz(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let data = pop_bytes(stack, "yaz")?;
+    let stream = pop_stream(stack, "yaz")?;
+    let mut inner = stream.inner.borrow_mut();
+    let writer = inner.as_mut().ok_or_else(closed_error)?.get_mut();
+    writer.write_all(&data).and_then(|_| writer.flush()).map_err(net_error)?;
+    Ok(None)
+}
+
+/// `<soket> <adres> <yazı | baytlar> gönder`
+fn gönder(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let data = pop_bytes(stack, "gönder")?;
+    let addr = pop_yazı(stack, "gönder")?;
+    let socket = pop_socket(stack, "gönder")?;
+    let inner = socket.inner.borrow();
+    inner.as_ref().ok_or_else(closed_error)?.send_to(&data, &addr).map_err(net_error)?;
+    Ok(None)
+}
+
+fn recv(stack: &mut StackMemory, fname: &str, bytes: bool) -> Result<Option<Object>, Error> {
+    let socket = pop_socket(stack, fname)?;
+    let inner = socket.inner.borrow();
+    let mut buf = [0; BUFFER_SIZE];
+    let (n, addr) = inner.as_ref().ok_or_else(closed_error)?.recv_from(&mut buf).map_err(net_error)?;
+    let mut map = Map::new();
+    map.insert(
+        "veri".to_string(),
+        if bytes {
+            Object::Baytlar(buf[..n].to_vec())
+        } else {
+            Object::Yazı(String::from_utf8_lossy(&buf[..n]).to_string())
+        },
+    );
+    map.insert("adres".to_string(), Object::Yazı(addr.to_string()));
+    Ok(Some(Object::Harita(map)))
+}
+
+/// `<soket> al -> <paket>`, the packet is a `Harita` with the keys `veri` and `adres`
+fn al(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    recv(stack, "al", false)
+}
+
+/// `<soket> bayt-al -> <paket>`, like `al` but `veri` is `Baytlar`
+fn bayt_al(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    recv(stack, "bayt-al", true)
+}
+
+/// `<dinleyici | bağlantı | soket> adres -> <adres>`, the local address
+fn adres(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let o = match pop(stack, "adres")? {
+        Object::FfiObject(o) => o,
+        b => return Err(unexpected_type("ağ nesnesi", "network object", &b)),
+    };
+    let addr = if let Some(l) = o.downcast_ref::<Listener>() {
+        addr_to_object(l.inner.borrow().as_ref().ok_or_else(closed_error)?.local_addr())?
+    } else if let Some(s) = o.downcast_ref::<Stream>() {
+        addr_to_object(s.inner.borrow().as_ref().ok_or_else(closed_error)?.get_ref().local_addr())?
+    } else if let Some(s) = o.downcast_ref::<Socket>() {
+        addr_to_object(s.inner.borrow().as_ref().ok_or_else(closed_error)?.local_addr())?
+    } else {
+        return Err(unexpected_type("ağ nesnesi", "network object", &Object::FfiObject(o)));
+    };
+    Ok(Some(addr))
+}
+
+/// `<bağlantı> uzak-adres -> <adres>`, the address of the other side
+fn uzak_adres(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let stream = pop_stream(stack, "uzak-adres")?;
+    let inner = stream.inner.borrow();
+    Ok(Some(addr_to_object(inner.as_ref().ok_or_else(closed_error)?.get_ref().peer_addr())?))
+}
+
+/// `<bağlantı | soket> <saniye | hiç> zaman-aşımı`, `hiç` waits forever
+fn zaman_aşımı(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let timeout = match pop(stack, "zaman-aşımı")? {
+        Object::Sayı(n) if n > 0. => match Duration::try_from_secs_f64(n) {
+            Ok(d) => Some(d),
+            Err(_) => return Err(unexpected_type("sonlu pozitif sayı", "finite positive number", &Object::Sayı(n))),
+        },
+        Object::Hiç => None,
+        b => return Err(unexpected_type("pozitif sayı", "positive number", &b)),
+    };
+    let o = match pop(stack, "zaman-aşımı")? {
+        Object::FfiObject(o) => o,
+        b => return Err(unexpected_type("ağ nesnesi", "network object", &b)),
+    };
+    let res = if let Some(s) = o.downcast_ref::<Stream>() {
+        let inner = s.inner.borrow();
+        let stream = inner.as_ref().ok_or_else(closed_error)?.get_ref();
+        stream.set_read_timeout(timeout).and_then(|_| stream.set_write_timeout(timeout))
+    } else if let Some(s) = o.downcast_ref::<Socket>() {
+        let inner = s.inner.borrow();
+        let socket = inner.as_ref().ok_or_else(closed_error)?;
+        socket.set_read_timeout(timeout).and_then(|_| socket.set_write_timeout(timeout))
+    } else {
+        return Err(unexpected_type("ağ nesnesi", "network object", &Object::FfiObject(o)));
+    };
+    res.map_err(net_error)?;
+    Ok(None)
+}
+
+/// `<dinleyici | bağlantı | soket> kapat`
+fn kapat(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    match pop(stack, "kapat")? {
+        Object::FfiObject(mut o) => {
+            if o.downcast_ref::<Listener>().is_some()
+                || o.downcast_ref::<Stream>().is_some()
+                || o.downcast_ref::<Socket>().is_some()
+            {
+                o.destroy();
+                Ok(None)
+            } else {
+                Err(unexpected_type("ağ nesnesi", "network object", &Object::FfiObject(o)))
+            }
+        }
+        b => Err(unexpected_type("ağ nesnesi", "network object", &b)),
+    }
+}
@@ -0,0 +1,240 @@
+//! `süreç` module, runs external programs
+//!
+//! ```text
+//! yükle "süreç" -> süreç
+//! "ls" süreç:komut "-l" süreç:argüman süreç:çalıştır -> sonuç
+//! sonuç:çıktı de
+//! ```
+//!
+//! A process is described by a `komut` which is built up with `argüman`, `ortam` and `dizin`.
+//! `çalıştır` runs it to completion while `başlat` starts it in the background so its input can
+//! be streamed with `yaz` before collecting its results with `bekle`.
+//! Results are returned as a `Harita` with the keys `çıktı`(stdout), `hata`(stderr) and
+//! `kod`(exit status, `hiç` if the process was killed by a signal).
+
+use std::cell::RefCell;
+use std::io::Write;
+use std::process::{Child, Output, Stdio};
+use std::rc::Rc;
+
+use crate::error::Error;
+use crate::ffi::FfiObject;
+use crate::make_function;
+use crate::make_object;
+use crate::mem::{HashMemory, Map, Object, StackMemory};
+use crate::util::{get_lang, SupportedLanguage};
+
+use super::{io_error, pop, pop_bytes, pop_object, pop_yazı, unexpected_type};
+
+pub const NAME: &str = "süreç";
+
+pub fn init(_stack: &mut StackMemory, hashs: &mut HashMemory) {
+    hashs.insert("komut".to_string(), make_function!(komut));
+    hashs.insert("argüman".to_string(), make_function!(argüman));
+    hashs.insert("ortam".to_string(), make_function!(ortam));
+    hashs.insert("dizin".to_string(), make_function!(dizin));
+    hashs.insert("çalıştır".to_string(), make_function!(çalıştır));
+    hashs.insert("başlat".to_string(), make_function!(başlat));
+    hashs.insert("yaz".to_string(), make_function!(yaz));
+    hashs.insert("kapat".to_string(), make_function!(kapat));
+    hashs.insert("bekle".to_string(), make_function!(bekle));
+}
+
+#[derive(Clone, Debug)]
+pub struct Command {
+    program: String,
+    args: Vec<String>,
+    env: Vec<(String, String)>,
+    dir: Option<String>,
+}
+
+impl Command {
+    fn build(&self) -> std::process::Command {
+        let mut cmd = std::process::Command::new(&self.program);
+        cmd.args(&self.args);
+        for (k, v) in self.env.iter() {
+            cmd.env(k, v);
+        }
+        if let Some(dir) = &self.dir {
+            cmd.current_dir(dir);
+        }
+        cmd
+    }
+}
+
+impl FfiObject for Command {
+    fn repr(&self) -> String {
+        format!("<komut: {}>", self.program)
+    }
+    fn destroy(&mut self) {}
+}
+
+#[derive(Clone)]
+pub struct Process {
+    program: String,
+    child: Rc<RefCell<Option<Child>>>,
+}
+
+impl FfiObject for Process {
+    fn repr(&self) -> String {
+        format!("<süreç: {}>", self.program)
+    }
+    fn destroy(&mut self) {
+        if let Some(mut child) = self.child.borrow_mut().take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+fn process_error(tr: &str, en: &str) -> Error {
+    match get_lang() {
+        SupportedLanguage::Turkish => Error::new("SüreçHatası", tr, vec![], None),
+        SupportedLanguage::English => Error::new("ProcessError", en, vec![], None),
+    }
+}
+
+fn pop_command(stack: &mut StackMemory, fname: &str) -> Result<Command, Error> {
+    pop_object(stack, fname, "komut", "command")
+}
+
+fn pop_process(stack: &mut StackMemory, fname: &str) -> Result<Process, Error> {
+    pop_object(stack, fname, "süreç", "process")
+}
+
+fn output_to_object(output: Output) -> Object {
+    let mut map = Map::new();
+    map.insert(
+        "çıktı".to_string(),
+        Object::Yazı(String::from_utf8_lossy(&output.stdout).to_string()),
+    );
+    map.insert(
+        "hata".to_string(),
+        Object::Yazı(String::from_utf8_lossy(&output.stderr).to_string()),
+    );
+    map.insert(
+        "kod".to_string(),
+        match output.status.code() {
+            Some(c) => Object::Sayı(c as f64),
+            None => Object::Hiç,
+        },
+    );
+    Object::Harita(map)
+}
+
+/// `<program> komut -> <komut>`
+fn komut(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let program = pop_yazı(stack, "komut")?;
+    Ok(Some(make_object!(Command {
+        program,
+        args: vec![],
+        env: vec![],
+        dir: None,
+    })))
+}
+
+/// `<komut> <argüman | argüman listesi> argüman -> <komut>`
+fn argüman(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let arg = pop(stack, "argüman")?;
+    let mut cmd = pop_command(stack, "argüman")?;
+    match arg {
+        Object::Yazı(s) => cmd.args.push(s),
+        Object::Liste(l) => {
+            for a in l.into_inner().into_iter() {
+                match a {
+                    Object::Yazı(s) => cmd.args.push(s),
+                    b => return Err(unexpected_type("yazı", "string", &b)),
+                }
+            }
+        }
+        b => return Err(unexpected_type("yazı", "string", &b)),
+    }
+    Ok(Some(make_object!(cmd)))
+}
+
+/// `<komut> <ad> <değer> ortam -> <komut>`
+fn ortam(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let val = pop_yazı(stack, "ortam")?;
+    let key = pop_yazı(stack, "ortam")?;
+    let mut cmd = pop_command(stack, "ortam")?;
+    cmd.env.push((key, val));
+    Ok(Some(make_object!(cmd)))
+}
+
+/// `<komut> <yol> dizin -> <komut>`
+fn dizin(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let dir = pop_yazı(stack, "dizin")?;
+    let mut cmd = pop_command(stack, "dizin")?;
+    cmd.dir = Some(dir);
+    Ok(Some(make_object!(cmd)))
+}
+
+/// `<komut> çalıştır -> <sonuç>`
+fn çalıştır(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let cmd = pop_command(stack, "çalıştır")?;
+    match cmd.build().output() {
+        Ok(output) => Ok(Some(output_to_object(output))),
+        Err(e) => Err(io_error("SüreçHatası", "ProcessError", e)),
+    }
+}
+
+/// `<komut> başlat -> <süreç>`
+fn başlat(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let cmd = pop_command(stack, "başlat")?;
+    let child = cmd
+        .build()
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn();
+    match child {
+        Ok(child) => Ok(Some(make_object!(Process {
+            program: cmd.program,
+            child: Rc::new(RefCell::new(Some(child))),
+        }))),
+        Err(e) => Err(io_error("SüreçHatası", "ProcessError", e)),
+    }
+}
+
+/// `<süreç> <yazı | baytlar> yaz`
+fn yaz(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let data = pop_bytes(stack, "yaz")?;
+    let process = pop_process(stack, "yaz")?;
+    let mut child = process.child.borrow_mut();
+    let stdin = match child.as_mut().and_then(|c| c.stdin.as_mut()) {
+        Some(stdin) => stdin,
+        None => return Err(process_error(
+            "sürecin girdisi kapatılmış",
+            "the input of the process has been closed",
+        )),
+    };
+    match stdin.write_all(&data).and_then(|_| stdin.flush()) {
+        Ok(()) => Ok(None),
+        Err(e) => Err(io_error("SüreçHatası", "ProcessError", e)),
+    }
+}
+
+/// `<süreç> kapat`, closes the input of the process
+fn kapat(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let process = pop_process(stack, "kapat")?;
+    if let Some(child) = process.child.borrow_mut().as_mut() {
+        child.stdin.take();
+    }
+    Ok(None)
+}
+
+/// `<süreç> bekle -> <sonuç>`, closes the input of the process and waits for it to finish
+fn bekle(stack: &mut StackMemory, _: &mut HashMemory) -> Result<Option<Object>, Error> {
+    let process = pop_process(stack, "bekle")?;
+    let child = match process.child.borrow_mut().take() {
+        Some(child) => child,
+        None => return Err(process_error(
+            "süreç zaten beklenmiş",
+            "the process has already been waited for",
+        )),
+    };
+    match child.wait_with_output() {
+        Ok(output) => Ok(Some(output_to_object(output))),
+        Err(e) => Err(io_error("SüreçHatası", "ProcessError", e)),
+    }
+}
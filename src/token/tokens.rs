@@ -1,11 +1,14 @@
 use serde::{Deserialize, Serialize};
 
+use crate::util::bytes_repr;
+
 pub mod tokentypes {
     use serde::{Deserialize, Serialize};
 
     #[derive(Debug, Clone, Serialize, Deserialize)]
     pub enum ParserTokenType {
         Yazı { val: String },
+        Baytlar { val: Vec<u8> },
         Artı,
         Eksi,
         ArtıArtı,
@@ -61,6 +64,7 @@ pub mod tokentypes {
         Comma,
         İşlev,
         Yazı,
+        Baytlar(Vec<u8>),
         Sayı,
         De,
         İken,
@@ -197,6 +201,7 @@ impl LexerToken {
             TokTyp::Ve => "ve".to_string(),
             TokTyp::Veya => "veya".to_string(),
             TokTyp::Yazı => format!("{:?}", self.lexeme),
+            TokTyp::Baytlar(_) => self.lexeme.clone(),
             TokTyp::Yoksa => "yoksa".to_string(),
             TokTyp::Çarpı => "*".to_string(),
             TokTyp::Üst => "üst".to_string(),
@@ -277,6 +282,7 @@ impl ParserToken {
             TokTyp::Ve => "ve".to_string(),
             TokTyp::Veya => "veya".to_string(),
             TokTyp::Yazı { ref val } => format!("{:?}", val),
+            TokTyp::Baytlar { ref val } => bytes_repr(val),
             TokTyp::Yoksa(_) => "yoksa".to_string(),
             TokTyp::Çarpı => "*".to_string(),
             TokTyp::Üst => "üst".to_string(),
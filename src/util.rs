@@ -1,7 +1,7 @@
 use std::fs::File;
 use std::io::ErrorKind::{IsADirectory, self};
 use std::io::Read;
-use std::path::PathBuf;
+use std::path::Path;
 use std::process::exit;
 
 use locale_config::Locale;
@@ -40,11 +40,11 @@ where
     exit(1);
 }
 
-pub fn read_file(path: &PathBuf) -> Result<String, FSErr> {
-    let mut file = match File::open(path.clone()) {
+pub fn read_file(path: &Path) -> Result<String, FSErr> {
+    let mut file = match File::open(path) {
         Err(e) => error_print(
             "error opening file",
-            format!("{}: {}", e, path.as_path().display()),
+            format!("{}: {}", e, path.display()),
         ),
         Ok(f) => f,
     };
@@ -60,6 +60,16 @@ pub fn read_file(path: &PathBuf) -> Result<String, FSErr> {
     Ok(buf)
 }
 
+/// Formats bytes the way a `Baytlar` literal is written, like `b"\x00abc"`
+pub fn bytes_repr(b: &[u8]) -> String {
+    let mut buf = String::from("b\"");
+    for c in b.iter() {
+        buf.extend(std::ascii::escape_default(*c).map(char::from));
+    }
+    buf.push('"');
+    buf
+}
+
 /// Returns a SupportedLanguage by checking systems default locale
 /// if an unrecognized language is found it will return English
 pub fn get_lang() -> SupportedLanguage {
@@ -89,7 +99,7 @@ pub fn char_in_str(a: char, b: &str) -> bool {
 /// Checks if &T is in a &Vec<T>
 /// It is shortcircuiting function meaning if it finds any match it will immideatly return true
 /// if no matches are found it will return false
-pub fn in_vec<T>(a: &T, v: &Vec<T>) -> bool
+pub fn in_vec<T>(a: &T, v: &[T]) -> bool
 where
     T: PartialEq,
 {
@@ -6,13 +6,13 @@ use crate::exit;
 use crate::util::*;
 use std::fs::File;
 use std::io::Read;
-use std::path::PathBuf;
+use std::path::Path;
 
-pub fn read_file_to_vec_u8(path: &PathBuf) -> Vec<u8> {
-    let mut file = match File::open(path.clone()) {
+pub fn read_file_to_vec_u8(path: &Path) -> Vec<u8> {
+    let mut file = match File::open(path) {
         Err(e) => error_print(
             "error opening file",
-            format!("{}: {}", e, path.as_path().display()),
+            format!("{}: {}", e, path.display()),
         ),
         Ok(f) => f,
     };
@@ -46,20 +46,21 @@ pub fn print_help(exit_code: i32, prog_name: String) -> ! {
                 "{} sürüm {}, {} tarihinde yayınlandı",
                 prog_name, VERSION, RELEASE
             );
-            println!("");
+            println!();
             println!("KULLANIM:");
             println!("  {} <ALTKOMUT> <DOSYA|KOMUT> [SEÇENEKLER]", prog_name);
-            println!("");
+            println!();
             println!("ALTKOMUTLAR:");
             println!("    y yürüt    DOSYA'yı yürüt");
             println!("    b byt      DOSYA'yı bytecode'a dönüstür");
             println!("    yb yürbyt  bytecode DOSYA'sını yürüt");
             println!("    k komut    KOMUT'u yürüt");
+            println!("    ayrıştır dis  bytecode DOSYA'sını okunabilir biçimde göster");
             #[cfg(feature = "interactive")]
             println!("    i inter    interaktif konsolu aç");
             #[cfg(feature = "fmt")]
             println!("    f fmt      programı formatla (deneysel)");
-            println!("");
+            println!();
             println!("SEÇENEKLER:");
             println!("    -h -y --yardım         yardım göster ve çık");
             println!("    -V -s --sürüm          sürümü göster ve çık");
@@ -69,6 +70,10 @@ pub fn print_help(exit_code: i32, prog_name: String) -> ! {
             println!(
                 "    -p --parser-çıktısı    parse sürecinden sonra parser'ın çıktısını göster"
             );
+            println!("    -j --json              ayrıştırmayı JSON olarak göster");
+            println!("    -O --iyileştir         programı yürütmeden ya da bytecode'a dönüştürmeden önce iyileştir");
+            println!("    --izinsiz              programın dosyalara, süreçlere, ağa, kütüphanelere ve girdiye erişimini engelle");
+            println!("    --izin-<İZİN>          yalnızca verilen izinleri ver {{ dosya | süreç | ağ | ffi | girdi }}");
             #[cfg(feature = "interactive")]
             println!("    -q --sessiz            1 kadar sessizleştir [0]");
             #[cfg(feature = "fmt")]
@@ -80,26 +85,31 @@ pub fn print_help(exit_code: i32, prog_name: String) -> ! {
         }
         SupportedLanguage::English => {
             println!("{} version {}, released at {}", prog_name, VERSION, RELEASE);
-            println!("");
+            println!();
             println!("USAGE:");
             println!("    {} <SUBCOMMAND> <FILE|CMD> [OPTIONS]", prog_name);
-            println!("");
+            println!();
             println!("SUBCOMMANDS:");
             println!("    y yürüt    run FILE");
             println!("    b byt      output bytecode for FILE");
             println!("    yb yürbyt  run bytecode FILE");
             println!("    k komut    run CMD");
+            println!("    ayrıştır dis  show bytecode FILE in human-readable form");
             #[cfg(feature = "interactive")]
             println!("    i inter    open interactive console");
             #[cfg(feature = "fmt")]
             println!("    f fmt      format program (experimental)");
-            println!("");
+            println!();
             println!("OPTIONS:");
             println!("    -h -y --yardım         print help and exit");
             println!("    -V -s --sürüm          print version and exit");
             println!("    -o -ç --çıkış <file>   write bytecode at <file>");
             println!("    -l --lexer-çıktısı     after lexing show lexed tokens");
             println!("    -p --parser-çıktısı    after parsing show parsed tokens");
+            println!("    -j --json              show disassembly as JSON");
+            println!("    -O --iyileştir         optimize the program before running or writing bytecode");
+            println!("    --izinsiz              deny the program access to files, processes, the network, libraries and input");
+            println!("    --izin-<PERMISSION>    only allow the given permissions {{ dosya | süreç | ağ | ffi | girdi }}");
             #[cfg(feature = "interactive")]
             println!("    -q --sessiz            quiet the output by 1 [0]");
             #[cfg(feature = "fmt")]
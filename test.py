@@ -1,6 +1,8 @@
 #!/usr/bin/env python3
+import json
 import tempfile
 import time
+import zlib
 from subprocess import STDOUT, check_output
 from sys import stderr, argv as args
 from os.path import join as join_paths
@@ -12,25 +14,33 @@ COLOR=False if '--color' in args or '-c' in args else None
 NO_COLOR='--no-color' in args or '-n' in args
 
 colorinit(strip=NO_COLOR or COLOR)
-TMPD = tempfile.TemporaryDirectory(prefix="tr-lang.test").name
+# kept around until the script exits, when it is removed
+TMPDIR = tempfile.TemporaryDirectory(prefix="tr-lang.test")
+TMPD = TMPDIR.name
 print(f"{Fore.BLUE+Style.BRIGHT}writing to {Style.RESET_ALL+Fore.YELLOW}{TMPD}")
 
 beg = time.time_ns()
 failed = []
 
 DCR_SYM = '&' if os.name == 'nt' else ';'
+TR_LANG = join_paths('.', 'target', 'debug', 'tr-lang')
 
 
 def test(
     test_name: str,
     expected: Union[str, List[str], Callable],
     input: Optional[str] = None,
+    args: str = "",
+    command: Optional[str] = None,
 ):
+    """runs `tr-lang y tests/<test_name>.trl <args>`, or `command` instead if it is given"""
     print(f"{Fore.BLUE+Style.BRIGHT}running{Style.RESET_ALL}", test_name, "...", end=" ")
+    if command is None:
+        command = f"{TR_LANG} y {join_paths('tests', f'{test_name}.trl')} {args}"
     t0 = time.time_ns()
     rf = str(
         check_output(
-            f"{join_paths('.', 'target', 'debug', 'tr-lang')} y {join_paths('tests', f'{test_name}.trl')} {DCR_SYM} exit 0",
+            f"{command} {DCR_SYM} exit 0",
             shell=True,
             stderr=STDOUT,
             input=bytes(input, encoding="utf8") if input else input,
@@ -63,6 +73,7 @@ def test(
             print(f"{Fore.RED+Style.BRIGHT}failure{Style.RESET_ALL}", file=stderr)
             print("found:", rf, sep="\n")
             print("found didn't pass through", expected)
+            failed.append(test_name)
 
 
 test("hello-world", expected="Hello, World!\n")
@@ -93,6 +104,20 @@ test(
 )
 test("scope", expected="31")
 test("order", expected="11")
+test("tail-call", expected="5\n1\nbitti\n500000500000\n")
+RECURSION_LIMIT = join_paths("tests", "recursion-limit.trl")
+test(
+    "recursion-limit",
+    expected=[
+        f"\n[ERROR] {RECURSION_LIMIT}, Line 12, Column 11: say\n"
+        f"[ERROR] {RECURSION_LIMIT}, Line 8, Column 14: say (repeated 10000 times)\n"
+        "    RecursionLimit: at most 10000 functions can be run at once\n",
+
+        f"\n[HATA] {RECURSION_LIMIT}, Satır 12, Sütun 11: say\n"
+        f"[HATA] {RECURSION_LIMIT}, Satır 8, Sütun 14: say (10000 kez tekrarlandı)\n"
+        "    ÖzyinelemeSınırı: aynı anda en fazla 10000 işlev çalışabilir\n",
+    ],
+)
 test("multiples_of_5_or_3", expected="33\n" "234168\n")
 for i in ("factorial", "faktoriyel"):
     test(
@@ -118,6 +143,201 @@ test(
     expected="Enter first number: Enter operation(+-*/): Enter second number: 31\n",
     input="10\n+\n21",
 )
+test("süreç", expected="merhaba\n0\nakış\n")
+test(
+    "ağ",
+    expected=[
+        "merhaba\nselam\nhiç\npaket\n\n    NetworkTimeout: the operation timed out\n",
+        "merhaba\nselam\nhiç\npaket\n\n    AğZamanAşımı: işlem zaman aşımına uğradı\n",
+    ],
+)
+
+test("http", expected="POST /selam\n200 merhaba dünya, hoşça kal\n404 yok\n200 kapandı\n")
+test(
+    "kodlama",
+    expected="4c6bcdd55f3153e1939669ab1ec039e4059174dc25abdfcb2f58868849b4d61b\n"
+        "8af22540a3ae8c4a05e7eabb53d26cfc3393e91c\n"
+        "c39436ee452e641cde2eb992ab397911\n"
+        "76dff90d\n"
+        "bWVyaGFiYSBkw7xueWE=\n"
+        "merhaba dünya\n"
+        "6d6572686162612064c3bc6e7961\n"
+        "merhaba dünya\n"
+        "a%20b%26c%3D%C3%A7%2F~\n"
+        "a b&c=ç/~\n",
+)
+test(
+    "baytlar",
+    expected=[
+        "b\"\\x00\\xffab\\n\"\n"
+        "5\n"
+        "255\n"
+        "98\n"
+        "b\"\\xffa\"\n"
+        "b\"\\x00\\xffab\\nc\"\n"
+        "doğru\n"
+        "[0, 255, 97, 98, 10]\n"
+        "doğru\n"
+        "b\"\\xc4\\x9f\\xc3\\xbc\\xc5\\x9f\"\n"
+        "b\"\\xf0\\xfc\\xfe\"\n"
+        "ğüş\n"
+        "merhaba\n"
+        "e62cb0c0\n"
+        "AP9hYgo=\n"
+        "doğru\n"
+        "b\"\\x00\\xffab\\nsonu\"\n"
+        "\n"
+        f"    {err}: stream did not contain valid UTF-8\n"
+        for err in ("FSError", "DosyaHatası")
+    ],
+)
+
+HELLO_WORLD = join_paths("tests", "hello-world.trl")
+HELLO_TRBYT = join_paths(TMPD, "hello-world.trbyt")
+test(
+    "bytecode",
+    expected="Hello, World!\n",
+    command=f"{TR_LANG} b {HELLO_WORLD} -o {HELLO_TRBYT} {DCR_SYM} {TR_LANG} yb {HELLO_TRBYT}",
+)
+
+
+def trbyt(name: str, change: Callable[[bytes], bytes], checksum: bool = False) -> str:
+    """writes hello-world.trbyt changed by `change` to `name`, with its checksum fixed if
+    `checksum` is set, and returns its path"""
+    with open(HELLO_TRBYT, "rb") as f:
+        bytecode = change(f.read())
+    if checksum:
+        header = 4 + 2 + 1 + bytecode[6]
+        crc = zlib.crc32(bytecode[header + 4:]).to_bytes(4, "little")
+        bytecode = bytecode[:header] + crc + bytecode[header + 4:]
+    path = join_paths(TMPD, name)
+    with open(path, "wb") as f:
+        f.write(bytecode)
+    return path
+
+
+def error(*explanations: str) -> Callable[[str], bool]:
+    """expects an error explained by one of `explanations`"""
+    return lambda found: any(f": {e}\n" in found for e in explanations)
+
+
+test(
+    "bytecode-magic",
+    expected=error("file is not a tr-lang bytecode file", "dosya bir tr-lang bytecode dosyası değil"),
+    command=f"{TR_LANG} yb {HELLO_WORLD}",
+)
+test(
+    "bytecode-format",
+    expected=error(
+        "bytecode format version 1 is not supported, this tr-lang reads version 2",
+        "bytecode biçimi sürümü 1 desteklenmiyor, bu tr-lang sürüm 2 biçimini okuyabilir",
+    ),
+    command=f"{TR_LANG} yb {trbyt('format.trbyt', lambda b: b[:4] + bytes([1, 0]) + b[6:])}",
+)
+test(
+    "bytecode-checksum",
+    expected=error(
+        "bytecode checksum does not match, the file is corrupted",
+        "bytecode sağlama toplamı eşleşmiyor, dosya bozulmuş",
+    ),
+    command=f"{TR_LANG} yb {trbyt('checksum.trbyt', lambda b: b[:-1] + bytes([b[-1] ^ 0x40]))}",
+)
+
+
+def missing_constant(bytecode: bytes) -> bytes:
+    """makes the first instruction of hello-world.trbyt, `Sabit(0)`, push a constant that
+    doesn't exist; the payload starts with the number of instructions as a u64, followed by the
+    u32 tag of the first one and its operand"""
+    operand = 4 + 2 + 1 + bytecode[6] + 4 + 12
+    return bytecode[:operand] + (5).to_bytes(4, "little") + bytecode[operand + 4:]
+
+
+INVALID_TRBYT = trbyt("invalid.trbyt", missing_constant, checksum=True)
+test(
+    "bytecode-verify",
+    expected=lambda found: "hello-world.trl:3:17) is invalid: there is no entry 5 in the constant pool\n" in found
+    or "hello-world.trl:3:17) geçersiz: sabit havuzunda 5 numaralı eleman yok\n" in found,
+    command=f"{TR_LANG} yb {INVALID_TRBYT}",
+)
+
+SCOPE = join_paths("tests", "scope.trl")
+SCOPE_TRBYT = join_paths(TMPD, "scope.trbyt")
+test(
+    "ayrıştır",
+    expected=f" 0  İşlev           lol -> 5 (İşlevSonlandır)       {SCOPE}:3:6\n"
+    f" 1  Sabit           3                               {SCOPE}:4:6\n"
+    f" 2  Koy             x                               {SCOPE}:4:9\n"
+    f" 3  Yerel           x                               {SCOPE}:5:6\n"
+    f" 4  De                                              {SCOPE}:5:9\n"
+    f" 5  İşlevSonlandır                                  {SCOPE}:6:4\n"
+    f" 6  Sabit           1                               {SCOPE}:7:2\n"
+    f" 7  Koy             x                               {SCOPE}:7:5\n"
+    f" 8  Yerel           lol                             {SCOPE}:8:4\n"
+    f" 9  Yerel           x                               {SCOPE}:9:2\n"
+    f"10  De                                              {SCOPE}:9:5\n",
+    command=f"{TR_LANG} b {SCOPE} -o {SCOPE_TRBYT} {DCR_SYM} {TR_LANG} ayrıştır {SCOPE_TRBYT}",
+)
+test(
+    "ayrıştır-json",
+    expected=lambda found: json.loads(found)[:2] == [
+        {"index": 0, "kind": "İşlev", "operands": ["lol"], "target": 5, "position": f"{SCOPE}:3:6"},
+        {"index": 1, "kind": "Sabit", "operands": ["3"], "target": None, "position": f"{SCOPE}:4:6"},
+    ] and len(json.loads(found)) == 11,
+    command=f"{TR_LANG} ayrıştır {SCOPE_TRBYT} -j",
+)
+# programs that would be rejected are still listed, followed by why they are rejected
+test(
+    "ayrıştır-invalid",
+    expected=lambda found: found.startswith(
+        f"0  Sabit           ?                               {HELLO_WORLD}:3:17\n"
+        f"1  De                                              {HELLO_WORLD}:3:20\n"
+    ) and error(
+        "instruction 0 (tests/hello-world.trl:3:17) is invalid: there is no entry 5 in the constant pool",
+        "0 numaralı komut (tests/hello-world.trl:3:17) geçersiz: sabit havuzunda 5 numaralı eleman yok",
+    )(found.replace("\\", "/")),
+    command=f"{TR_LANG} ayrıştır {INVALID_TRBYT}",
+)
+
+for args in ("", "-O"):
+    test("optimize", expected="14\nmerhaba\ndoğru\n", args=args)
+OPTIMIZE_TRBYT = join_paths(TMPD, "optimize.trbyt")
+# unused top-level functions are kept in bytecode, a host may call them
+test(
+    "optimize-bytecode",
+    expected=lambda found: all(
+        s in found for s in ("Sabit           7 ", 'Sabit           "merhaba"', "İşlev           kullanılmayan")
+    )
+    and not any(s in found for s in ("Artı", "İse", "İken", "yanlış", "hiç çalışmıyor")),
+    command=f"{TR_LANG} b {join_paths('tests', 'optimize.trl')} -O -o {OPTIMIZE_TRBYT} {DCR_SYM} {TR_LANG} ayrıştır {OPTIMIZE_TRBYT}",
+)
+
+
+def denied(module: str) -> Callable[[str], bool]:
+    """expects loading `module` to be denied"""
+    return error(f"no permission to load the module `{module}`", f"`{module}` modülünü yükleme izni yok")
+
+
+test("süreç", expected=denied("süreç"), args="--izinsiz")
+test("süreç", expected=denied("süreç"), args="--izin-dosya --izin-ağ")
+test("süreç", expected="merhaba\n0\nakış\n", args="--izin-süreç")
+test(
+    "type-conv",
+    expected=lambda found: found.startswith("Enter a number: ")
+    and error("no permission to read input", "girdi okuma izni yok")(found),
+    input="1",
+    args="--izinsiz",
+)
+test("type-conv", expected="Enter a number: 124.321\ndoğru\n", input="1", args="--izin-girdi")
+for module, permission in (("dosya", "dosya"), ("ağ", "ağ"), ("http", "ağ")):
+    test(
+        f"izin-{permission}",
+        expected=denied(module),
+        command=f"{TR_LANG} k \"yükle '{module}' -> m\" --izinsiz",
+    )
+    test(f"izin-{permission}", expected="", command=f"{TR_LANG} k \"yükle '{module}' -> m\" --izin-{permission}")
+# modules that can't do anything harmful are always allowed
+test("izinsiz", expected="", command=f"{TR_LANG} k \"yükle 'kodlama' -> m\" --izinsiz")
+test("izin-yok", expected='unknown argument: "--izin-yok"\n', command=f"{TR_LANG} y {HELLO_WORLD} --izin-yok")
 
 print(
     f"script {Fore.BLUE+Style.BRIGHT}took{Style.RESET_ALL}",
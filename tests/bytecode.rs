@@ -0,0 +1,233 @@
+mod common;
+
+use std::fs;
+
+use common::{assert_error, program, program_in, run_with};
+use tr_lang::bytecode::{
+    disassemble, from_bytecode, optimize, to_bytecode, verify, Instruction, Program, FORMAT_VERSION, MAGIC,
+};
+use tr_lang::error::Error;
+
+/// Asserts that `error` is a `BytecodeError` explained by `tr` or `en`
+fn assert_invalid(error: Error, tr: &str, en: &str) {
+    assert_error(&error, "BytecodeHatası", "BytecodeError");
+    let explanation = error.explanation();
+    assert!(
+        explanation.contains(tr) || explanation.contains(en),
+        "expected `{}` or `{}`, found `{}`",
+        tr,
+        en,
+        explanation,
+    );
+}
+
+fn read(bytecode: &[u8]) -> Result<Program, Error> {
+    from_bytecode(bytecode, "test.trbyt")
+}
+
+const SOURCE: &str = "
+işlev f -> x
+    blok b
+        x 1 + -> y
+    son
+    b:y ver
+son
+3 -> i
+:. i 0 > iken
+    i f de
+    i 1 - -> i
+son
+";
+
+#[test]
+fn bytecode_round_trips() {
+    let program = program(SOURCE);
+    let read = read(&to_bytecode(&program)).unwrap();
+    assert_eq!(disassemble(&read), disassemble(&program));
+    let (out, res) = run_with(read, common::config);
+    res.unwrap();
+    assert_eq!(out, "432");
+}
+
+#[test]
+fn every_test_program_verifies() {
+    for entry in fs::read_dir("tests").unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_none_or(|e| e != "trl") {
+            continue;
+        }
+        let file = path.display().to_string();
+        let program = program_in(&fs::read_to_string(&path).unwrap(), &file);
+        verify(&program, &file).unwrap();
+        let optimized = optimize(program);
+        verify(&optimized, &file).unwrap();
+        read(&to_bytecode(&optimized)).unwrap();
+    }
+}
+
+#[test]
+fn header_is_checked() {
+    let bytecode = to_bytecode(&program(SOURCE));
+    assert_eq!(&bytecode[..4], MAGIC);
+    assert_eq!(bytecode[4..6], FORMAT_VERSION.to_le_bytes());
+
+    assert_invalid(read(&[]).unwrap_err(), "başlığı bitmeden", "ends before the end of the bytecode header");
+    assert_invalid(
+        read(&bytecode[..8]).unwrap_err(),
+        "başlığı bitmeden",
+        "ends before the end of the bytecode header",
+    );
+    assert_invalid(
+        read(SOURCE.as_bytes()).unwrap_err(),
+        "bir tr-lang bytecode dosyası değil",
+        "is not a tr-lang bytecode file",
+    );
+
+    let mut format = bytecode.clone();
+    format[4..6].copy_from_slice(&(FORMAT_VERSION + 1).to_le_bytes());
+    assert_invalid(read(&format).unwrap_err(), "desteklenmiyor", "is not supported");
+
+    let mut version = bytecode;
+    version[7] = b'x';
+    assert_invalid(read(&version).unwrap_err(), "yeniden derleyin", "recompile the file");
+}
+
+#[test]
+fn corruption_is_caught_by_the_checksum() {
+    let bytecode = to_bytecode(&program(SOURCE));
+    let header = 4 + 2 + 1 + bytecode[6] as usize + 4;
+    for at in [header - 1, header, bytecode.len() / 2, bytecode.len() - 1] {
+        let mut corrupted = bytecode.clone();
+        corrupted[at] ^= 0x40;
+        assert_invalid(read(&corrupted).unwrap_err(), "sağlama toplamı", "checksum does not match");
+    }
+    assert_invalid(
+        read(&bytecode[..bytecode.len() - 1]).unwrap_err(),
+        "sağlama toplamı",
+        "checksum does not match",
+    );
+}
+
+/// Asserts that `program` changed by `tamper` is rejected, both by [`verify`] and when read
+fn assert_rejected(tamper: impl FnOnce(&mut Program), tr: &str, en: &str) {
+    let mut program = program(SOURCE);
+    tamper(&mut program);
+    assert_invalid(verify(&program, "test.trbyt").unwrap_err(), tr, en);
+    assert_invalid(read(&to_bytecode(&program)).unwrap_err(), tr, en);
+}
+
+/// Where the first instruction matching `f` is
+fn find(program: &Program, f: impl Fn(&Instruction) -> bool) -> usize {
+    program.code.iter().position(f).unwrap()
+}
+
+#[test]
+fn pool_indexes_are_checked() {
+    assert_rejected(
+        |p| {
+            let at = find(p, |i| matches!(i, Instruction::Sabit(_)));
+            p.code[at] = Instruction::Sabit(99);
+        },
+        "sabit havuzunda 99 numaralı eleman yok",
+        "there is no entry 99 in the constant pool",
+    );
+    assert_rejected(
+        |p| {
+            let at = find(p, |i| matches!(i, Instruction::Koy(_)));
+            p.code[at] = Instruction::Koy(99);
+        },
+        "isim havuzunda 99 numaralı eleman yok",
+        "there is no entry 99 in the name pool",
+    );
+    assert_rejected(
+        |p| {
+            p.spans[0].file = 9;
+        },
+        "olmayan bir dosyada",
+        "in a file that doesn't exist",
+    );
+    assert_rejected(
+        |p| {
+            p.spans.pop();
+        },
+        "eşleşmiyor",
+        "do not match",
+    );
+}
+
+#[test]
+fn jumps_are_checked() {
+    assert_rejected(
+        |p| {
+            let len = p.code.len() as u32;
+            let at = find(p, |i| matches!(i, Instruction::Atla(_)));
+            p.code[at] = Instruction::Atla(len + 1);
+        },
+        "bu komut yok",
+        "which doesn't exist",
+    );
+    // from the loop into the function
+    assert_rejected(
+        |p| {
+            let at = find(p, |i| matches!(i, Instruction::Atla(_)));
+            p.code[at] = Instruction::Atla(2);
+        },
+        "bir işlev ya da bloğa giriyor veya çıkıyor",
+        "enters or leaves a function or block",
+    );
+    // out of the block in the function
+    assert_rejected(
+        |p| {
+            let at = find(p, |i| matches!(i, Instruction::Blok(_)));
+            p.code[at + 1] = Instruction::Atla(p.code.len() as u32);
+        },
+        "bir işlev ya da bloğa giriyor veya çıkıyor",
+        "enters or leaves a function or block",
+    );
+}
+
+#[test]
+fn nesting_is_checked() {
+    assert_rejected(
+        |p| {
+            let at = find(p, |i| matches!(i, Instruction::BlokSonlandır));
+            p.code[at] = Instruction::İşlevSonlandır;
+        },
+        "işlevin sonu bir işlevi kapatmıyor",
+        "end of function does not close a function",
+    );
+    assert_rejected(
+        |p| {
+            let at = find(p, |i| matches!(i, Instruction::İşlevSonlandır));
+            p.code[at] = Instruction::BlokSonlandır;
+        },
+        "sonlanmıyor",
+        "function does not end at instruction",
+    );
+    assert_rejected(
+        |p| {
+            let at = find(p, |i| matches!(i, Instruction::İşlev { .. }));
+            if let Instruction::İşlev { son, .. } = &mut p.code[at] {
+                *son = at as u32;
+            }
+        },
+        "sonlanmıyor",
+        "function does not end at instruction",
+    );
+    assert_rejected(
+        |p| {
+            let at = find(p, |i| matches!(i, Instruction::De));
+            p.code[at] = Instruction::Ver;
+        },
+        "`ver` bir işlevin içinde değil",
+        "`ver` is not inside a function",
+    );
+    assert_rejected(
+        |p| {
+            let last = p.code.len() - 1;
+            p.code[last] = Instruction::Blok(0);
+        },
+        "sonlandırılmamış",
+        "it is never closed",
+    );
+}
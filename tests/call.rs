@@ -0,0 +1,214 @@
+mod common;
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use common::{assert_error, config, program, Output};
+use tr_lang::error::Error;
+use tr_lang::ffi::FfiFunction;
+use tr_lang::mem::{HashMemory, Object, StackMemory};
+use tr_lang::runtime::{self, Run, RunConfig};
+
+const SOURCE: &str = "
+işlev topla -> b -> a
+    a b + ver
+son
+işlev selamla -> ad
+    \"merhaba \" ad + de
+son
+işlev böl -> b -> a
+    b 0 = ise
+        yok
+    son
+    a b / ver
+son
+işlev ikisi
+    1 2
+son
+\"değil\" -> işlev-değil
+";
+
+/// Runs [`SOURCE`] to define its functions, returning the run and its memories
+fn defined(out: &Output) -> (Run, (StackMemory, HashMemory)) {
+    let mut run = Run::new(program(SOURCE));
+    let mem = run
+        .run(config(out))
+        .unwrap_or_else(|(_, _, e)| panic!("{}", e.explanation()));
+    (run, mem)
+}
+
+#[allow(clippy::result_large_err, clippy::type_complexity)]
+fn call(
+    run: &mut Run,
+    mem: (StackMemory, HashMemory),
+    name: &str,
+    args: Vec<Object>,
+    out: &Output,
+) -> Result<(Vec<Object>, StackMemory, HashMemory), (StackMemory, HashMemory, Error)> {
+    run.call(name, args, RunConfig { mem, ..config(out) })
+}
+
+#[test]
+fn calls_return_what_their_functions_give_back() {
+    let out = Output::default();
+    let (mut run, mem) = defined(&out);
+    let (values, stack, hashs) = call(
+        &mut run,
+        mem,
+        "topla",
+        vec![Object::Sayı(2.0), Object::Sayı(3.0)],
+        &out,
+    )
+    .unwrap_or_else(|(_, _, e)| panic!("{}", e.explanation()));
+    assert_eq!(format!("{:?}", values), "[5]");
+
+    let (values, stack, hashs) = call(
+        &mut run,
+        (stack, hashs),
+        "selamla",
+        vec![Object::Yazı("dünya".to_string())],
+        &out,
+    )
+    .unwrap_or_else(|(_, _, e)| panic!("{}", e.explanation()));
+    assert!(values.is_empty());
+    assert_eq!(out.text(), "merhaba dünya");
+
+    // functions are called in a scope of their own, which takes what they did not give back
+    let (values, stack, hashs) = call(&mut run, (stack, hashs), "ikisi", vec![], &out)
+        .unwrap_or_else(|(_, _, e)| panic!("{}", e.explanation()));
+    assert!(values.is_empty());
+    assert!(stack.is_empty());
+    assert_eq!(hashs.depth(), 1);
+}
+
+#[test]
+fn memories_are_given_back_after_errors() {
+    let out = Output::default();
+    let (mut run, mem) = defined(&out);
+    let (stack, hashs, e) = call(
+        &mut run,
+        mem,
+        "böl",
+        vec![Object::Sayı(1.0), Object::Sayı(0.0)],
+        &out,
+    )
+    .unwrap_err();
+    assert_error(&e, "BilinmeyenTanımlayıcı", "BilinmeyenTanımlayıcı");
+    assert!(stack.is_empty());
+    assert_eq!(hashs.depth(), 1);
+
+    // and the run can be called again
+    let (values, _, _) = call(
+        &mut run,
+        (stack, hashs),
+        "böl",
+        vec![Object::Sayı(1.0), Object::Sayı(4.0)],
+        &out,
+    )
+    .unwrap_or_else(|(_, _, e)| panic!("{}", e.explanation()));
+    assert_eq!(format!("{:?}", values), "[0.25]");
+}
+
+#[test]
+fn only_functions_can_be_called() {
+    let out = Output::default();
+    let (mut run, mem) = defined(&out);
+    let (stack, hashs, e) = call(&mut run, mem, "yok", vec![], &out).unwrap_err();
+    assert_error(&e, "BilinmeyenTanımlayıcı", "BilinmeyenTanımlayıcı");
+    let (_, _, e) = call(&mut run, (stack, hashs), "işlev-değil", vec![], &out).unwrap_err();
+    assert_error(&e, "BeklenmedikTip", "BeklenmedikTip");
+}
+
+#[test]
+fn functions_of_other_programs_can_not_be_called() {
+    let out = Output::default();
+    let (_, (stack, hashs)) = defined(&out);
+    let mut other = Run::new(program("1 at"));
+    let (_, _, e) = call(
+        &mut other,
+        (stack, hashs),
+        "topla",
+        vec![Object::Sayı(2.0), Object::Sayı(3.0)],
+        &out,
+    )
+    .unwrap_err();
+    assert_error(&e, "ÇağrıHatası", "CallError");
+
+    // not even through the program
+    let (_, (stack, mut hashs)) = defined(&out);
+    let topla = hashs.get(&"topla".to_string()).unwrap().clone();
+    hashs.insert("başka".to_string(), topla);
+    let (_, _, e) = Run::new(program("2 3 başka de"))
+        .run(RunConfig {
+            mem: (stack, hashs),
+            ..config(&out)
+        })
+        .unwrap_err();
+    assert_error(&e, "ÇağrıHatası", "CallError");
+}
+
+#[test]
+fn ffi_functions_can_be_called() {
+    let out = Output::default();
+    let (mut run, (stack, mut hashs)) = defined(&out);
+    let calls = Rc::new(Cell::new(0));
+    let counter = calls.clone();
+    hashs.insert(
+        "iki-katı".to_string(),
+        Object::FfiFunction(FfiFunction::new(move |stack, _| {
+            counter.set(counter.get() + 1);
+            match stack.pop() {
+                Some(Object::Sayı(n)) => Ok(Some(Object::Sayı(n * 2.0))),
+                _ => unreachable!(),
+            }
+        })),
+    );
+    let (values, _, _) = call(
+        &mut run,
+        (stack, hashs),
+        "iki-katı",
+        vec![Object::Sayı(21.0)],
+        &out,
+    )
+    .unwrap_or_else(|(_, _, e)| panic!("{}", e.explanation()));
+    assert_eq!(format!("{:?}", values), "[42]");
+    assert_eq!(calls.get(), 1);
+}
+
+#[test]
+fn ffi_functions_can_call_back_into_the_program() {
+    let out = Output::default();
+    let mut hashs = HashMemory::new();
+    // calls the function named on top of the stack with the value under it
+    hashs.insert(
+        "uygula".to_string(),
+        Object::FfiFunction(FfiFunction::new(|stack, hashs| {
+            let f = match stack.pop() {
+                Some(Object::Yazı(name)) => hashs.get(&name).unwrap().clone(),
+                _ => unreachable!(),
+            };
+            runtime::call(&f, stack, hashs)?;
+            Ok(None)
+        })),
+    );
+    let source = "
+işlev kare -> x
+    x x * ver
+son
+7 \"kare\" uygula de
+";
+    let mut run = Run::new(program(source));
+    let res = run.run(RunConfig {
+        mem: (StackMemory::new(), hashs),
+        ..config(&out)
+    });
+    if let Err((_, _, e)) = res {
+        panic!("{}: {}", e.name(), e.explanation());
+    }
+    assert_eq!(out.text(), "49");
+
+    // there is no program to call back into outside of a run
+    let (mut stack, mut hashs) = (StackMemory::new(), HashMemory::new());
+    let e = runtime::call(&Object::İşlev(0), &mut stack, &mut hashs).unwrap_err();
+    assert_error(&e, "ÇağrıHatası", "CallError");
+}
@@ -0,0 +1,102 @@
+//! Helpers shared by the integration tests
+#![allow(dead_code)]
+
+use std::cell::RefCell;
+use std::io::{self, Write};
+use std::rc::Rc;
+
+use tr_lang::bytecode::{compile, Program};
+use tr_lang::error::Error;
+use tr_lang::lexer::Lexer;
+use tr_lang::mem::{HashMemory, StackMemory};
+use tr_lang::parser::Parser;
+use tr_lang::runtime::{Run, RunConfig};
+
+/// Compiles `source`, as if it was read from `test.trl`
+pub fn program(source: &str) -> Program {
+    program_in(source, "test.trl")
+}
+
+/// Compiles `source` read from `file`, which files it imports are found relative to
+pub fn program_in(source: &str, file: &str) -> Program {
+    let mut lexer = Lexer::new(source.to_string());
+    let mut parser = Parser::from_lexer(&mut lexer, file.to_string()).unwrap();
+    compile(parser.parse().unwrap()).unwrap()
+}
+
+/// Everything written to it, shared between its clones so that it can be looked at after being
+/// given to a run
+#[derive(Clone, Default)]
+pub struct Output(Rc<RefCell<Vec<u8>>>);
+
+impl Output {
+    pub fn text(&self) -> String {
+        String::from_utf8(self.0.borrow().clone()).unwrap()
+    }
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A config that writes to `out` and reads nothing
+pub fn config(out: &Output) -> RunConfig {
+    RunConfig {
+        stdin: Some(Box::new(io::empty())),
+        stdout: Some(Box::new(out.clone())),
+        stderr: Some(Box::new(out.clone())),
+        ..Default::default()
+    }
+}
+
+/// Runs `program` with `config`, returning what it wrote along with how it ended
+#[allow(clippy::result_large_err)]
+pub fn run_with(
+    program: Program,
+    config: impl FnOnce(&Output) -> RunConfig,
+) -> (String, Result<(StackMemory, HashMemory), Error>) {
+    let out = Output::default();
+    let res = Run::new(program).run(config(&out)).map_err(|(_, _, e)| e);
+    (out.text(), res)
+}
+
+/// Compiles and runs `source`, returning what it wrote
+pub fn run(source: &str) -> String {
+    match run_with(program(source), config) {
+        (out, Ok(_)) => out,
+        (out, Err(e)) => panic!("{}\n{}: {}", out, e.name(), e.explanation()),
+    }
+}
+
+/// Compiles and runs `source` expecting it to fail, returning the error
+pub fn fail(source: &str) -> Error {
+    fail_with(source, config)
+}
+
+/// Compiles and runs `source` with `config` expecting it to fail, returning the error
+pub fn fail_with(source: &str, config: impl FnOnce(&Output) -> RunConfig) -> Error {
+    match run_with(program(source), config) {
+        (_, Err(e)) => e,
+        (out, Ok(_)) => panic!("expected an error, the program wrote:\n{}", out),
+    }
+}
+
+/// Asserts that `error` is called `tr` or `en`, depending on the language it was made in
+pub fn assert_error(error: &Error, tr: &str, en: &str) {
+    let name = error.name();
+    assert!(
+        name == tr || name == en,
+        "expected {} or {}, found {}: {}",
+        tr,
+        en,
+        name,
+        error.explanation(),
+    );
+}
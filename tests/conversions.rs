@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+
+use tr_lang::mem::{ConversionError, FromObject, IntoObject, List, Map, Object};
+
+/// `o` turned back into a `T`
+fn back<T: FromObject>(o: Object) -> Result<T, ConversionError> {
+    T::from_object(o)
+}
+
+#[test]
+fn numbers() {
+    assert_eq!(back::<f64>(1.5.into_object()).unwrap(), 1.5);
+    assert_eq!(back::<i32>((-7i32).into_object()).unwrap(), -7);
+    assert_eq!(back::<u8>(Object::Sayı(255.)).unwrap(), 255);
+    assert_eq!(back::<i64>(Object::Sayı(-(2f64.powi(63)))).unwrap(), i64::MIN);
+    assert_eq!(back::<u64>(Object::Sayı(2f64.powi(63))).unwrap(), 1 << 63);
+
+    // only whole numbers that fit are converted
+    let e = back::<i32>(Object::Sayı(1.5)).unwrap_err();
+    assert_eq!((e.expected, e.found), ("tam sayı", "sayı"));
+    assert!(back::<u8>(Object::Sayı(256.)).is_err());
+    assert!(back::<u8>(Object::Sayı(-1.)).is_err());
+    assert!(back::<i64>(Object::Sayı(2f64.powi(63))).is_err());
+    assert!(back::<isize>(Object::Sayı(2f64.powi(63))).is_err());
+    assert!(back::<u64>(Object::Sayı(2f64.powi(64))).is_err());
+    assert!(back::<usize>(Object::Sayı(2f64.powi(64))).is_err());
+    assert!(back::<i32>(Object::Sayı(f64::NAN)).is_err());
+    assert!(back::<i32>(Object::Sayı(f64::INFINITY)).is_err());
+    assert!(back::<i32>(Object::Yazı("1".to_string())).is_err());
+}
+
+#[test]
+fn collections() {
+    let o = vec![Some(1u8), None].into_object();
+    assert_eq!(format!("{:?}", o), "[1, hiç]");
+    assert_eq!(back::<Vec<Option<u8>>>(o).unwrap(), [Some(1), None]);
+
+    let o = HashMap::from([("a".to_string(), "b")]).into_object();
+    assert_eq!(back::<HashMap<String, String>>(o).unwrap()["a"], "b");
+
+    assert!(back::<List>(Object::Liste(List::new(vec![]))).is_ok());
+    assert!(back::<Map>(Object::Harita(Map::new())).is_ok());
+    assert_eq!(back::<String>("yazı".into_object()).unwrap(), "yazı");
+    assert!(back::<bool>(true.into_object()).unwrap());
+
+    // errors point at where the object that could not be converted was
+    let e = back::<Vec<Vec<u8>>>(vec![vec![1.], vec![2., 0.5]].into_object()).unwrap_err();
+    assert_eq!(e.at, ["1", "1"]);
+    assert!(e.to_string().starts_with("`1.1`: "), "{}", e);
+}
+
+#[derive(Debug, PartialEq, IntoObject, FromObject)]
+struct Kullanıcı {
+    ad: String,
+    yaş: u32,
+    takma_ad: Option<String>,
+    puanlar: Vec<Vec<f64>>,
+    ayarlar: HashMap<String, Vec<bool>>,
+}
+
+fn kullanıcı() -> Kullanıcı {
+    Kullanıcı {
+        ad: "Ayşe".to_string(),
+        yaş: 30,
+        takma_ad: None,
+        puanlar: vec![vec![1., 2.], vec![]],
+        ayarlar: HashMap::from([("tema".to_string(), vec![true, false])]),
+    }
+}
+
+#[test]
+fn derived_conversions() {
+    let o = kullanıcı().into_object();
+    let map = match &o {
+        Object::Harita(map) => map.clone(),
+        o => panic!("{:?}", o),
+    };
+    assert_eq!(format!("{:?}", map["ad"]), "Ayşe");
+    assert_eq!(format!("{:?}", map["takma_ad"]), "hiç");
+    assert_eq!(format!("{:?}", map["puanlar"]), "[[1, 2], []]");
+    assert_eq!(back::<Kullanıcı>(o).unwrap(), kullanıcı());
+
+    // `Option` fields can be left out
+    let mut map = map.into_inner();
+    map.remove("takma_ad");
+    assert_eq!(back::<Kullanıcı>(Object::Harita(Map::from(map))).unwrap(), kullanıcı());
+}
+
+#[test]
+fn derived_conversion_errors() {
+    let with = |key: &str, o: Object| {
+        let mut map = match kullanıcı().into_object() {
+            Object::Harita(map) => map.into_inner(),
+            _ => unreachable!(),
+        };
+        map.insert(key.to_string(), o);
+        back::<Kullanıcı>(Object::Harita(Map::from(map))).unwrap_err()
+    };
+
+    let e = with("yaş", Object::Sayı(-1.));
+    assert_eq!(e.at, ["yaş"]);
+    assert_eq!((e.expected, e.found), ("tam sayı", "sayı"));
+
+    let e = with("puanlar", vec![vec![1.into_object()], vec![Object::Hiç]].into_object());
+    assert_eq!(e.at, ["puanlar", "1", "0"]);
+    assert_eq!((e.expected, e.found), ("sayı", "hiç"));
+
+    let ayarlar = HashMap::from([("tema".to_string(), vec![true.into_object(), 1.into_object()])]);
+    let e = with("ayarlar", ayarlar.into_object());
+    assert_eq!(e.at, ["ayarlar", "tema", "1"]);
+    assert_eq!((e.expected, e.found), ("bool", "sayı"));
+
+    // fields that aren't `Option`s can't be left out
+    let e = with("ad", Object::Hiç);
+    assert_eq!(e.at, ["ad"]);
+    assert_eq!((e.expected, e.found), ("yazı", "hiç"));
+
+    let e = back::<Kullanıcı>(Object::Liste(List::new(vec![]))).unwrap_err();
+    assert!(e.at.is_empty());
+    assert_eq!((e.expected, e.found), ("harita", "liste"));
+}
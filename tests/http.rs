@@ -0,0 +1,74 @@
+mod common;
+
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+use std::time::Duration;
+
+use common::{assert_error, fail};
+
+#[test]
+fn requests_can_not_have_line_breaks_in_their_head() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let e = fail(&format!(
+        "
+yükle \"http\" -> http
+blok başlıklar
+    \"a\\r\\nSet-Cookie: b\" -> X-Deneme
+son
+\"get\" \"{}/\" başlıklar hiç http:istek
+",
+        url
+    ));
+    assert_error(&e, "HttpHatası", "HttpError");
+    let e = fail(&format!("yükle \"http\" -> http \"{}/a\\r\\nX: b\" http:al", url));
+    assert_error(&e, "HttpHatası", "HttpError");
+
+    // nothing was sent before they were rejected
+    for _ in 0..2 {
+        let mut sent = vec![];
+        listener.accept().unwrap().0.read_to_end(&mut sent).unwrap();
+        assert!(sent.is_empty(), "{:?}", String::from_utf8_lossy(&sent));
+    }
+}
+
+#[test]
+fn responses_can_not_have_line_breaks_in_their_head() {
+    let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+    // sends a request once the server is listening
+    let client = thread::spawn(move || {
+        for _ in 0..500 {
+            if let Ok(mut stream) = TcpStream::connect(("127.0.0.1", port)) {
+                stream.write_all(b"GET / HTTP/1.1\r\n\r\n").unwrap();
+                let mut res = String::new();
+                stream.read_to_string(&mut res).unwrap();
+                return res;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        panic!("the server never started listening");
+    });
+    let e = fail(&format!(
+        "
+yükle \"http\" -> http
+işlev işleyici -> istek
+    blok yanıt
+        blok başlıklar
+            \"a\\r\\nSet-Cookie: b\" -> X-Deneme
+        son
+        \"merhaba\" -> gövde
+    son
+    yanıt ver
+son
+\"127.0.0.1:{}\" http:sunucu \"işleyici\" http:karşıla
+",
+        port
+    ));
+    assert_error(&e, "HttpHatası", "HttpError");
+
+    // the client is told that the server failed instead
+    let res = client.join().unwrap();
+    assert!(res.starts_with("HTTP/1.1 500 "), "{:?}", res);
+    assert!(!res.contains("Set-Cookie"), "{:?}", res);
+}
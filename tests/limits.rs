@@ -0,0 +1,228 @@
+mod common;
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+use common::{assert_error, config, fail, program, Output};
+use tr_lang::mem::{HashMemory, Object, StackMemory};
+use tr_lang::runtime::{CancelHandle, Run, RunConfig};
+
+const FOREVER: &str = ":. doğru iken 1 at son";
+
+#[test]
+fn instruction_limit() {
+    let out = Output::default();
+    let mut run = Run::new(program("1 de 2 de 3 de"));
+    let (stack, hashs, e) = run
+        .run(RunConfig { max_instructions: Some(3), ..config(&out) })
+        .unwrap_err();
+    assert_error(&e, "KomutSınırı", "InstructionLimit");
+    assert_eq!(out.text(), "1");
+    assert_eq!(run.current(), 3);
+
+    // runs that stopped at a limit can carry on with more, along with their memories
+    let (stack, hashs, e) = run
+        .resume(RunConfig { mem: (stack, hashs), max_instructions: Some(2), ..config(&out) })
+        .unwrap_err();
+    assert_error(&e, "KomutSınırı", "InstructionLimit");
+    assert_eq!(out.text(), "12");
+    run.resume(RunConfig { mem: (stack, hashs), max_instructions: Some(2), ..config(&out) })
+        .unwrap_or_else(|(_, _, e)| panic!("{}", e.explanation()));
+    assert_eq!(out.text(), "123");
+
+    // exactly enough instructions is enough
+    let mut run = Run::new(program("1 de 2 de 3 de"));
+    run.run(RunConfig { max_instructions: Some(6), ..config(&out) })
+        .unwrap_or_else(|(_, _, e)| panic!("{}", e.explanation()));
+}
+
+#[test]
+fn instruction_limit_stops_loops() {
+    let out = Output::default();
+    let (_, _, e) = Run::new(program(FOREVER))
+        .run(RunConfig { max_instructions: Some(100_000), ..config(&out) })
+        .unwrap_err();
+    assert_error(&e, "KomutSınırı", "InstructionLimit");
+}
+
+#[test]
+fn timeout() {
+    let out = Output::default();
+    let start = Instant::now();
+    let (_, _, e) = Run::new(program(FOREVER))
+        .run(RunConfig { timeout: Some(Duration::from_millis(50)), ..config(&out) })
+        .unwrap_err();
+    assert_error(&e, "ZamanAşımı", "Timeout");
+    assert!(start.elapsed() >= Duration::from_millis(50));
+    assert!(start.elapsed() < Duration::from_secs(10));
+}
+
+#[test]
+fn socket_timeouts_are_not_the_run_timing_out() {
+    let e = fail(
+        "
+yükle \"ağ\" -> ağ
+\"127.0.0.1:0\" ağ:udp -> s
+s 0.01 ağ:zaman-aşımı
+s ağ:al
+",
+    );
+    assert_error(&e, "AğZamanAşımı", "NetworkTimeout");
+
+    // nor can a socket be given a timeout too long to wait
+    for timeout in ["1 0 /".to_string(), format!("1{}", "0".repeat(30))] {
+        let e = fail(&format!(
+            "yükle \"ağ\" -> ağ\n\"127.0.0.1:0\" ağ:udp -> s\n{} -> t\ns t ağ:zaman-aşımı",
+            timeout
+        ));
+        assert_error(&e, "BeklenmedikTip", "BeklenmedikTip");
+    }
+}
+
+#[test]
+fn cancel_from_another_thread() {
+    let out = Output::default();
+    let cancel = CancelHandle::new();
+    let canceller = {
+        let cancel = cancel.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            cancel.cancel();
+        })
+    };
+    let (_, _, e) = Run::new(program(FOREVER))
+        .run(RunConfig { cancel: Some(cancel.clone()), ..config(&out) })
+        .unwrap_err();
+    canceller.join().unwrap();
+    assert_error(&e, "İptalEdildi", "Cancelled");
+    assert!(cancel.is_cancelled());
+}
+
+#[test]
+fn cancelled_runs_stop_right_away() {
+    let out = Output::default();
+    let cancel = CancelHandle::new();
+    cancel.cancel();
+    let (_, _, e) = Run::new(program(FOREVER))
+        .run(RunConfig { cancel: Some(cancel), ..config(&out) })
+        .unwrap_err();
+    assert_error(&e, "İptalEdildi", "Cancelled");
+}
+
+#[test]
+fn limits_apply_to_functions_called_from_rust() {
+    let out = Output::default();
+    let mut run = Run::new(program("işlev sonsuz\n    :. doğru iken 1 at son\nson"));
+    let (stack, hashs) = run
+        .run(config(&out))
+        .unwrap_or_else(|(_, _, e)| panic!("{}", e.explanation()));
+    let (_, _, e) = run
+        .call("sonsuz", vec![], RunConfig { mem: (stack, hashs), max_instructions: Some(1000), ..config(&out) })
+        .unwrap_err();
+    assert_error(&e, "KomutSınırı", "InstructionLimit");
+}
+
+/// Runs `source` with `limit` set on its config, expecting a `BellekSınırı` explained by `tr` or
+/// `en`
+fn assert_memory_limit(source: &str, limit: impl FnOnce(&mut RunConfig), tr: &str, en: &str) {
+    let out = Output::default();
+    let mut config = config(&out);
+    limit(&mut config);
+    let (_, _, e) = Run::new(program(source)).run(config).unwrap_err();
+    assert_error(&e, "BellekSınırı", "MemoryLimit");
+    let explanation = e.explanation();
+    assert!(explanation == tr || explanation == en, "{}", explanation);
+}
+
+#[test]
+fn stack_limit() {
+    let source = ":. doğru iken 1 son";
+    assert_memory_limit(
+        source,
+        |c| c.max_stack = Some(100),
+        "kümede en fazla 100 değer olabilir",
+        "there can be at most 100 values on the stack",
+    );
+}
+
+#[test]
+fn value_limit() {
+    // the stack and the variables are counted together
+    let source = "1 2 3 -> a 4 -> b 5 -> c 6 -> d";
+    assert_memory_limit(
+        source,
+        |c| c.max_values = Some(4),
+        "en fazla 4 değer tutulabilir",
+        "at most 4 values can be kept",
+    );
+    let out = Output::default();
+    Run::new(program(source))
+        .run(RunConfig { max_values: Some(6), ..config(&out) })
+        .unwrap_or_else(|(_, _, e)| panic!("{}", e.explanation()));
+}
+
+#[test]
+fn memory_limit() {
+    assert_memory_limit(
+        "\"a\" -> a :. doğru iken a a + -> a son",
+        |c| c.max_memory = Some(1 << 20),
+        "değerler en fazla 1048576 bayt yer kaplayabilir",
+        "values can take up at most 1048576 bytes",
+    );
+}
+
+#[test]
+fn huge_values_are_checked_before_they_are_made() {
+    let start = Instant::now();
+    assert_memory_limit(
+        "\"abc\" 1000000000000 *",
+        |c| c.max_memory = Some(1 << 20),
+        "değerler en fazla 1048576 bayt yer kaplayabilir",
+        "values can take up at most 1048576 bytes",
+    );
+    assert!(start.elapsed() < Duration::from_secs(10));
+}
+
+#[test]
+fn memory_is_given_back_when_scopes_end() {
+    let source = "
+işlev f
+    \"abcdefghij\" 1000 * -> büyük
+son
+0 -> i
+:. i 100 < iken
+    f
+    i 1 + -> i
+son
+";
+    let out = Output::default();
+    Run::new(program(source))
+        .run(RunConfig { max_memory: Some(1 << 16), ..config(&out) })
+        .unwrap_or_else(|(_, _, e)| panic!("{}", e.explanation()));
+}
+
+#[test]
+fn bytes_are_only_counted_with_a_memory_limit() {
+    let out = Output::default();
+    let (stack, hashs) = Run::new(program("\"abcdefghij\" 1000 * -> büyük 1"))
+        .run(config(&out))
+        .unwrap_or_else(|(_, _, e)| panic!("{}", e.explanation()));
+    assert_eq!((stack.bytes(), hashs.bytes()), (None, None));
+
+    // what the memories already hold counts once there is a limit
+    let (_, _, e) = Run::new(program("2"))
+        .run(RunConfig { mem: (stack, hashs), max_memory: Some(1000), ..config(&out) })
+        .unwrap_err();
+    assert_error(&e, "BellekSınırı", "MemoryLimit");
+
+    let (mut stack, mut hashs) = (StackMemory::new(), HashMemory::new());
+    stack.push(Object::Yazı("a".repeat(100)));
+    hashs.insert("a".to_string(), Object::Yazı("a".repeat(100)));
+    assert_eq!((stack.bytes(), hashs.bytes()), (None, None));
+    stack.count_bytes(true);
+    hashs.count_bytes(true);
+    assert!(stack.bytes().unwrap() >= 100 && hashs.bytes().unwrap() >= 100);
+    stack.pop();
+    hashs.remove("a".to_string());
+    assert_eq!((stack.bytes(), hashs.bytes()), (Some(0), Some(0)));
+}
@@ -0,0 +1,141 @@
+mod common;
+
+use common::{program, run_with};
+use tr_lang::bytecode::{optimize, optimize_program, verify, Constant, Instruction, Program};
+
+/// The names of the functions `program` defines
+fn functions(program: &Program) -> Vec<&str> {
+    program
+        .code
+        .iter()
+        .filter_map(|ins| match ins {
+            Instruction::İşlev { ad, .. } => Some(program.names[*ad as usize].as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn has(program: &Program, ins: Instruction) -> bool {
+    program.code.contains(&ins)
+}
+
+/// Asserts that `source` writes the same with and without being optimized, returning what it
+/// wrote
+fn same_output(source: &str) -> String {
+    let (expected, res) = run_with(program(source), common::config);
+    res.unwrap();
+    for optimized in [optimize(program(source)), optimize_program(program(source))] {
+        verify(&optimized, "test.trl").unwrap();
+        let (out, res) = run_with(optimized, common::config);
+        res.unwrap();
+        assert_eq!(out, expected);
+    }
+    expected
+}
+
+#[test]
+fn literals_are_folded() {
+    let optimized = optimize(program("3 4 + -> a\n\"mer\" \"haba\" + -> b\n1 2 = -> c\n5 ++ -> d"));
+    let constants: Vec<String> = optimized
+        .code
+        .iter()
+        .filter_map(|ins| match ins {
+            Instruction::Sabit(i) => Some(format!("{:?}", optimized.consts[*i as usize])),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(constants, ["Sayı(7.0)", "Yazı(\"merhaba\")", "Sayı(6.0)"]);
+    assert!(has(&optimized, Instruction::Bool(false)));
+    assert!(!has(&optimized, Instruction::Artı));
+    // constants that are no longer used are dropped
+    assert!(!optimized.consts.iter().any(|c| matches!(c, Constant::Yazı(s) if s == "mer")));
+}
+
+#[test]
+fn operations_that_can_fail_are_left_to_the_run() {
+    let optimized = optimize(program("\"a\" 1 + -> a\ndoğru ++ -> b"));
+    assert!(has(&optimized, Instruction::Artı));
+    assert!(has(&optimized, Instruction::ArtıArtı));
+}
+
+#[test]
+fn literal_branches_are_removed() {
+    let source = "
+doğru ise \"evet\" de yoksa \"hayır\" de son
+yanlış ise \"evet\" de son
+:. yanlış iken \"hiç\" de son
+";
+    assert_eq!(same_output(source), "evet");
+    let optimized = optimize(program(source));
+    assert!(!optimized.code.iter().any(|i| matches!(i, Instruction::İse(_) | Instruction::İken(_))));
+    assert_eq!(optimized.code.iter().filter(|i| **i == Instruction::De).count(), 1);
+}
+
+#[test]
+fn unused_functions_are_removed() {
+    let source = "
+işlev kullanılmayan
+    işlev iç son
+son
+işlev dış
+    işlev kullanılan son
+    işlev içteki-kullanılmayan son
+    kullanılan
+son
+blok b
+    işlev bloktaki son
+son
+dış
+";
+    assert_eq!(functions(&program(source)).len(), 6);
+    // a host can still call top-level functions
+    assert_eq!(
+        functions(&optimize(program(source))),
+        ["kullanılmayan", "dış", "kullanılan", "bloktaki"],
+    );
+    assert_eq!(functions(&optimize_program(program(source))), ["dış", "kullanılan", "bloktaki"]);
+    same_output(source);
+}
+
+#[test]
+fn functions_named_in_strings_are_kept() {
+    let source = "
+işlev yanıtla son
+işlev kullanılmayan son
+\"yanıtla\" de
+";
+    assert_eq!(functions(&optimize_program(program(source))), ["yanıtla"]);
+}
+
+#[test]
+fn positions_are_kept() {
+    let source = "1 2 +\n\"a\" de";
+    let original = program(source);
+    let optimized = optimize(program(source));
+    let de = |p: &Program| p.spans[p.code.iter().position(|i| *i == Instruction::De).unwrap()];
+    assert_eq!(de(&optimized), de(&original));
+}
+
+#[test]
+fn optimized_programs_run_the_same() {
+    same_output(
+        "
+işlev faktöriyel -> n
+    n 1 > ise
+        n 1 - -> m
+        n m faktöriyel * ver
+    son
+    1 ver
+son
+3 4 + -> n
+:. n 0 > iken
+    n faktöriyel de \"\\n\" de
+    n 2 - -> n
+son
+blok b
+    2 3 * -> c
+son
+b:c de
+",
+    );
+}
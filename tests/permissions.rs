@@ -0,0 +1,98 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::{assert_error, config, fail_with, program, run_with, Output};
+use tr_lang::bytecode::{Instruction, Program};
+use tr_lang::error::Error;
+use tr_lang::runtime::{Permission, Permissions, RunConfig};
+
+/// A config that allows only what `permissions` allows
+fn allowing(permissions: Permissions) -> impl FnOnce(&Output) -> RunConfig {
+    move |out| RunConfig { permissions, ..config(out) }
+}
+
+/// Asserts that `error` is an `İzinHatası` explained by `tr` or `en`
+fn assert_denied(error: &Error, tr: &str, en: &str) {
+    assert_error(error, "İzinHatası", "İzinHatası");
+    let explanation = error.explanation();
+    assert!(explanation == tr || explanation == en, "{}", explanation);
+}
+
+#[test]
+fn permissions() {
+    let all = Permissions::all();
+    let none = Permissions::none();
+    for p in [Permission::File, Permission::Process, Permission::Network, Permission::Ffi, Permission::Input] {
+        assert!(all.allows(p));
+        assert!(!none.allows(p));
+        assert!(none.allow(p).allows(p));
+        assert!(!all.deny(p).allows(p));
+    }
+    assert_eq!(none.allow(Permission::File), Permissions { file: true, ..none });
+    assert_eq!(all.deny(Permission::Network), Permissions { network: false, ..all });
+    assert_eq!(RunConfig::default().permissions, all);
+}
+
+#[test]
+fn modules_need_their_permission() {
+    for (module, permission) in [
+        ("dosya", Permission::File),
+        ("süreç", Permission::Process),
+        ("ağ", Permission::Network),
+        ("http", Permission::Network),
+    ] {
+        let source = format!("yükle \"{}\" -> m", module);
+        assert_denied(
+            &fail_with(&source, allowing(Permissions::all().deny(permission))),
+            &format!("`{}` modülünü yükleme izni yok", module),
+            &format!("no permission to load the module `{}`", module),
+        );
+        run_with(program(&source), allowing(Permissions::none().allow(permission)))
+            .1
+            .unwrap_or_else(|e| panic!("{}: {}", module, e.explanation()));
+    }
+    for module in ["kodlama", "şifreleme"] {
+        let source = format!("yükle \"{}\" -> m", module);
+        run_with(program(&source), allowing(Permissions::none()))
+            .1
+            .unwrap_or_else(|e| panic!("{}: {}", module, e.explanation()));
+    }
+}
+
+#[test]
+fn input_needs_its_permission() {
+    let source = "girdi de";
+    assert_denied(
+        &fail_with(source, allowing(Permissions::all().deny(Permission::Input))),
+        "girdi okuma izni yok",
+        "no permission to read input",
+    );
+    let (out, res) = run_with(program(source), |out| RunConfig {
+        stdin: Some(Box::new(Cursor::new("merhaba\n"))),
+        ..allowing(Permissions::none().allow(Permission::Input))(out)
+    });
+    res.unwrap_or_else(|e| panic!("{}", e.explanation()));
+    assert_eq!(out, "merhaba");
+}
+
+/// A program that loads the shared library `name`
+fn library(name: &str) -> Program {
+    let mut program = program("1");
+    program.names.push(name.to_string());
+    program.code = vec![Instruction::LibSymbol(program.names.len() as u32 - 1)];
+    program
+}
+
+#[test]
+fn libraries_need_the_ffi_permission() {
+    let (_, res) = run_with(library("libyok.so"), allowing(Permissions::all().deny(Permission::Ffi)));
+    assert_denied(
+        &res.unwrap_err(),
+        "`libyok.so` kütüphanesini yükleme izni yok",
+        "no permission to load the library `libyok.so`",
+    );
+    // allowed, it fails to load instead
+    let (_, res) = run_with(library("libyok.so"), allowing(Permissions::none().allow(Permission::Ffi)));
+    assert_eq!(res.unwrap_err().name(), "DinamikYüklemeHatası");
+}
@@ -0,0 +1,201 @@
+mod common;
+
+use common::{assert_error, config, fail_with, Output};
+use tr_lang::error::Error;
+use tr_lang::runtime::RunConfig;
+
+/// A config that allows at most `max_depth` functions at once
+fn depth(max_depth: usize) -> impl FnOnce(&Output) -> RunConfig {
+    move |out| RunConfig { max_depth, ..config(out) }
+}
+
+/// Runs `source` allowing at most `max_depth` functions at once, returning what it wrote
+fn run_at(source: &str, max_depth: usize) -> String {
+    let (out, res) = common::run_with(common::program(source), depth(max_depth));
+    if let Err(e) = res {
+        panic!("{}\n{}: {}", out, e.name(), e.explanation());
+    }
+    out
+}
+
+/// The function names in the traceback of `error`, `-` for lines outside functions
+fn calls(error: &Error) -> Vec<String> {
+    error
+        .traceback()
+        .iter()
+        .map(|p| {
+            let p = format!("{:?}", p);
+            match p.split_once("function_name: Some(\"") {
+                Some((_, name)) => name.trim_end_matches("\") }").to_string(),
+                None => "-".to_string(),
+            }
+        })
+        .collect()
+}
+
+const COUNT: &str = "
+işlev say -> n
+    n 0 > ise
+        n 1 - -> m
+        m say
+    yoksa
+        \"bitti\" de
+    son
+son
+";
+
+#[test]
+fn tail_calls_take_over_the_frame_of_their_caller() {
+    let source = format!("{}\n100000 say", COUNT);
+    assert_eq!(run_at(&source, 2), "bitti");
+}
+
+#[test]
+fn mutual_tail_calls() {
+    let source = "
+işlev çift -> n
+    n 0 = ise
+        \"çift\" de
+    yoksa
+        n 1 - -> m
+        m tek
+    son
+son
+işlev tek -> n
+    n 0 = ise
+        \"tek\" de
+    yoksa
+        n 1 - -> m
+        m çift
+    son
+son
+10001 çift
+";
+    assert_eq!(run_at(source, 4), "tek");
+}
+
+#[test]
+fn calls_before_ver_are_tail_calls() {
+    let source = "
+işlev faktöriyel -> n -> sonuç
+    n 0 > ise
+        sonuç n * -> sonuç
+        n 1 - -> n
+        sonuç n faktöriyel ver
+    son
+    sonuç ver
+son
+1 20 faktöriyel de
+";
+    assert_eq!(run_at(source, 2), "2432902008176640000");
+    let sum = source.replace("sonuç n * ->", "sonuç n + ->").replace("1 20 faktöriyel", "0 100000 faktöriyel");
+    assert_eq!(run_at(&sum, 2), "5000050000");
+}
+
+#[test]
+fn tail_calls_before_ver_return_what_is_under_their_arguments_if_nothing_is_returned() {
+    let source = "
+işlev g -> x
+    x de
+son
+işlev f -> x
+    1 x g ver
+son
+5 f de
+";
+    assert_eq!(run_at(source, 2), "51");
+
+    // even after a tail call that returns nothing through the end of a function
+    let source = "
+işlev yaz -> x
+    x de
+son
+işlev g -> x
+    x yaz
+son
+işlev f -> x
+    1 x g ver
+son
+5 f de
+";
+    assert_eq!(run_at(source, 2), "51");
+
+    // while what is returned to the end of a function is dropped
+    let source = "
+işlev g -> x
+    x ver
+son
+işlev f -> x
+    x g
+son
+5 f
+\"boş\" de
+";
+    assert_eq!(run_at(source, 2), "boş");
+}
+
+#[test]
+fn tail_calls_are_elided_from_tracebacks() {
+    let source = format!("{}\n3 say", COUNT.replace("\"bitti\" de", "yok"));
+    let e = fail_with(&source, config);
+    assert_error(&e, "BilinmeyenTanımlayıcı", "BilinmeyenTanımlayıcı");
+    let last_call = &calls(&e)[calls(&e).len() - 2];
+    assert!(
+        last_call == "say (2 tail calls elided)" || last_call == "say (2 kuyruk çağrısı gösterilmiyor)",
+        "{}",
+        last_call,
+    );
+}
+
+#[test]
+fn going_too_deep_is_a_recursion_limit() {
+    let source = "
+işlev say -> n
+    n 0 > ise
+        n 1 - -> m
+        m say
+        \"geri dönüldü\" de
+    son
+son
+100 say
+";
+    let (out, res) = common::run_with(common::program(source), depth(20));
+    assert_eq!(out, "");
+    let e = res.unwrap_err();
+    assert_error(&e, "ÖzyinelemeSınırı", "RecursionLimit");
+    // the calls that repeat are collapsed into one
+    let calls = calls(&e);
+    assert_eq!(calls.len(), 2);
+    assert!(
+        calls[1] == "say (repeated 20 times)" || calls[1] == "say (20 kez tekrarlandı)",
+        "{}",
+        calls[1],
+    );
+
+    assert_eq!(run_at(source, 101), "geri dönüldü".repeat(100));
+}
+
+#[test]
+fn repeating_cycles_of_calls_are_collapsed() {
+    let source = "
+işlev a -> n
+    n 1 - -> m
+    m b
+    1 at
+son
+işlev b -> n
+    n a
+    1 at
+son
+10 a
+";
+    let e = fail_with(source, depth(10));
+    assert_error(&e, "ÖzyinelemeSınırı", "RecursionLimit");
+    let calls = calls(&e);
+    assert!(
+        calls.iter().any(|c| c.contains("last 2 calls repeated") || c.contains("son 2 çağrı")),
+        "{:?}",
+        calls,
+    );
+    assert!(calls.len() < 6, "{:?}", calls);
+}
@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_test::{assert_tokens, Token};
+use tr_lang::ffi::{FfiFunction, FfiObject};
+use tr_lang::mem::{List, Map, Object};
+
+/// An object compared with `=`, which `assert_tokens` needs
+#[derive(Serialize, Deserialize)]
+#[serde(transparent)]
+struct Same(Object);
+
+impl PartialEq for Same {
+    fn eq(&self, other: &Self) -> bool {
+        same(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for Same {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+
+fn same(a: &Object, b: &Object) -> bool {
+    matches!(a.eşittir(b.clone()), Ok(Object::Bool(true)))
+}
+
+/// `o` written as JSON and read back
+fn through_json(o: &Object) -> Object {
+    serde_json::from_str(&serde_json::to_string(o).unwrap()).unwrap()
+}
+
+fn nested() -> Object {
+    let inner = Map::from(HashMap::from([
+        ("hiç".to_string(), Object::Hiç),
+        ("liste".to_string(), Object::Liste(List::new(vec![Object::Bool(true), Object::Sayı(-1.5)]))),
+    ]));
+    Object::Liste(List::new(vec![
+        Object::Yazı("merhaba".to_string()),
+        Object::Harita(inner),
+        Object::Liste(List::new(vec![])),
+        Object::Hiç,
+    ]))
+}
+
+#[test]
+fn objects_round_trip_through_self_describing_formats() {
+    let o = nested();
+    assert!(same(&through_json(&o), &o), "{:?}", through_json(&o));
+    assert_eq!(serde_json::to_string(&Object::Hiç).unwrap(), "null");
+    assert!(matches!(through_json(&Object::Hiç), Object::Hiç));
+
+    // `Baytlar` are written as bytes, which JSON doesn't have but other formats do
+    assert_tokens(&Same(Object::Baytlar(vec![0, 255])), &[Token::Bytes(&[0, 255])]);
+    assert_tokens(
+        &Same(Object::Liste(List::new(vec![Object::Baytlar(vec![1]), Object::Hiç]))),
+        &[
+            Token::Seq { len: Some(2) },
+            Token::Bytes(&[1]),
+            Token::Unit,
+            Token::SeqEnd,
+        ],
+    );
+    assert_eq!(format!("{:?}", through_json(&Object::Baytlar(vec![1, 2]))), "[1, 2]");
+
+    // and maps, on their own as well
+    let map = Map::from(HashMap::from([("a".to_string(), nested())]));
+    let back: Map = serde_json::from_str(&serde_json::to_string(&map).unwrap()).unwrap();
+    assert!(same(&Object::Harita(back), &Object::Harita(map)));
+}
+
+#[derive(Clone)]
+struct Nesne;
+
+impl FfiObject for Nesne {
+    fn repr(&self) -> String {
+        "<nesne>".to_string()
+    }
+    fn destroy(&mut self) {}
+}
+
+#[test]
+fn functions_and_ffi_objects_can_not_be_serialized() {
+    let objects = [
+        Object::İşlev(0),
+        Object::FfiFunction(FfiFunction::new(|_, _| Ok(None))),
+        Object::FfiObject(Box::new(Nesne)),
+    ];
+    for (o, name) in objects.iter().zip(["işlev", "işlev", "nesne"]) {
+        let e = serde_json::to_string(o).unwrap_err().to_string();
+        assert!(
+            e == format!("{} serileştirilemez", name)
+                || e == format!("{}s can't be serialized", if name == "nesne" { "object" } else { "function" }),
+            "{}",
+            e,
+        );
+    }
+    // even inside of lists
+    let e = serde_json::to_string(&Object::Liste(List::new(vec![Object::İşlev(0)]))).unwrap_err();
+    assert!(e.to_string().contains("işlev") || e.to_string().contains("function"), "{}", e);
+}
+
+#[test]
+fn objects_can_not_be_read_from_formats_that_are_not_self_describing() {
+    let bytes = bincode::serialize(&Object::Sayı(1.)).unwrap();
+    assert!(bincode::deserialize::<Object>(&bytes).is_err());
+}
@@ -0,0 +1,133 @@
+mod common;
+
+use common::{assert_error, fail, program, run, Output};
+use tr_lang::bytecode::{Instruction, Program};
+use tr_lang::mem::{HashMemory, Object, StackMemory};
+use tr_lang::runtime::{Run, RunConfig};
+
+/// How every lookup of `name` in `program` was resolved
+fn lookups(program: &Program, name: &str) -> Vec<&'static str> {
+    program
+        .code
+        .iter()
+        .filter_map(|ins| match *ins {
+            Instruction::Identifier(i) if program.names[i as usize] == name => Some("Identifier"),
+            Instruction::Yerel(i) if program.names[i as usize] == name => Some("Yerel"),
+            Instruction::Global(i) if program.names[i as usize] == name => Some("Global"),
+            _ => None,
+        })
+        .collect()
+}
+
+const SOURCE: &str = "
+işlev f
+    x de
+    3 -> y
+    y de
+son
+1 -> x
+f
+doğru ise 1 -> z son
+z de
+blok b
+    2 -> w
+    w de
+    x de
+son
+";
+
+#[test]
+fn lookups_are_resolved_where_they_can_be() {
+    let program = program(SOURCE);
+    // functions see the variables of whatever called them
+    assert_eq!(lookups(&program, "x"), ["Identifier", "Global"]);
+    assert_eq!(lookups(&program, "y"), ["Yerel"]);
+    assert_eq!(lookups(&program, "f"), ["Yerel"]);
+    // not set on every path, but nothing other than the global scope can have it
+    assert_eq!(lookups(&program, "z"), ["Global"]);
+    assert_eq!(lookups(&program, "w"), ["Yerel"]);
+    assert_eq!(run(SOURCE), "13121");
+}
+
+#[test]
+fn functions_see_the_scopes_of_their_callers() {
+    let source = "
+işlev göster
+    x de
+son
+işlev çağır
+    2 -> x
+    göster
+son
+1 -> x
+göster
+çağır
+göster
+";
+    assert_eq!(run(source), "121");
+}
+
+#[test]
+fn names_set_on_some_paths_are_looked_up_at_run_time() {
+    let source = "
+işlev f -> koşul
+    koşul ise 2 -> x son
+    x de
+son
+1 -> x
+doğru f
+yanlış f
+";
+    assert_eq!(lookups(&program(source), "x"), ["Identifier"]);
+    assert_eq!(run(source), "21");
+    assert_error(&fail("yanlış ise 1 -> x son x de"), "BilinmeyenTanımlayıcı", "BilinmeyenTanımlayıcı");
+}
+
+#[test]
+fn imports_set_names() {
+    let import = |what: &str| {
+        format!(
+            "
+blok b
+    1 -> x
+son
+işlev f
+    yükle ({})
+    x de
+son
+f
+",
+            what,
+        )
+    };
+    assert_eq!(lookups(&program(&import("b:x")), "x"), ["Yerel"]);
+    assert_eq!(run(&import("b:x")), "1");
+    // what is imported is only known at run time
+    assert_eq!(lookups(&program(&import("b:*")), "x"), ["Identifier"]);
+    assert_eq!(run(&import("b:*")), "1");
+}
+
+#[test]
+fn names_are_found_in_the_memories_a_run_is_given() {
+    let mut hashs = HashMemory::new();
+    hashs.insert("x".to_string(), Object::Sayı(5.0));
+    hashs.insert("önceki".to_string(), Object::Sayı(1.0));
+    let out = Output::default();
+    let mut run = Run::new(program("x de 6 -> y"));
+    let (_, mut hashs) = run
+        .run(RunConfig { mem: (StackMemory::new(), hashs), ..common::config(&out) })
+        .unwrap_or_else(|(_, _, e)| panic!("{}", e.explanation()));
+    assert_eq!(out.text(), "5");
+    assert_eq!(format!("{:?}", hashs.get(&"y".to_string())), "Some(6)");
+
+    // and again, with memories whose names are in another order
+    let mut other = HashMemory::new();
+    other.insert("y".to_string(), Object::Sayı(0.0));
+    other.insert("x".to_string(), Object::Yazı("yeni".to_string()));
+    let (_, mut other) = run
+        .run(RunConfig { mem: (StackMemory::new(), other), ..common::config(&out) })
+        .unwrap_or_else(|(_, _, e)| panic!("{}", e.explanation()));
+    assert_eq!(out.text(), "5yeni");
+    assert_eq!(format!("{:?}", other.get(&"y".to_string())), "Some(6)");
+    assert_eq!(format!("{:?}", hashs.get(&"önceki".to_string())), "Some(1)");
+}
@@ -0,0 +1,156 @@
+mod common;
+
+use common::{assert_error, config, program, run, Output};
+use tr_lang::bytecode::{Instruction, Program};
+use tr_lang::error::Error;
+use tr_lang::ffi::FfiFunction;
+use tr_lang::mem::{HashMemory, Object, StackMemory};
+use tr_lang::runtime::{Permission, Permissions, Run, RunConfig, Snapshot, SNAPSHOT_MAGIC, SNAPSHOT_VERSION};
+
+const SOURCE: &str = "
+yükle \"kodlama\" -> k
+işlev kare -> x
+    x x * ver
+son
+işlev say -> n
+    n 0 > ise
+        n kare de \" \" de
+        n 1 - -> m
+        m say
+    son
+son
+blok b
+    \"blok\" -> ad
+    ad de
+son
+0 -> i
+:. i 3 < iken
+    i de
+    i 1 + -> i
+son
+4 say
+\"merhaba\" k:base64-kodla de
+";
+
+/// Runs `source` until it is out of `steps` instructions, then saves it, restores it and runs it
+/// to the end, returning what it wrote or `None` if it didn't run out
+fn saved_after(source: &str, steps: u64) -> Option<String> {
+    let out = Output::default();
+    let mut run = Run::new(program(source));
+    let (stack, hashs, e) = match run.run(RunConfig { max_instructions: Some(steps), ..config(&out) }) {
+        Ok(_) => return None,
+        Err(stopped) => stopped,
+    };
+    assert_error(&e, "KomutSınırı", "InstructionLimit");
+    let bytes = run
+        .snapshot(&stack, &hashs)
+        .unwrap_or_else(|e| panic!("{}: {}", e.explanation(), e.after_note().unwrap_or_default()))
+        .to_bytes();
+    drop((run, stack, hashs));
+
+    let snapshot = Snapshot::from_bytes(&bytes).unwrap_or_else(|e| panic!("{}", e.explanation()));
+    let (mut run, stack, hashs) =
+        Run::restore(snapshot, Permissions::all()).unwrap_or_else(|e| panic!("{}", e.explanation()));
+    run.resume(RunConfig { mem: (stack, hashs), ..config(&out) })
+        .unwrap_or_else(|(_, _, e)| panic!("after {} steps: {}", steps, e.explanation()));
+    Some(out.text())
+}
+
+#[test]
+fn restored_runs_carry_on_where_they_were_saved() {
+    let expected = run(SOURCE);
+    assert_eq!(expected, "blok01216 9 4 1 bWVyaGFiYQ==");
+    // saved at every instruction, in functions, blocks and loops alike
+    let mut steps = 1;
+    while let Some(out) = saved_after(SOURCE, steps) {
+        assert_eq!(out, expected, "saved after {} steps", steps);
+        steps += 1;
+    }
+    assert!(steps > 50, "{}", steps);
+}
+
+#[test]
+fn snapshots_start_with_their_magic_and_version() {
+    let out = Output::default();
+    let mut run = Run::new(program("1 -> x"));
+    let (stack, hashs) = run.run(config(&out)).unwrap_or_else(|(_, _, e)| panic!("{}", e.explanation()));
+    let bytes = run.snapshot(&stack, &hashs).unwrap().to_bytes();
+    assert_eq!(&bytes[..4], SNAPSHOT_MAGIC);
+    assert_eq!(bytes[4..6], SNAPSHOT_VERSION.to_le_bytes());
+}
+
+/// Reads `bytes` as a snapshot and restores it, expecting it to fail
+fn rejected(bytes: &[u8], permissions: Permissions) -> Error {
+    match Snapshot::from_bytes(bytes).and_then(|s| Run::restore(s, permissions)) {
+        Ok(_) => panic!("the snapshot was restored"),
+        Err(e) => e,
+    }
+}
+
+/// The bytes of a snapshot of `source` run to the end
+fn saved(source: &str) -> Vec<u8> {
+    let out = Output::default();
+    let mut run = Run::new(program(source));
+    let (stack, hashs) = run.run(config(&out)).unwrap_or_else(|(_, _, e)| panic!("{}", e.explanation()));
+    run.snapshot(&stack, &hashs).unwrap().to_bytes()
+}
+
+#[test]
+fn bad_snapshots_are_rejected() {
+    let bytes = saved("1 -> x");
+
+    let e = rejected(b"TRBY\x02\x00", Permissions::all());
+    assert_error(&e, "KayıtHatası", "SnapshotError");
+    assert!(
+        ["dosya bir tr-lang kaydı değil", "file is not a tr-lang snapshot"].contains(&e.explanation().as_str()),
+        "{}",
+        e.explanation(),
+    );
+    assert_error(&rejected(&bytes[..3], Permissions::all()), "KayıtHatası", "SnapshotError");
+
+    let mut newer = bytes.clone();
+    newer[4..6].copy_from_slice(&(SNAPSHOT_VERSION + 1).to_le_bytes());
+    let e = rejected(&newer, Permissions::all());
+    assert_error(&e, "KayıtHatası", "SnapshotError");
+    assert!(e.explanation().contains(&(SNAPSHOT_VERSION + 1).to_string()), "{}", e.explanation());
+
+    assert_error(&rejected(&bytes[..bytes.len() - 1], Permissions::all()), "KayıtHatası", "SnapshotError");
+}
+
+#[test]
+fn restoring_needs_the_permissions_of_the_modules_loaded() {
+    let bytes = saved("yükle \"süreç\" -> s");
+    let e = rejected(&bytes, Permissions::all().deny(Permission::Process));
+    assert_error(&e, "İzinHatası", "İzinHatası");
+    assert!(e.explanation().contains("süreç"), "{}", e.explanation());
+    Run::restore(Snapshot::from_bytes(&bytes).unwrap(), Permissions::none().allow(Permission::Process))
+        .unwrap_or_else(|e| panic!("{}", e.explanation()));
+}
+
+#[test]
+fn what_can_not_be_saved_is_listed() {
+    let out = Output::default();
+    let mut run = Run::new(program("1 -> x"));
+    let (mut stack, mut hashs) = run.run(config(&out)).unwrap_or_else(|(_, _, e)| panic!("{}", e.explanation()));
+    let f = Object::FfiFunction(FfiFunction::new(|_, _| Ok(None)));
+    hashs.insert("özel".to_string(), f.clone());
+    stack.push(f);
+    let e = run.snapshot(&stack, &hashs).err().unwrap();
+    assert_error(&e, "KayıtHatası", "SnapshotError");
+    assert!(
+        ["2 değer kaydedilemez", "2 values can't be saved"].contains(&e.explanation().as_str()),
+        "{}",
+        e.explanation(),
+    );
+    let note = e.after_note().unwrap();
+    assert!(note.contains("`özel`"), "{}", note);
+    assert!(note.contains("<küme>") || note.contains("<stack>"), "{}", note);
+
+    // nor can programs that load shared libraries
+    let mut program: Program = program("1");
+    program.names.push("libyok.so".to_string());
+    program.code = vec![Instruction::LibSymbol(program.names.len() as u32 - 1)];
+    let e = Run::new(program).snapshot(&StackMemory::new(), &HashMemory::new()).err().unwrap();
+    assert_error(&e, "KayıtHatası", "SnapshotError");
+    assert!(e.after_note().unwrap().contains("libyok.so"));
+}
@@ -0,0 +1,157 @@
+mod common;
+
+use common::{assert_error, config, program, Output};
+use tr_lang::mem::Object;
+use tr_lang::runtime::{Run, RunConfig, Status};
+
+const SOURCE: &str = "
+0 -> i
+:. i 3 < iken
+    i de
+    i 1 + -> i
+son
+\"bitti\" de
+";
+
+#[test]
+fn runs_can_be_stepped_through() {
+    let out = Output::default();
+    let mut run = Run::new(program("1 de 2 de"));
+    let (mut stack, mut hashs) = run.start(config(&out));
+    assert_eq!(run.current(), 0);
+    assert_eq!(run.step(&mut stack, &mut hashs).unwrap(), Status::Running);
+    assert_eq!(run.current(), 1);
+    assert_eq!(stack.len(), 1);
+    assert_eq!(run.step(&mut stack, &mut hashs).unwrap(), Status::Running);
+    assert_eq!(out.text(), "1");
+    assert_eq!(run.step(&mut stack, &mut hashs).unwrap(), Status::Running);
+    assert_eq!(run.step(&mut stack, &mut hashs).unwrap(), Status::Finished);
+    assert_eq!(out.text(), "12");
+    assert_eq!(run.current(), run.program().code.len());
+}
+
+#[test]
+fn run_until_runs_at_most_as_many_instructions_as_it_is_given() {
+    let out = Output::default();
+    let mut run = Run::new(program(SOURCE));
+    let (mut stack, mut hashs) = run.start(config(&out));
+    let mut calls = 0;
+    while run.run_until(5, &mut stack, &mut hashs).unwrap() == Status::Running {
+        calls += 1;
+    }
+    assert_eq!(out.text(), "012bitti");
+    assert!(calls > 3, "{}", calls);
+
+    // and started again, runs from the start
+    let (mut stack, mut hashs) = run.start(config(&out));
+    assert_eq!(run.run_until(u64::MAX, &mut stack, &mut hashs).unwrap(), Status::Finished);
+    assert_eq!(out.text(), "012bitti012bitti");
+}
+
+#[test]
+fn breakpoints() {
+    let out = Output::default();
+    let mut run = Run::new(program(SOURCE));
+    let printing = run.program().at_line("test.trl", 4).unwrap();
+    let end = run.program().at_line("test.trl", 7).unwrap();
+    assert_eq!(run.program().at_line("test.trl", 100), None);
+    assert_eq!(run.program().at_line("başka.trl", 4), None);
+    run.add_breakpoint(end);
+    run.add_breakpoint(printing);
+    assert_eq!(run.breakpoints().collect::<Vec<_>>(), [printing, end]);
+
+    let (mut stack, mut hashs) = run.start(config(&out));
+    // stopped before every time the line is run
+    for i in 0..3 {
+        assert_eq!(run.run_until(u64::MAX, &mut stack, &mut hashs).unwrap(), Status::Breakpoint(printing));
+        assert_eq!(run.current(), printing);
+        assert_eq!(out.text(), "012"[..i]);
+    }
+    assert!(run.remove_breakpoint(end));
+    assert!(!run.remove_breakpoint(end));
+    assert_eq!(run.run_until(u64::MAX, &mut stack, &mut hashs).unwrap(), Status::Finished);
+    assert_eq!(out.text(), "012bitti");
+}
+
+#[test]
+fn breakpoints_at_the_first_instruction() {
+    let out = Output::default();
+    let mut run = Run::new(program("1 de 2 de"));
+    run.add_breakpoint(0);
+    let (mut stack, mut hashs) = run.start(config(&out));
+    assert_eq!(run.run_until(u64::MAX, &mut stack, &mut hashs).unwrap(), Status::Breakpoint(0));
+    assert_eq!(out.text(), "");
+    assert_eq!(run.run_until(u64::MAX, &mut stack, &mut hashs).unwrap(), Status::Finished);
+    assert_eq!(out.text(), "12");
+
+    // and again when started over
+    let (mut stack, mut hashs) = run.start(config(&out));
+    assert_eq!(run.step(&mut stack, &mut hashs).unwrap(), Status::Breakpoint(0));
+    assert_eq!(run.current(), 0);
+}
+
+#[test]
+fn stepping_onto_a_breakpoint() {
+    let out = Output::default();
+    let mut run = Run::new(program("1 de 2 de"));
+    run.add_breakpoint(1);
+    run.add_breakpoint(2);
+    let (mut stack, mut hashs) = run.start(config(&out));
+    assert_eq!(run.step(&mut stack, &mut hashs).unwrap(), Status::Running);
+    // the next instruction is at a breakpoint, which is stopped at before it is run
+    assert_eq!(run.run_until(u64::MAX, &mut stack, &mut hashs).unwrap(), Status::Breakpoint(1));
+    assert_eq!(out.text(), "");
+    // stepping from a breakpoint runs it
+    assert_eq!(run.step(&mut stack, &mut hashs).unwrap(), Status::Running);
+    assert_eq!(out.text(), "1");
+    assert_eq!(run.run_until(u64::MAX, &mut stack, &mut hashs).unwrap(), Status::Breakpoint(2));
+    assert_eq!(run.current(), 2);
+    assert_eq!(run.run_until(u64::MAX, &mut stack, &mut hashs).unwrap(), Status::Finished);
+    assert_eq!(out.text(), "12");
+}
+
+#[test]
+fn memories_can_be_changed_between_steps() {
+    let out = Output::default();
+    let mut run = Run::new(program(SOURCE));
+    run.add_breakpoint(run.program().at_line("test.trl", 4).unwrap());
+    let (mut stack, mut hashs) = run.start(config(&out));
+    assert!(matches!(run.run_until(u64::MAX, &mut stack, &mut hashs), Ok(Status::Breakpoint(_))));
+    assert_eq!(format!("{:?}", hashs.get(&"i".to_string())), "Some(0)");
+    hashs.insert("i".to_string(), Object::Sayı(2.0));
+    assert!(matches!(run.run_until(u64::MAX, &mut stack, &mut hashs), Ok(Status::Finished)));
+    assert_eq!(out.text(), "2bitti");
+
+    // values put on the stack are used by what comes next
+    let mut run = Run::new(program("de"));
+    let (mut stack, mut hashs) = run.start(config(&out));
+    stack.push(Object::Yazı("!".to_string()));
+    assert!(matches!(run.run_until(u64::MAX, &mut stack, &mut hashs), Ok(Status::Finished)));
+    assert_eq!(out.text(), "2bitti!");
+}
+
+#[test]
+fn limits_count_every_step() {
+    let out = Output::default();
+    let mut run = Run::new(program("1 de 2 de 3 de"));
+    let (mut stack, mut hashs) = run.start(RunConfig { max_instructions: Some(4), ..config(&out) });
+    for _ in 0..4 {
+        assert_eq!(run.step(&mut stack, &mut hashs).unwrap(), Status::Running);
+    }
+    let e = run.run_until(10, &mut stack, &mut hashs).unwrap_err();
+    assert_error(&e, "KomutSınırı", "InstructionLimit");
+    assert_eq!(out.text(), "12");
+}
+
+#[test]
+fn failed_instructions_are_run_again() {
+    let out = Output::default();
+    let mut run = Run::new(program("x de"));
+    let (mut stack, mut hashs) = run.start(config(&out));
+    let e = run.step(&mut stack, &mut hashs).unwrap_err();
+    assert_error(&e, "BilinmeyenTanımlayıcı", "BilinmeyenTanımlayıcı");
+    assert_eq!(run.current(), 0);
+    hashs.insert("x".to_string(), Object::Sayı(1.0));
+    assert_eq!(run.run_until(u64::MAX, &mut stack, &mut hashs).unwrap(), Status::Finished);
+    assert_eq!(out.text(), "1");
+}
@@ -0,0 +1,114 @@
+mod common;
+
+use common::{assert_error, fail, program, run, run_with};
+use tr_lang::error::Position;
+
+#[test]
+fn arithmetic_follows_precedence() {
+    assert_eq!(run("3 + 4 * 2 de"), "11");
+    assert_eq!(run("(3 + 4) * 2 de"), "14");
+    assert_eq!(run("7 % 4 de \"\\n\" de 1 - 0.5 de"), "3\n0.5");
+}
+
+#[test]
+fn stack_operations() {
+    assert_eq!(run("1 2 tks de de"), "12");
+    assert_eq!(run("1 kpy de de"), "11");
+    assert_eq!(run("1 2 üst de de de"), "121");
+    assert_eq!(run("1 2 3 dön de de de"), "123");
+    assert_eq!(run("1 2 at de"), "1");
+}
+
+#[test]
+fn strings_and_conversions() {
+    assert_eq!(run("\"merhaba \" \"dünya\" + de"), "merhaba dünya");
+    assert_eq!(run("\"12\"@sayı 1 + de"), "13");
+    assert_eq!(run("1923@bool@yazı de"), "doğru");
+}
+
+#[test]
+fn bytes_only_support_their_own_operators() {
+    assert_eq!(run("b\"ab\" b\"c\" + -> x x b\"abc\" = de"), "doğru");
+    for source in [
+        "b\"ab\" b\"ac\" <",
+        "b\"a\" b\"a\" >=",
+        "b\"a\" 2 *",
+        "2 b\"a\" *",
+        "b\"a\" 1 -",
+        "\"a\" b\"a\" +",
+        "1 b\"a\" =",
+        "b\"a\" b\"a\" /",
+        "b\"a\" b\"a\" %",
+        "doğru b\"a\" +",
+        "b\"a\" !",
+    ] {
+        assert_error(&fail(&format!("{} de", source)), "BeklenmedikTip", "BeklenmedikTip");
+    }
+}
+
+#[test]
+fn branches_and_loops() {
+    assert_eq!(run("1 2 < ise \"a\" de yoksa \"b\" de son"), "a");
+    assert_eq!(run("1 2 > ise \"a\" de yoksa \"b\" de son"), "b");
+    assert_eq!(run("3 :. kpy 0 > iken kpy de -- son at"), "321");
+}
+
+#[test]
+fn functions_and_recursion() {
+    let source = "
+işlev faktöriyel -> n
+    n 1 > ise
+        n 1 - -> m
+        n m faktöriyel * ver
+    son
+    1 ver
+son
+10 faktöriyel de
+";
+    assert_eq!(run(source), "3628800");
+}
+
+#[test]
+fn variables_are_scoped_to_functions() {
+    let source = "
+işlev f
+    3 -> x
+    x de
+son
+1 -> x
+f
+x de
+";
+    assert_eq!(run(source), "31");
+}
+
+#[test]
+fn blocks_keep_their_variables() {
+    let source = "
+blok b
+    3 -> a
+    4 -> c
+son
+b:a b:c + de
+";
+    assert_eq!(run(source), "7");
+}
+
+#[test]
+fn what_is_left_is_given_back() {
+    let (out, res) = run_with(program("1 2 \"üç\" 4 -> dört"), common::config);
+    assert_eq!(out, "");
+    let (mut stack, mut hashs) = res.unwrap();
+    assert_eq!(format!("{:?}", stack.iter_vec()), "[1, 2, üç]");
+    assert_eq!(format!("{:?}", hashs.get(&"dört".to_string())), "Some(4)");
+}
+
+#[test]
+fn errors_point_at_where_they_happen() {
+    let e = fail("1 de\n  yok de");
+    assert_error(&e, "BilinmeyenTanımlayıcı", "BilinmeyenTanımlayıcı");
+    let trace = e.traceback();
+    let last = trace.last().unwrap();
+    let expected = Position::new(2, 6, "test.trl".to_string(), None);
+    assert_eq!(format!("{:?}", last), format!("{:?}", expected));
+}